@@ -0,0 +1,173 @@
+//! ASCII armor of the files written by the crate
+//!
+//! An armored object is a [`container`] encoded in base64 between text markers,
+//! so that it survives being pasted in an email or a chat:
+//!
+//! ```text
+//! -----BEGIN MCELIECE PUBLIC KEY-----
+//! Version: 1
+//! Params: n=1024, k=524, t=50
+//!
+//! TUNFTAEBAAAEAAAAAgwAAAAyAAAAAAAB...
+//! -----END MCELIECE PUBLIC KEY-----
+//! ```
+//!
+//! The body is split in lines of 64 characters.
+//! The headers are informative: they are checked against the container when present
+//! and headers of unknown name are ignored.
+//!
+//! [`container`]: ../container/index.html
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::container::{self, CodeParams, ObjectType};
+use crate::{Error, Result};
+
+const BEGIN: &str = "-----BEGIN MCELIECE ";
+const END: &str = "-----END MCELIECE ";
+const DASHES: &str = "-----";
+const LINE_LEN: usize = 64;
+
+impl ObjectType {
+    fn label(self) -> String {
+        self.name().to_uppercase()
+    }
+}
+
+/// Wraps the payload in a container and encodes it in ASCII armor
+pub fn armor(kind: ObjectType, params: &CodeParams, payload: &[u8]) -> String {
+    let body = STANDARD.encode(container::seal(kind, params, payload));
+    let mut text = format!("{}{}{}\n", BEGIN, kind.label(), DASHES);
+    text.push_str(&format!("Version: {}\n", container::VERSION));
+    text.push_str(&format!(
+        "Params: n={}, k={}, t={}\n\n",
+        params.n, params.k, params.t
+    ));
+    for line in body.as_bytes().chunks(LINE_LEN) {
+        // Base64 is ASCII
+        text.push_str(std::str::from_utf8(line).unwrap());
+        text.push('\n');
+    }
+    text.push_str(&format!("{}{}{}\n", END, kind.label(), DASHES));
+    text
+}
+
+/// Decodes an armored object of the given kind
+///
+/// Returns the code parameters and the payload of the container.
+pub fn dearmor(text: &str, kind: ObjectType) -> Result<(CodeParams, Vec<u8>)> {
+    let (_, params, payload) = dearmor_any(text, &[kind])?;
+    Ok((params, payload))
+}
+
+/// Decodes an armored object of any of the given kinds
+///
+/// Also returns the kind of the object.
+pub(crate) fn dearmor_any(
+    text: &str,
+    kinds: &[ObjectType],
+) -> Result<(ObjectType, CodeParams, Vec<u8>)> {
+    let mut lines = text.lines().map(str::trim).skip_while(|l| l.is_empty());
+    let label = lines
+        .next()
+        .and_then(|l| l.strip_prefix(BEGIN))
+        .and_then(|l| l.strip_suffix(DASHES))
+        .ok_or(Error::Malformed("Missing armor header line"))?;
+    let kind = match kinds.iter().find(|kind| kind.label() == label) {
+        Some(&kind) => kind,
+        None => {
+            return Err(Error::UnexpectedObject {
+                expected: kinds[0].name(),
+                found: found_name(label),
+            })
+        }
+    };
+
+    let mut headers = None;
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or(Error::Malformed("Invalid armor header"))?;
+        if name.trim() == "Params" {
+            headers = Some(parse_params(value)?);
+        }
+    }
+
+    let mut body = String::new();
+    let mut ended = false;
+    for line in lines {
+        if let Some(end) = line.strip_prefix(END) {
+            if end.strip_suffix(DASHES) != Some(label) {
+                return Err(Error::Malformed("Mismatched armor footer line"));
+            }
+            ended = true;
+            break;
+        }
+        body.push_str(line);
+    }
+    if !ended {
+        return Err(Error::Truncated(kind.name()));
+    }
+
+    let bytes = STANDARD
+        .decode(body)
+        .map_err(|_| Error::Malformed("Invalid base64 in armor"))?;
+    let (params, payload) = container::open(&bytes, kind)?;
+    if headers.is_some_and(|headers| !headers.matches(&params)) {
+        return Err(Error::Malformed("Armor headers do not match the content"));
+    }
+    Ok((kind, params, payload.to_vec()))
+}
+
+/// Returns true if the bytes start like an armored object
+pub fn is_armored(vec: &[u8]) -> bool {
+    let start = vec
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(vec.len());
+    vec[start..].starts_with(BEGIN.as_bytes())
+}
+
+/// Name of the object of the given label, for error messages
+fn found_name(label: &str) -> &'static str {
+    [
+        ObjectType::PublicKey,
+        ObjectType::SecretKey,
+        ObjectType::Ciphertext,
+        ObjectType::Plaintext,
+        ObjectType::EncryptedSecretKey,
+        ObjectType::KeySeed,
+    ]
+    .iter()
+    .find(|kind| kind.label() == label)
+    .map_or("unknown object", |kind| kind.name())
+}
+
+/// Parses the value of a `Params` header such as `n=1024, k=524, t=50`
+///
+/// The header does not record the field polynomial.
+fn parse_params(value: &str) -> Result<CodeParams> {
+    let (mut n, mut k, mut t) = (None, None, None);
+    for field in value.split(',') {
+        let (name, number) = field
+            .split_once('=')
+            .ok_or(Error::Malformed("Invalid armor parameters"))?;
+        let number = number
+            .trim()
+            .parse()
+            .map_err(|_| Error::Malformed("Invalid armor parameters"))?;
+        match name.trim() {
+            "n" => n = Some(number),
+            "k" => k = Some(number),
+            "t" => t = Some(number),
+            _ => return Err(Error::Malformed("Invalid armor parameters")),
+        }
+    }
+    match (n, k, t) {
+        (Some(n), Some(k), Some(t)) => Ok(CodeParams { n, k, t, poly: 0 }),
+        _ => Err(Error::Malformed("Invalid armor parameters")),
+    }
+}
@@ -0,0 +1,121 @@
+//! CCA2-secure encryption using the Fujisaki-Okamoto transform
+//!
+//! To encrypt a message m, we draw a random seed &sigma; of k bits
+//! and derive the error vector e from a hash of &sigma; and m.
+//! The ciphertext is the McEliece encryption of &sigma; with error e,
+//! followed by m masked with a hash of &sigma;.
+//!
+//! On decryption, the ciphertext minus the error vector found by the decoder must be a codeword
+//! and the error vector must be the one derived from the recovered &sigma; and m
+//! (re-encryption check).
+//! Otherwise, decryption does not fail but returns pseudorandom bytes
+//! derived from the secret rejection value and the ciphertext (implicit rejection).
+//! See Hofheinz, D., Hövelmanns, K., & Kiltz, E. (2017),
+//! A modular analysis of the Fujisaki-Okamoto transformation.
+//! Theory of Cryptography Conference, 341-371.
+
+use log::debug;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::sync::Arc;
+
+use super::{hash, PublicKey, SecretKey};
+use crate::{
+    finite_field::{Field, F2},
+    matrix::RowVec,
+    Error, Result,
+};
+
+/// Hash prefix for the derivation of the error vector
+const ERROR: u8 = 2;
+
+/// Hash prefix for the derivation of the mask
+const MASK: u8 = 3;
+
+/// Hash prefix for implicit rejection
+const REJECTION: u8 = 4;
+
+impl PublicKey {
+    /// Encrypts a message of arbitrary length
+    ///
+    /// The ciphertext layout is:
+    /// - bytes 0-a: McEliece encryption of the seed (see [`RowVec`]::[`to_bytes()`])
+    /// - bytes a-b: message masked with a hash of the seed
+    ///
+    /// [`RowVec`]: ../matrix/struct.RowVec.html
+    /// [`to_bytes()`]: ../matrix/struct.RowVec.html#method.to_bytes
+    pub fn encrypt_cca2(&self, m: &[u8]) -> Vec<u8> {
+        self.encrypt_cca2_with_rng(&mut rand::thread_rng(), m)
+    }
+
+    /// Like encrypt_cca2, except the seed is drawn from rng
+    pub fn encrypt_cca2_with_rng<R>(&self, rng: &mut R, m: &[u8]) -> Vec<u8>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let f2 = Arc::new(F2::generate(()));
+        let sigma = RowVec::random_with_rng(rng, f2, self.k());
+        debug!("Seed:{}", sigma);
+
+        let sigma_bytes = sigma.to_bytes();
+        let e = error_vector(&sigma_bytes, m, self.n(), self.t);
+        let c1 = self.encrypt_with_error(&sigma, &e);
+        let mut c = c1.to_bytes();
+        let mask = hash::expand(MASK, &[&sigma_bytes], m.len());
+        c.extend(m.iter().zip(mask).map(|(x, y)| x ^ y));
+        c
+    }
+}
+
+impl SecretKey {
+    /// Decrypts a ciphertext produced by [`encrypt_cca2()`]
+    ///
+    /// An invalid ciphertext of valid length decrypts to pseudorandom bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Truncated`] if the ciphertext is too short
+    /// and [`Error::DimensionMismatch`] if its first part does not match the code length.
+    ///
+    /// [`Error::Truncated`]: ../enum.Error.html#variant.Truncated
+    /// [`Error::DimensionMismatch`]: ../enum.Error.html#variant.DimensionMismatch
+    ///
+    /// [`encrypt_cca2()`]: struct.PublicKey.html#method.encrypt_cca2
+    pub fn decrypt_cca2(&self, c: &[u8]) -> Result<Vec<u8>> {
+        let n = self.p.len();
+        if c.len() < 4 + crate::div_ceil(n, 8) {
+            return Err(Error::Truncated("ciphertext"));
+        }
+        let (read, c1) = RowVec::from_bytes(c)?;
+        if c1.cols() != n {
+            return Err(Error::DimensionMismatch);
+        }
+        let c2 = &c[read..];
+
+        let reject = || hash::expand(REJECTION, &[&self.rejection, c], c2.len());
+        let (sigma, e) = match self.context().try_decrypt_with_error(&c1) {
+            Ok(decrypted) => decrypted,
+            Err(e) => {
+                debug!("{}: reject the ciphertext", e);
+                return Ok(reject());
+            }
+        };
+        let sigma = sigma.to_bytes();
+        let mask = hash::expand(MASK, &[&sigma], c2.len());
+        let m: Vec<u8> = c2.iter().zip(mask).map(|(x, y)| x ^ y).collect();
+
+        if e == error_vector(&sigma, &m, n, self.goppa.poly().degree()) {
+            Ok(m)
+        } else {
+            debug!("Re-encryption check failed");
+            Ok(reject())
+        }
+    }
+}
+
+/// Derives an error vector of length n and weight t from a hash of the seed and the message
+fn error_vector(sigma: &[u8], m: &[u8], n: usize, t: usize) -> RowVec<F2> {
+    let f2 = Arc::new(F2::generate(()));
+    let mut rng = ChaCha20Rng::from_seed(hash::hash(ERROR, &[sigma, m]));
+    RowVec::random_with_weight_with_rng(&mut rng, f2, n, t)
+}
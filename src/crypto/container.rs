@@ -0,0 +1,377 @@
+//! Versioned container format of the files written by the crate
+//!
+//! Keys, ciphertexts and plaintexts are saved in a container with the following layout:
+//! - bytes 0-3: magic bytes `MCEL`
+//! - byte 4: format version (currently 1)
+//! - byte 5: object type (1: public key, 2: secret key, 3: ciphertext, 4: plaintext,
+//!   5: secret key encrypted under a passphrase, 6: secret key seed)
+//! - bytes 6-9: code length n
+//! - bytes 10-13: code dimension k
+//! - bytes 14-17: correction capacity t
+//! - bytes 18-21: field polynomial of the Goppa code (0 if the object does not record it)
+//! - bytes 22-25: length l of the payload
+//! - bytes 26-26+l: payload (the object encoded with its `to_bytes()` method)
+//! - bytes 26+l-30+l: CRC-32 of all the previous bytes
+//!
+//! Numbers are encoded big-endian. No byte may follow the checksum.
+//! Files without the magic bytes are read as the bare payload they held
+//! before the container format was introduced.
+//! Files in [`armor`] are recognized and decoded when read.
+//!
+//! [`armor`]: ../armor/index.html
+
+use std::{
+    fs::File,
+    io::{Read, Write},
+};
+
+use super::{armor, PublicKey, SecretKey};
+use crate::{finite_field::F2, matrix::RowVec, Error, Result};
+
+/// Magic bytes starting every container
+pub const MAGIC: [u8; 4] = *b"MCEL";
+
+/// Current version of the container format
+pub const VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 26;
+const CHECKSUM_LEN: usize = 4;
+
+/// Kind of object held by a container
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjectType {
+    PublicKey,
+    SecretKey,
+    Ciphertext,
+    Plaintext,
+    EncryptedSecretKey,
+    KeySeed,
+}
+
+impl ObjectType {
+    pub fn name(self) -> &'static str {
+        match self {
+            ObjectType::PublicKey => "public key",
+            ObjectType::SecretKey => "secret key",
+            ObjectType::Ciphertext => "ciphertext",
+            ObjectType::Plaintext => "plaintext",
+            ObjectType::EncryptedSecretKey => "encrypted secret key",
+            ObjectType::KeySeed => "secret key seed",
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            ObjectType::PublicKey => 1,
+            ObjectType::SecretKey => 2,
+            ObjectType::Ciphertext => 3,
+            ObjectType::Plaintext => 4,
+            ObjectType::EncryptedSecretKey => 5,
+            ObjectType::KeySeed => 6,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            1 => Ok(ObjectType::PublicKey),
+            2 => Ok(ObjectType::SecretKey),
+            3 => Ok(ObjectType::Ciphertext),
+            4 => Ok(ObjectType::Plaintext),
+            5 => Ok(ObjectType::EncryptedSecretKey),
+            6 => Ok(ObjectType::KeySeed),
+            _ => Err(Error::Malformed("Unknown object type")),
+        }
+    }
+}
+
+/// Parameters of the code an object belongs to
+///
+/// The field polynomial is 0 for objects which do not record the field of the Goppa code,
+/// like public keys and vectors written with the parameters of a public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CodeParams {
+    pub n: usize,
+    pub k: usize,
+    pub t: usize,
+    pub poly: u32,
+}
+
+impl CodeParams {
+    /// Returns true if both parameters may describe the same code
+    ///
+    /// The field polynomials are only compared if both are recorded.
+    pub fn matches(&self, other: &CodeParams) -> bool {
+        (self.n, self.k, self.t) == (other.n, other.k, other.t)
+            && (self.poly == 0 || other.poly == 0 || self.poly == other.poly)
+    }
+}
+
+impl PublicKey {
+    pub fn params(&self) -> CodeParams {
+        CodeParams {
+            n: self.n(),
+            k: self.k(),
+            t: self.t(),
+            poly: 0,
+        }
+    }
+}
+
+impl SecretKey {
+    pub fn params(&self) -> CodeParams {
+        CodeParams {
+            n: self.goppa.len(),
+            k: self.s.rows(),
+            t: self.goppa.poly().degree(),
+            poly: self.goppa.field().poly(),
+        }
+    }
+}
+
+/// Wraps the payload in a container
+pub fn seal(kind: ObjectType, params: &CodeParams, payload: &[u8]) -> Vec<u8> {
+    let mut vec = Vec::with_capacity(HEADER_LEN + payload.len() + CHECKSUM_LEN);
+    vec.extend_from_slice(&MAGIC);
+    vec.push(VERSION);
+    vec.push(kind.to_byte());
+    for &x in &[params.n, params.k, params.t] {
+        vec.extend_from_slice(&(x as u32).to_be_bytes());
+    }
+    vec.extend_from_slice(&params.poly.to_be_bytes());
+    vec.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    vec.extend_from_slice(payload);
+    let checksum = crc32fast::hash(&vec);
+    vec.extend_from_slice(&checksum.to_be_bytes());
+    vec
+}
+
+/// Unwraps a container holding an object of the given kind
+///
+/// Checks the magic bytes, the version, the object type, the length and the checksum,
+/// and that nothing follows the checksum.
+/// Returns the code parameters and the payload.
+pub fn open(vec: &[u8], kind: ObjectType) -> Result<(CodeParams, &[u8])> {
+    let (_, params, payload) = open_any(vec, &[kind])?;
+    Ok((params, payload))
+}
+
+/// Unwraps a container holding an object of any of the given kinds
+///
+/// Also returns the kind of the object.
+pub(crate) fn open_any<'a>(
+    vec: &'a [u8],
+    kinds: &[ObjectType],
+) -> Result<(ObjectType, CodeParams, &'a [u8])> {
+    let (kind, params, len) = parse_header(vec, kinds)?;
+    let end = HEADER_LEN + len;
+    if vec.len() < end + CHECKSUM_LEN {
+        return Err(Error::Truncated(kind.name()));
+    }
+    if crate::read_u32(vec, end, kind.name())? != crc32fast::hash(&vec[..end]) {
+        return Err(Error::ChecksumMismatch);
+    }
+    if vec.len() > end + CHECKSUM_LEN {
+        return Err(Error::Malformed("Trailing data after the checksum"));
+    }
+    Ok((kind, params, &vec[HEADER_LEN..end]))
+}
+
+/// Returns true if the bytes start like a container
+pub fn is_container(vec: &[u8]) -> bool {
+    vec.starts_with(&MAGIC)
+}
+
+/// Checks the header of a container holding an object of any of the given kinds
+///
+/// Returns the kind of the object, the code parameters and the payload length.
+fn parse_header(vec: &[u8], kinds: &[ObjectType]) -> Result<(ObjectType, CodeParams, usize)> {
+    let kind = kinds[0];
+    if vec.len() < HEADER_LEN {
+        return Err(Error::Truncated(kind.name()));
+    } else if !is_container(vec) {
+        return Err(Error::Malformed("Missing magic bytes"));
+    } else if vec[4] != VERSION {
+        return Err(Error::UnsupportedVersion(vec[4]));
+    }
+    let found = ObjectType::from_byte(vec[5])?;
+    if !kinds.contains(&found) {
+        return Err(Error::UnexpectedObject {
+            expected: kind.name(),
+            found: found.name(),
+        });
+    }
+    let params = CodeParams {
+        n: crate::read_u32(vec, 6, kind.name())? as usize,
+        k: crate::read_u32(vec, 10, kind.name())? as usize,
+        t: crate::read_u32(vec, 14, kind.name())? as usize,
+        poly: crate::read_u32(vec, 18, kind.name())?,
+    };
+    let len = crate::read_u32(vec, 22, kind.name())? as usize;
+    Ok((found, params, len))
+}
+
+/// Writes the payload in a container
+pub(crate) fn write_to<W: Write>(
+    mut writer: W,
+    kind: ObjectType,
+    params: &CodeParams,
+    payload: &[u8],
+) -> Result<()> {
+    writer.write_all(&seal(kind, params, payload))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the payload in an armored container
+pub(crate) fn write_armored_to<W: Write>(
+    mut writer: W,
+    kind: ObjectType,
+    params: &CodeParams,
+    payload: &[u8],
+) -> Result<()> {
+    writer.write_all(armor::armor(kind, params, payload).as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads an object of the given kind until the end of the reader
+///
+/// Returns the code parameters and the payload of a container, armored or not,
+/// or no parameters and all the bytes read in the legacy layout.
+pub(crate) fn read_from<R: Read>(
+    reader: R,
+    kind: ObjectType,
+) -> Result<(Option<CodeParams>, Vec<u8>)> {
+    let (found, payload) = read_any_from(reader, &[kind])?;
+    Ok((found.map(|(_, params)| params), payload))
+}
+
+/// Kind and code parameters recorded in a container
+type Header = (ObjectType, CodeParams);
+
+/// Reads an object of any of the given kinds until the end of the reader
+///
+/// Like [`read_from()`], except the kind of a container is also returned.
+///
+/// [`read_from()`]: fn.read_from.html
+pub(crate) fn read_any_from<R: Read>(
+    mut reader: R,
+    kinds: &[ObjectType],
+) -> Result<(Option<Header>, Vec<u8>)> {
+    let mut vec = Vec::new();
+    reader.read_to_end(&mut vec)?;
+    decode(vec, kinds)
+}
+
+/// Reads the code parameters of an object of the given kind
+///
+/// Only the header of a binary container is read: its payload is returned empty.
+/// Other objects are read until the end of the reader and decoded as by [`read_from()`].
+///
+/// [`read_from()`]: fn.read_from.html
+pub(crate) fn read_params_from<R: Read>(
+    mut reader: R,
+    kind: ObjectType,
+) -> Result<(Option<CodeParams>, Vec<u8>)> {
+    let mut vec = Vec::with_capacity(HEADER_LEN);
+    reader
+        .by_ref()
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut vec)?;
+    if is_container(&vec) {
+        return Ok((Some(parse_header(&vec, &[kind])?.1), Vec::new()));
+    }
+    reader.read_to_end(&mut vec)?;
+    let (found, payload) = decode(vec, &[kind])?;
+    Ok((found.map(|(_, params)| params), payload))
+}
+
+/// Decodes the bytes of an object of any of the given kinds
+fn decode(vec: Vec<u8>, kinds: &[ObjectType]) -> Result<(Option<Header>, Vec<u8>)> {
+    if armor::is_armored(&vec) {
+        let text = std::str::from_utf8(&vec).map_err(|_| Error::Malformed("Armor is not text"))?;
+        let (kind, params, payload) = armor::dearmor_any(text, kinds)?;
+        return Ok((Some((kind, params)), payload));
+    }
+    if !is_container(&vec) {
+        return Ok((None, vec));
+    }
+    let (kind, params, payload) = open_any(&vec, kinds)?;
+    Ok((Some((kind, params)), payload.to_vec()))
+}
+
+/// Saves a ciphertext or a plaintext of the code of the given parameters
+pub fn write_vector(
+    file_name: &str,
+    kind: ObjectType,
+    params: &CodeParams,
+    vec: &RowVec<F2>,
+) -> Result<()> {
+    write_vector_to(File::create(file_name)?, kind, params, vec)
+}
+
+/// Writes a ciphertext or a plaintext of the code of the given parameters
+pub fn write_vector_to<W: Write>(
+    writer: W,
+    kind: ObjectType,
+    params: &CodeParams,
+    vec: &RowVec<F2>,
+) -> Result<()> {
+    write_to(writer, kind, params, &vec.to_bytes())
+}
+
+/// Saves a ciphertext or a plaintext of the code of the given parameters in [`armor`]
+///
+/// [`armor`]: ../armor/index.html
+pub fn write_vector_armored(
+    file_name: &str,
+    kind: ObjectType,
+    params: &CodeParams,
+    vec: &RowVec<F2>,
+) -> Result<()> {
+    write_vector_armored_to(File::create(file_name)?, kind, params, vec)
+}
+
+/// Writes a ciphertext or a plaintext of the code of the given parameters in [`armor`]
+///
+/// [`armor`]: ../armor/index.html
+pub fn write_vector_armored_to<W: Write>(
+    writer: W,
+    kind: ObjectType,
+    params: &CodeParams,
+    vec: &RowVec<F2>,
+) -> Result<()> {
+    write_armored_to(writer, kind, params, &vec.to_bytes())
+}
+
+/// Reads a ciphertext or a plaintext of the code of the given parameters
+///
+/// See [`read_vector_from()`](fn.read_vector_from.html).
+pub fn read_vector(file_name: &str, kind: ObjectType, params: &CodeParams) -> Result<RowVec<F2>> {
+    read_vector_from(File::open(file_name)?, kind, params)
+}
+
+/// Reads a ciphertext or a plaintext of the code of the given parameters
+/// until the end of the reader
+///
+/// Fails if the reader holds another kind of object, belongs to another code
+/// or if the vector length is not n (ciphertext) or k (plaintext).
+pub fn read_vector_from<R: Read>(
+    reader: R,
+    kind: ObjectType,
+    params: &CodeParams,
+) -> Result<RowVec<F2>> {
+    let (found, payload) = read_from(reader, kind)?;
+    if found.is_some_and(|found| !found.matches(params)) {
+        return Err(Error::Malformed("Code parameters do not match the key"));
+    }
+    let (_, vec) = RowVec::from_bytes(&payload)?;
+    let len = match kind {
+        ObjectType::Ciphertext => params.n,
+        _ => params.k,
+    };
+    if vec.cols() != len {
+        return Err(Error::DimensionMismatch);
+    }
+    Ok(vec)
+}
@@ -0,0 +1,214 @@
+//! Precomputations speeding up the decryption of many ciphertexts with one secret key
+//!
+//! Decrypting a ciphertext requires the parity-check matrix XYZ of the Goppa code
+//! or the values g(&alpha;)<sup>-1</sup> (see [`Precomputed`]),
+//! the inverse of matrix S and the inverse of permutation P.
+//! [`SecretKey::decrypt()`] computes them for every ciphertext;
+//! a [`DecryptionContext`] computes them once
+//! (S<sup>-1</sup> on the first decryption, which the [`kem`] does not need).
+//! The context also holds the [`Decoder`] correcting the errors of the Goppa code,
+//! [`Patterson`] unless chosen otherwise.
+//!
+//! [`Precomputed`]: ../../goppa/decoder/struct.Precomputed.html
+//! [`SecretKey::decrypt()`]: ../struct.SecretKey.html#method.decrypt
+//! [`DecryptionContext`]: struct.DecryptionContext.html
+//! [`Decoder`]: ../../goppa/decoder/trait.Decoder.html
+//! [`Patterson`]: ../../goppa/decoder/struct.Patterson.html
+//! [`kem`]: ../kem/index.html
+
+use log::debug;
+use std::sync::OnceLock;
+
+use super::SecretKey;
+use crate::{
+    finite_field::{F2m, Field, F2},
+    goppa::decoder::{Decoder, Patterson, Precomputed},
+    matrix::{BitMat, Mat, Perm, RowVec},
+    polynomial::RootFinding,
+    Error, Result,
+};
+
+/// Secret key along with the values it decrypts with
+pub struct DecryptionContext<'a> {
+    sk: &'a SecretKey,
+    precomputed: Precomputed<F2m>,
+    s_inv: OnceLock<BitMat>,
+    p_inv: Perm,
+    decoder: Box<dyn Decoder<F2m>>,
+}
+
+impl<'a> DecryptionContext<'a> {
+    /// Precomputes the values needed to decrypt with the secret key
+    /// and decodes with the Patterson algorithm
+    ///
+    /// # Panics
+    ///
+    /// Decryption panics if S is not invertible.
+    pub fn new(sk: &'a SecretKey) -> Self {
+        Self::with_decoder(sk, Box::new(Patterson::new()))
+    }
+
+    /// Like new, except errors are corrected by the given decoder
+    ///
+    /// # Panics
+    ///
+    /// Decryption panics if S is not invertible.
+    pub fn with_decoder(sk: &'a SecretKey, decoder: Box<dyn Decoder<F2m>>) -> Self {
+        Self {
+            sk,
+            precomputed: Precomputed::new(&sk.goppa),
+            s_inv: OnceLock::new(),
+            p_inv: sk.p.inverse(),
+            decoder,
+        }
+    }
+
+    /// Sets the algorithm finding the roots of the error locator polynomial
+    ///
+    /// Defaults to the [`default_root_finding()`] of the Goppa code.
+    ///
+    /// [`default_root_finding()`]: ../../goppa/struct.Goppa.html#method.default_root_finding
+    pub fn with_root_finding(mut self, method: RootFinding) -> Self {
+        self.decoder.set_root_finding(method);
+        self
+    }
+
+    pub fn secret_key(&self) -> &SecretKey {
+        self.sk
+    }
+
+    /// Returns the parity-check matrix XYZ of the Goppa code
+    pub fn xyz(&self) -> &Mat<F2m> {
+        self.precomputed.xyz()
+    }
+
+    /// Returns g(&alpha;)<sup>-1</sup> for every element &alpha; of the Goppa set
+    pub fn g_inv(&self) -> &Vec<<F2m as Field>::FieldElement> {
+        self.precomputed.g_inv()
+    }
+
+    /// Returns the inverse of matrix S, computed on the first call
+    ///
+    /// # Panics
+    ///
+    /// Panics if S is not invertible.
+    pub fn s_inv(&self) -> &BitMat {
+        self.s_inv.get_or_init(|| self.sk.s.inverse().unwrap())
+    }
+
+    /// Returns the inverse of permutation P
+    pub fn p_inv(&self) -> &Perm {
+        &self.p_inv
+    }
+
+    pub fn decoder(&self) -> &dyn Decoder<F2m> {
+        self.decoder.as_ref()
+    }
+
+    pub fn root_finding(&self) -> RootFinding {
+        self.decoder
+            .root_finding()
+            .unwrap_or_else(|| self.sk.goppa.default_root_finding())
+    }
+
+    /// Decrypts the ciphertext, like [`SecretKey::decrypt()`]
+    ///
+    /// A ciphertext with too many errors to decode decrypts to garbage.
+    ///
+    /// [`SecretKey::decrypt()`]: ../struct.SecretKey.html#method.decrypt
+    pub fn decrypt(&self, c: &RowVec<F2>) -> RowVec<F2> {
+        self.decrypt_with_error(c).0
+    }
+
+    /// Decrypts the ciphertext
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DecodingFailure`](../../enum.Error.html#variant.DecodingFailure)
+    /// if the decoder finds the ciphertext has more than t errors.
+    pub fn try_decrypt(&self, c: &RowVec<F2>) -> Result<RowVec<F2>> {
+        let c1 = c * &self.p_inv;
+        let e1 = self
+            .decoder
+            .error_with(&self.sk.goppa, &self.precomputed, &c1)?;
+        Ok(self.decrypt_from_error(c1, e1).0)
+    }
+
+    /// Decrypts the ciphertext and also returns the error vector found by the decoder
+    pub(crate) fn decrypt_with_error(&self, c: &RowVec<F2>) -> (RowVec<F2>, RowVec<F2>) {
+        let c1 = c * &self.p_inv;
+        let e1 = match self
+            .decoder
+            .error_with(&self.sk.goppa, &self.precomputed, &c1)
+        {
+            Ok(e1) => e1,
+            Err(e) => {
+                debug!("{}: leave the ciphertext as is", e);
+                RowVec::zero(c1.field(), c1.cols())
+            }
+        };
+        self.decrypt_from_error(c1, e1)
+    }
+
+    /// Like decrypt_with_error, except decoding fails if the ciphertext minus the error vector
+    /// is not a codeword
+    pub(crate) fn try_decrypt_with_error(
+        &self,
+        c: &RowVec<F2>,
+    ) -> Result<(RowVec<F2>, RowVec<F2>)> {
+        let c1 = c * &self.p_inv;
+        let e1 = self.checked_error(&c1)?;
+        Ok(self.decrypt_from_error(c1, e1))
+    }
+
+    /// Finds the error vector of the ciphertext
+    ///
+    /// Unlike try_decrypt, also fails if the ciphertext minus the error vector
+    /// is not a codeword.
+    pub(crate) fn error(&self, c: &RowVec<F2>) -> Result<RowVec<F2>> {
+        let c1 = c * &self.p_inv;
+        Ok(self.checked_error(&c1)? * &self.sk.p)
+    }
+
+    /// Decodes the permuted ciphertext c1 and checks that c1 minus the error is a codeword
+    fn checked_error(&self, c1: &RowVec<F2>) -> Result<RowVec<F2>> {
+        let goppa = &self.sk.goppa;
+        let syndrome = self.decoder.syndrome(goppa, &self.precomputed, c1);
+        let e1 = self.decoder.decode_syndrome(goppa, &syndrome)?;
+        if self.decoder.syndrome(goppa, &self.precomputed, &e1) != syndrome {
+            debug!("Error vector found does not have the syndrome of the ciphertext");
+            return Err(Error::DecodingFailure);
+        }
+        Ok(e1)
+    }
+
+    fn decrypt_from_error(&self, c1: RowVec<F2>, e1: RowVec<F2>) -> (RowVec<F2>, RowVec<F2>) {
+        let sk = self.sk;
+        let m1 = c1 + &e1;
+        debug!("Decoded codeword mSG:{}", m1);
+
+        let e = e1 * &sk.p;
+        debug!("Error vector:{}", e);
+
+        let ms = m1.extract_cols(&sk.info_set);
+        debug!("Use information set {:?} to extract mS:{}", sk.info_set, ms);
+
+        (ms * self.s_inv(), e)
+    }
+}
+
+impl SecretKey {
+    /// Precomputes the values needed to decrypt many ciphertexts with the key
+    ///
+    /// See [`DecryptionContext::new()`](context/struct.DecryptionContext.html#method.new).
+    pub fn context(&self) -> DecryptionContext<'_> {
+        DecryptionContext::new(self)
+    }
+
+    /// Like context, except errors are corrected by the given decoder
+    ///
+    /// See [`DecryptionContext::with_decoder()`](context/struct.DecryptionContext.html#method.with_decoder).
+    pub fn context_with_decoder(&self, decoder: Box<dyn Decoder<F2m>>) -> DecryptionContext<'_> {
+        DecryptionContext::with_decoder(self, decoder)
+    }
+}
@@ -0,0 +1,39 @@
+//! Hash functions used by the key encapsulation mechanism and the CCA2 conversions
+//!
+//! Every call is prefixed with a byte so that the different uses of the hash
+//! function are independent (domain separation).
+
+use sha2::{Digest, Sha256};
+
+/// Length in bytes of a hash
+pub(crate) const HASH_LEN: usize = 32;
+
+/// Hashes the concatenation of the prefix and data
+pub(crate) fn hash(prefix: u8, data: &[&[u8]]) -> [u8; HASH_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update([prefix]);
+    for d in data {
+        hasher.update(d);
+    }
+    hasher.finalize().into()
+}
+
+/// Expands the concatenation of the prefix and data into len pseudorandom bytes
+///
+/// Block i of the output is the hash of prefix, i (on four bytes) and data.
+pub(crate) fn expand(prefix: u8, data: &[&[u8]], len: usize) -> Vec<u8> {
+    let mut vec = Vec::with_capacity(len + HASH_LEN);
+    let mut i: u32 = 0;
+    while vec.len() < len {
+        let mut hasher = Sha256::new();
+        hasher.update([prefix]);
+        hasher.update(i.to_be_bytes());
+        for d in data {
+            hasher.update(d);
+        }
+        vec.extend_from_slice(&hasher.finalize());
+        i += 1;
+    }
+    vec.truncate(len);
+    vec
+}
@@ -0,0 +1,190 @@
+//! Hybrid encryption of arbitrary data
+//!
+//! A random key is encapsulated with the McEliece public key
+//! (see module [`kem`]) and protects the data with ChaCha20-Poly1305.
+//! The data is split into chunks which are encrypted and authenticated separately
+//! following the STREAM construction: the nonce of a chunk is its index
+//! and a flag telling whether it is the last chunk.
+//! Reordering, removing or appending chunks is therefore detected.
+//! See Hoang, V. T., Reyhanitabar, R., Rogaway, P., & Vizár, D. (2015),
+//! Online authenticated-encryption and its nonce-reuse misuse-resistance.
+//! Annual Cryptology Conference, 493-517.
+//!
+//! [`kem`]: ../kem/index.html
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use log::debug;
+use rand::{CryptoRng, RngCore};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+};
+
+use super::{hash, kem, PublicKey, SecretKey};
+use crate::{matrix::RowVec, Error, Result};
+
+/// Number of plaintext bytes per chunk
+pub const CHUNK_LEN: usize = 1 << 16;
+
+/// Length in bytes of the authentication tag appended to each chunk
+const TAG_LEN: usize = 16;
+
+/// Length in bytes of a nonce
+const NONCE_LEN: usize = 12;
+
+/// Hash prefix for the derivation of the symmetric key
+const KEY: u8 = 7;
+
+/// Encrypts data read from input and writes the result to output
+///
+/// The output layout is:
+/// - bytes 0-a: ciphertext of the key encapsulation (see [`RowVec`]::[`to_bytes()`])
+/// - bytes a-b: encrypted chunks, each followed by its 16 bytes tag
+///
+/// [`RowVec`]: ../../matrix/struct.RowVec.html
+/// [`to_bytes()`]: ../../matrix/struct.RowVec.html#method.to_bytes
+pub fn encrypt<R: Read, W: Write>(pk: &PublicKey, input: R, output: W) -> Result<()> {
+    encrypt_with_rng(&mut rand::thread_rng(), pk, input, output)
+}
+
+/// Like encrypt, except the key encapsulation draws from rng
+pub fn encrypt_with_rng<G, R, W>(
+    rng: &mut G,
+    pk: &PublicKey,
+    mut input: R,
+    mut output: W,
+) -> Result<()>
+where
+    G: CryptoRng + RngCore,
+    R: Read,
+    W: Write,
+{
+    let (c, ss) = kem::encapsulate_with_rng(rng, pk);
+    output.write_all(&c.to_bytes())?;
+    let cipher = cipher(&ss);
+
+    let mut buf = vec![0; CHUNK_LEN];
+    let mut len = read_chunk(&mut input, &mut buf)?;
+    let mut index = 0;
+    loop {
+        // Look ahead to find out whether this chunk is the last one
+        let mut next = vec![0; CHUNK_LEN];
+        let next_len = if len == CHUNK_LEN {
+            read_chunk(&mut input, &mut next)?
+        } else {
+            0
+        };
+        let last = next_len == 0;
+        let chunk = cipher
+            .encrypt(&nonce(index, last), &buf[..len])
+            .map_err(|_| Error::InvalidParameters("Chunk too long to encrypt"))?;
+        output.write_all(&chunk)?;
+        debug!("Encrypted chunk #{} ({} bytes)", index, len);
+        if last {
+            break;
+        }
+        buf = next;
+        len = next_len;
+        index += 1;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// Decrypts data produced by [`encrypt()`]
+///
+/// # Errors
+///
+/// Returns [`Error::Truncated`] if the data is truncated,
+/// [`Error::DimensionMismatch`] if the encapsulation does not match the secret key
+/// and [`Error::Malformed`] if a chunk fails authentication.
+/// Some plaintext may have been written to output before the error is detected.
+///
+/// [`encrypt()`]: fn.encrypt.html
+/// [`Error::Truncated`]: ../../enum.Error.html#variant.Truncated
+/// [`Error::DimensionMismatch`]: ../../enum.Error.html#variant.DimensionMismatch
+/// [`Error::Malformed`]: ../../enum.Error.html#variant.Malformed
+pub fn decrypt<R: Read, W: Write>(sk: &SecretKey, mut input: R, mut output: W) -> Result<()> {
+    let redundancy = sk.p().len() - sk.s().rows();
+    let mut header = vec![0; 4 + crate::div_ceil(redundancy, 8)];
+    input.read_exact(&mut header)?;
+    let (_, c) = RowVec::from_bytes(&header)?;
+    if c.cols() != redundancy {
+        return Err(Error::DimensionMismatch);
+    }
+    let ss = kem::decapsulate(sk, &c);
+    let cipher = cipher(&ss);
+
+    let mut buf = vec![0; CHUNK_LEN + TAG_LEN];
+    let mut len = read_chunk(&mut input, &mut buf)?;
+    let mut index = 0;
+    loop {
+        if len < TAG_LEN {
+            return Err(Error::Truncated("ciphertext"));
+        }
+        let mut next = vec![0; CHUNK_LEN + TAG_LEN];
+        let next_len = if len == CHUNK_LEN + TAG_LEN {
+            read_chunk(&mut input, &mut next)?
+        } else {
+            0
+        };
+        let last = next_len == 0;
+        let chunk = cipher
+            .decrypt(&nonce(index, last), &buf[..len])
+            .map_err(|_| Error::Malformed("Chunk fails authentication"))?;
+        output.write_all(&chunk)?;
+        debug!("Decrypted chunk #{} ({} bytes)", index, chunk.len());
+        if last {
+            break;
+        }
+        buf = next;
+        len = next_len;
+        index += 1;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// Encrypts file input into file output
+pub fn encrypt_file(pk: &PublicKey, input: &str, output: &str) -> Result<()> {
+    let input = BufReader::new(File::open(input)?);
+    let output = BufWriter::new(File::create(output)?);
+    encrypt(pk, input, output)
+}
+
+/// Decrypts file input into file output
+pub fn decrypt_file(sk: &SecretKey, input: &str, output: &str) -> Result<()> {
+    let input = BufReader::new(File::open(input)?);
+    let output = BufWriter::new(File::create(output)?);
+    decrypt(sk, input, output)
+}
+
+fn cipher(ss: &kem::SharedSecret) -> ChaCha20Poly1305 {
+    let key = hash::hash(KEY, &[ss]);
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+/// Nonce of a chunk: three zero bytes, its index on eight bytes and the last chunk flag
+fn nonce(index: u64, last: bool) -> Nonce {
+    let mut nonce = [0; NONCE_LEN];
+    nonce[3..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = last as u8;
+    *Nonce::from_slice(&nonce)
+}
+
+/// Reads from input until buf is full or end of file is reached
+///
+/// Returns the number of bytes read.
+fn read_chunk<R: Read>(input: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match input.read(&mut buf[len..])? {
+            0 => break,
+            read => len += read,
+        }
+    }
+    Ok(len)
+}
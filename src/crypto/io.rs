@@ -3,61 +3,162 @@
 use log::debug;
 use std::{
     convert::TryInto,
-    error::Error,
     fs::File,
-    io::{BufWriter, Read, Write},
+    io::{Read, Write},
 };
 
-use super::{PublicKey, SecretKey};
+use super::{
+    armor,
+    container::{self, CodeParams, ObjectType},
+    hash, passphrase,
+    seed::KeySeed,
+    PublicKey, SecretKey,
+};
 use crate::{
     finite_field::FiniteField,
     goppa::Goppa,
-    matrix::{Mat, Perm},
+    matrix::{BitMat, Perm},
+    Error, Result,
 };
 
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
-
 impl PublicKey {
-    /// Saves public key on disk
+    /// Encodes the public key in bytes
     ///
-    /// The output file layout is:
-    /// - bytes 0-3: number k of rows of matrix sgp
-    /// - bytes 4-7: number n of columns of matrix sgp
-    /// - bytes 8-x: coefficients of matrix sgp (eight per byte)
+    /// The encoded byte vector has the following layout:
+    /// - bytes 0-3: code dimension k (number of rows of matrix r)
+    /// - bytes 4-7: number n - k of columns of matrix r
+    /// - bytes 8-x: coefficients of matrix r (eight per byte)
     /// - bytes x-x+4: correction capacity t
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut vec = self.r.to_bytes();
+        vec.extend_from_slice(&(self.t as u32).to_be_bytes());
+        vec
+    }
+
+    /// Decodes bytes encoded with [`to_bytes()`] to a public key
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<Self> {
+        let (i, r) = BitMat::from_bytes(vec)?;
+        let t = crate::read_u32(vec, i, "public key")? as usize;
+        if t == 0 || t > r.rows() + r.cols() {
+            return Err(Error::Malformed("Invalid correction capacity"));
+        }
+        Ok(PublicKey { r, t })
+    }
+
+    /// Saves public key on disk
+    ///
+    /// The key is encoded with [`to_bytes()`] and wrapped in a [`container`].
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    /// [`container`]: container/index.html
     pub fn write(&self, file_name: &str) -> Result<()> {
-        let mut f = File::create(file_name)?;
-        f.write_all(&self.sgp.to_bytes())?;
-        f.write_all(&(self.t as u32).to_be_bytes())?;
-        Ok(())
+        self.write_to(File::create(file_name)?)
     }
 
+    /// Writes public key like [`write()`] does, to any writer
+    ///
+    /// [`write()`]: #method.write
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_to(
+            writer,
+            ObjectType::PublicKey,
+            &self.params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Saves public key on disk in [`armor`], as text
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn write_armored(&self, file_name: &str) -> Result<()> {
+        self.write_armored_to(File::create(file_name)?)
+    }
+
+    /// Writes public key in [`armor`], to any writer
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn write_armored_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_armored_to(
+            writer,
+            ObjectType::PublicKey,
+            &self.params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Encodes the public key in [`armor`]
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn to_armor(&self) -> String {
+        armor::armor(ObjectType::PublicKey, &self.params(), &self.to_bytes())
+    }
+
+    /// Decodes a public key encoded with [`to_armor()`]
+    ///
+    /// [`to_armor()`]: #method.to_armor
+    pub fn from_armor(text: &str) -> Result<Self> {
+        let (params, payload) = armor::dearmor(text, ObjectType::PublicKey)?;
+        let pk = Self::from_bytes(&payload)?;
+        if pk.params() != params {
+            return Err(Error::Malformed("Header does not match the public key"));
+        }
+        Ok(pk)
+    }
+
+    /// Reads a public key saved with [`write()`], [`write_armored()`]
+    /// or in the legacy layout of [`to_bytes()`]
+    ///
+    /// [`write()`]: #method.write
+    /// [`write_armored()`]: #method.write_armored
+    /// [`to_bytes()`]: #method.to_bytes
     pub fn read_public_key(file_name: &str) -> Result<Self> {
-        let mut f = File::open(file_name)?;
-        let mut vec = Vec::new();
-        f.read_to_end(&mut vec)?;
-        let (i, sgp) = Mat::from_bytes(&vec)?;
-        let t = u32::from_be_bytes(vec[i..i + 4].try_into()?) as usize;
-        Ok(PublicKey { sgp, t })
+        Self::read_from(File::open(file_name)?)
+    }
+
+    /// Reads a public key like [`read_public_key()`] does, until the end of the reader
+    ///
+    /// [`read_public_key()`]: #method.read_public_key
+    pub fn read_from<R: Read>(reader: R) -> Result<Self> {
+        let (params, payload) = container::read_from(reader, ObjectType::PublicKey)?;
+        let pk = Self::from_bytes(&payload)?;
+        if params.is_some_and(|params| params != pk.params()) {
+            return Err(Error::Malformed("Header does not match the public key"));
+        }
+        Ok(pk)
+    }
+
+    /// Reads the code parameters of a public key saved on disk
+    ///
+    /// Only the header is read, unless the key is armored or in the legacy layout.
+    pub fn read_params(file_name: &str) -> Result<CodeParams> {
+        Self::read_params_from(File::open(file_name)?)
+    }
+
+    /// Reads the code parameters of a public key like [`read_params()`] does, from any reader
+    ///
+    /// [`read_params()`]: #method.read_params
+    pub fn read_params_from<R: Read>(reader: R) -> Result<CodeParams> {
+        match container::read_params_from(reader, ObjectType::PublicKey)? {
+            (Some(params), _) => Ok(params),
+            (None, payload) => Ok(Self::from_bytes(&payload)?.params()),
+        }
     }
 
     pub fn read_code_dimension(file_name: &str) -> Result<usize> {
-        let mut f = File::open(file_name)?;
-        let mut buf = [0; 4];
-        f.read_exact(&mut buf)?;
-        let k = u32::from_be_bytes(buf) as usize;
-        Ok(k)
+        Ok(Self::read_params(file_name)?.k)
     }
 }
 
 impl SecretKey {
-    /// Saves secret key on disk
+    /// Encodes the secret key in bytes
     ///
-    /// The output file layout is:
+    /// The encoded byte vector has the following layout:
     /// - bytes 0-3: finite field order q
     /// - bytes 4-7: number of rows of matrix s
     /// - bytes 8-11: number of columns of matrix s
-    /// - bytes 11-a: coefficients of matrix s (eight per byte)
+    /// - bytes 12-a: coefficients of matrix s (eight per byte)
     /// - bytes a-a+4: finite field order q
     /// - bytes a+4-a+8: correction capacity t
     /// - bytes a+8-b: coefficients of Goppa polynomial (four bytes per coefficient)
@@ -65,63 +166,212 @@ impl SecretKey {
     /// - bytes c-d: information set (four bytes per column index)
     /// - bytes d-d+4: length n of the code
     /// - bytes d+4-e: permutation P (four bytes per image)
+    /// - bytes e-e+32: rejection value
     ///
     /// [`Goppa`]: ../goppa/struct.Goppa.html
     /// [`to_bytes()`]: ../goppa/struct.Goppa.html#method.to_bytes
-    pub fn write(&self, file_name: &str) -> Result<()> {
-        let f = File::create(file_name)?;
-        let mut f = BufWriter::new(f);
-        f.write_all(&(self.goppa.field().order() as u32).to_be_bytes())?;
-        f.write_all(&self.s.to_bytes())?;
-        f.write_all(&self.goppa.to_bytes())?;
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut vec = Vec::new();
+        vec.extend_from_slice(&(self.goppa.field().order() as u32).to_be_bytes());
+        vec.extend(self.s.to_bytes());
+        vec.extend(self.goppa.to_bytes());
 
         for i in 0..self.info_set.len() {
-            f.write_all(&(self.info_set[i] as u32).to_be_bytes())?;
+            vec.extend_from_slice(&(self.info_set[i] as u32).to_be_bytes());
         }
 
-        f.write_all(&(self.p.len() as u32).to_be_bytes())?;
+        vec.extend_from_slice(&(self.p.len() as u32).to_be_bytes());
         for i in 0..self.p.len() {
-            f.write_all(&(self.p[i] as u32).to_be_bytes())?;
+            vec.extend_from_slice(&(self.p[i] as u32).to_be_bytes());
         }
-        Ok(())
+        vec.extend_from_slice(&self.rejection);
+        vec
     }
 
-    pub fn read_secret_key(file_name: &str) -> Result<Self> {
-        let mut f = File::open(file_name)?;
-        let mut vec = Vec::new();
-        f.read_to_end(&mut vec)?;
+    /// Decodes bytes encoded with [`to_bytes()`] to a secret key
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<Self> {
+        let q = crate::read_u32(vec, 0, "secret key")? as usize;
         let mut i = 4;
 
-        let (bytes, s) = Mat::from_bytes(&vec[i..])?;
+        let (bytes, s) = BitMat::from_bytes(&vec[i..])?;
         i += bytes;
         debug!("Read matrix s:{}", s);
 
         let (bytes, goppa) = Goppa::from_bytes(&vec[i..])?;
         i += bytes;
         debug!("Read Goppa code:\n{}", goppa);
+        if goppa.field().order() != q {
+            return Err(Error::Malformed(
+                "Field order does not match the Goppa code",
+            ));
+        }
 
         let k = s.rows();
+        if s.cols() != k {
+            return Err(Error::Malformed("Matrix s is not square"));
+        }
+        if !s.is_invertible() {
+            return Err(Error::Malformed("Matrix s is not invertible"));
+        }
+        let n = goppa.len();
         let mut info_set = Vec::new();
         for _j in 0..k {
-            info_set.push(u32::from_be_bytes(vec[i..i + 4].try_into()?) as usize);
+            let col = crate::read_u32(vec, i, "secret key")? as usize;
+            if col >= n {
+                return Err(Error::Malformed("Information set out of the code length"));
+            }
+            info_set.push(col);
             i += 4;
         }
         debug!("Read information set:\n{:?}", info_set);
 
-        let p_len = u32::from_be_bytes(vec[i..i + 4].try_into()?) as usize;
+        let p_len = crate::read_u32(vec, i, "secret key")? as usize;
         i += 4;
+        if p_len != n {
+            return Err(Error::Malformed("Permutation and code lengths differ"));
+        }
         let mut p = Vec::with_capacity(p_len);
         for _j in 0..p_len {
-            p.push(u32::from_be_bytes(vec[i..i + 4].try_into()?) as usize);
+            p.push(crate::read_u32(vec, i, "secret key")? as usize);
             i += 4;
         }
-        let p = Perm::new(p);
+        let p = Perm::try_new(p)?;
+
+        let rejection = vec
+            .get(i..i + hash::HASH_LEN)
+            .ok_or(Error::Truncated("secret key"))?
+            .try_into()
+            .unwrap();
 
         Ok(SecretKey {
             s,
             goppa,
             info_set,
             p,
+            rejection,
         })
     }
+
+    /// Saves secret key on disk
+    ///
+    /// The key is encoded with [`to_bytes()`] and wrapped in a [`container`].
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    /// [`container`]: container/index.html
+    pub fn write(&self, file_name: &str) -> Result<()> {
+        self.write_to(File::create(file_name)?)
+    }
+
+    /// Writes secret key like [`write()`] does, to any writer
+    ///
+    /// [`write()`]: #method.write
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_to(
+            writer,
+            ObjectType::SecretKey,
+            &self.params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Saves secret key on disk in [`armor`], as text
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn write_armored(&self, file_name: &str) -> Result<()> {
+        self.write_armored_to(File::create(file_name)?)
+    }
+
+    /// Writes secret key in [`armor`], to any writer
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn write_armored_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_armored_to(
+            writer,
+            ObjectType::SecretKey,
+            &self.params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Encodes the secret key in [`armor`]
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn to_armor(&self) -> String {
+        armor::armor(ObjectType::SecretKey, &self.params(), &self.to_bytes())
+    }
+
+    /// Decodes a secret key encoded with [`to_armor()`]
+    ///
+    /// [`to_armor()`]: #method.to_armor
+    pub fn from_armor(text: &str) -> Result<Self> {
+        let (params, payload) = armor::dearmor(text, ObjectType::SecretKey)?;
+        let sk = Self::from_bytes(&payload)?;
+        if sk.params() != params {
+            return Err(Error::Malformed("Header does not match the secret key"));
+        }
+        Ok(sk)
+    }
+
+    /// Reads a secret key saved with [`write()`], [`write_armored()`]
+    /// or in the legacy layout of [`to_bytes()`]
+    ///
+    /// A [`KeySeed`] is also accepted: the secret key is derived from it.
+    /// Fails with [`Error::PassphraseRequired`] if the key is encrypted under a passphrase:
+    /// use [`read_secret_key_with_passphrase()`] or [`read_secret_key_with_prompt()`] instead.
+    ///
+    /// [`write()`]: #method.write
+    /// [`write_armored()`]: #method.write_armored
+    /// [`to_bytes()`]: #method.to_bytes
+    /// [`Error::PassphraseRequired`]: ../enum.Error.html#variant.PassphraseRequired
+    /// [`read_secret_key_with_passphrase()`]: #method.read_secret_key_with_passphrase
+    /// [`read_secret_key_with_prompt()`]: #method.read_secret_key_with_prompt
+    /// [`KeySeed`]: seed/struct.KeySeed.html
+    pub fn read_secret_key(file_name: &str) -> Result<Self> {
+        Self::read_from(File::open(file_name)?)
+    }
+
+    /// Reads a secret key like [`read_secret_key()`] does, until the end of the reader
+    ///
+    /// [`read_secret_key()`]: #method.read_secret_key
+    pub fn read_from<R: Read>(reader: R) -> Result<Self> {
+        Self::read_protected(reader, || Err::<&[u8], _>(Error::PassphraseRequired))
+    }
+
+    /// Reads a secret key, decrypting it with the passphrase returned by prompt if it is encrypted
+    /// and deriving it if it is stored as a [`KeySeed`]
+    ///
+    /// Prompt is only called for an encrypted key.
+    ///
+    /// [`KeySeed`]: seed/struct.KeySeed.html
+    pub(crate) fn read_protected<R, F, P, E>(reader: R, prompt: F) -> std::result::Result<Self, E>
+    where
+        R: Read,
+        F: FnOnce() -> std::result::Result<P, E>,
+        P: AsRef<[u8]>,
+        E: From<Error>,
+    {
+        let kinds = [
+            ObjectType::SecretKey,
+            ObjectType::EncryptedSecretKey,
+            ObjectType::KeySeed,
+        ];
+        let (found, mut payload) = container::read_any_from(reader, &kinds)?;
+        if let Some((ObjectType::KeySeed, params)) = found {
+            let seed = KeySeed::from_bytes(&payload)?;
+            if params != seed.code_params() {
+                return Err(Error::Malformed("Header does not match the secret key seed").into());
+            }
+            return Ok(seed.secret_key());
+        }
+        if let Some((ObjectType::EncryptedSecretKey, _)) = found {
+            payload = passphrase::unlock(&payload, prompt()?.as_ref())?;
+        }
+        let sk = Self::from_bytes(&payload)?;
+        if found.is_some_and(|(_, params)| params != sk.params()) {
+            return Err(Error::Malformed("Header does not match the secret key").into());
+        }
+        Ok(sk)
+    }
 }
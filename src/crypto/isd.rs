@@ -0,0 +1,275 @@
+//! Security estimates against information-set decoding
+//!
+//! The best known attacks on the McEliece cryptosystem decode a random linear code
+//! of length n and dimension k with t errors.
+//! Information-set decoding (ISD) repeatedly picks k positions and hopes
+//! the error vector has a particular shape on them.
+//! The work factor of an algorithm is the expected number of iterations
+//! times the cost of an iteration, in bit operations.
+//! Every result is given as a base 2 logarithm (bits of security).
+//!
+//! The cost of an iteration always includes a Gaussian elimination of
+//! (n - k)<sup>2</sup>(n + k) / 2 bit operations.
+//! The estimates follow the simplified models of:
+//! - Prange, E. (1962), The use of information sets in decoding cyclic codes.
+//! - Lee, P. J., & Brickell, E. F. (1988), An observation on the security
+//!   of McEliece's public-key cryptosystem.
+//! - Stern, J. (1989), A method for finding codewords of small weight.
+//! - Dumer, I. (1991), On minimum distance decoding of linear codes.
+//! - Becker, A., Joux, A., May, A., & Meurer, A. (2012), Decoding random binary linear codes
+//!   in 2<sup>n/20</sup>: How 1 + 1 = 0 improves information set decoding
+//!   (one level of representations).
+
+use std::fmt::{self, Display, Formatter};
+
+/// Largest weight p tried on the information set
+const P_MAX: usize = 40;
+
+/// Largest number e of extra positions tried by the representation technique
+const E_MAX: usize = 10;
+
+/// Work factors of the ISD algorithms for given code parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorkFactors {
+    pub prange: f64,
+    pub lee_brickell: f64,
+    pub stern: f64,
+    pub dumer: f64,
+    pub bjmm: f64,
+}
+
+impl WorkFactors {
+    /// Computes the work factors for a code of length n and dimension k with t errors
+    ///
+    /// # Panics
+    ///
+    /// Panics if k &ge; n or t &gt; n - k.
+    pub fn new(n: usize, k: usize, t: usize) -> Self {
+        let isd = Isd::new(n, k, t);
+        Self {
+            prange: isd.prange(),
+            lee_brickell: isd.lee_brickell(),
+            stern: isd.stern(),
+            dumer: isd.dumer(),
+            bjmm: isd.bjmm(),
+        }
+    }
+
+    /// Returns the security level, i.e. the smallest work factor
+    pub fn security(&self) -> f64 {
+        self.prange
+            .min(self.lee_brickell)
+            .min(self.stern)
+            .min(self.dumer)
+            .min(self.bjmm)
+    }
+}
+
+impl Display for WorkFactors {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Prange: {:.1}, Lee-Brickell: {:.1}, Stern: {:.1}, Dumer: {:.1}, BJMM: {:.1}",
+            self.prange, self.lee_brickell, self.stern, self.dumer, self.bjmm
+        )
+    }
+}
+
+/// Returns the work factor of Prange's algorithm
+///
+/// The error vector must vanish on the information set.
+pub fn prange(n: usize, k: usize, t: usize) -> f64 {
+    Isd::new(n, k, t).prange()
+}
+
+/// Returns the work factor of the Lee-Brickell algorithm
+///
+/// The error vector must have weight p on the information set.
+pub fn lee_brickell(n: usize, k: usize, t: usize) -> f64 {
+    Isd::new(n, k, t).lee_brickell()
+}
+
+/// Returns the work factor of Stern's algorithm
+///
+/// The error vector must have weight p on each half of the information set
+/// and vanish on l other positions.
+pub fn stern(n: usize, k: usize, t: usize) -> f64 {
+    Isd::new(n, k, t).stern()
+}
+
+/// Returns the work factor of Dumer's algorithm
+///
+/// Like Stern's algorithm, except the halves are taken on the information set
+/// extended by the l positions.
+pub fn dumer(n: usize, k: usize, t: usize) -> f64 {
+    Isd::new(n, k, t).dumer()
+}
+
+/// Returns the work factor of the BJMM algorithm
+///
+/// Like Dumer's algorithm, except the weight 2p vector is written as a sum of two
+/// vectors of weight p + e in many ways, only a fraction of which is enumerated.
+pub fn bjmm(n: usize, k: usize, t: usize) -> f64 {
+    Isd::new(n, k, t).bjmm()
+}
+
+/// Code parameters along with a table of log<sub>2</sub> i!
+struct Isd {
+    n: usize,
+    k: usize,
+    t: usize,
+    log_factorial: Vec<f64>,
+}
+
+impl Isd {
+    fn new(n: usize, k: usize, t: usize) -> Self {
+        if k >= n || t > n - k {
+            panic!("Invalid code parameters");
+        }
+        let mut log_factorial = Vec::with_capacity(n + 1);
+        log_factorial.push(0.0);
+        for i in 1..n + 1 {
+            log_factorial.push(log_factorial[i - 1] + (i as f64).log2());
+        }
+        Self {
+            n,
+            k,
+            t,
+            log_factorial,
+        }
+    }
+
+    /// Returns log<sub>2</sub> C(a, b)
+    fn binomial(&self, a: usize, b: usize) -> f64 {
+        if b > a {
+            f64::NEG_INFINITY
+        } else {
+            self.log_factorial[a] - self.log_factorial[b] - self.log_factorial[a - b]
+        }
+    }
+
+    /// Returns log<sub>2</sub> of the cost of a Gaussian elimination
+    fn gauss(&self) -> f64 {
+        let r = (self.n - self.k) as f64;
+        2.0 * r.log2() + ((self.n + self.k) as f64).log2() - 1.0
+    }
+
+    /// Returns log<sub>2</sub> of the work factor given
+    /// log<sub>2</sub> of the cost of an iteration and of the number of solutions it finds
+    fn work(&self, cost: f64, solutions: f64) -> f64 {
+        cost - (solutions - self.binomial(self.n, self.t)).min(0.0)
+    }
+
+    fn prange(&self) -> f64 {
+        let (n, k, t) = (self.n, self.k, self.t);
+        self.work(self.gauss(), self.binomial(n - k, t))
+    }
+
+    fn lee_brickell(&self) -> f64 {
+        let (n, k, t) = (self.n, self.k, self.t);
+        (0..P_MAX.min(t).min(k) + 1)
+            .map(|p| {
+                let list = self.binomial(k, p);
+                let check = list + log2(p) + log2(n - k);
+                let solutions = list + self.binomial(n - k, t - p);
+                self.work(add(self.gauss(), check), solutions)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn stern(&self) -> f64 {
+        let (n, k, t) = (self.n, self.k, self.t);
+        let mut best = f64::INFINITY;
+        for p in 0..P_MAX.min(t / 2).min(k / 2) + 1 {
+            let list1 = self.binomial(k / 2, p);
+            let list2 = self.binomial(k - k / 2, p);
+            for l in 0..n - k - (t - 2 * p) + 1 {
+                let outside = self.binomial(n - k - l, t - 2 * p);
+                best = best.min(self.collision(list1, list2, p, l, outside));
+            }
+        }
+        best
+    }
+
+    fn dumer(&self) -> f64 {
+        let (n, k, t) = (self.n, self.k, self.t);
+        let mut best = f64::INFINITY;
+        for l in 0..n - k + 1 {
+            for p in 0..P_MAX.min(t / 2).min((k + l) / 2) + 1 {
+                if t - 2 * p > n - k - l {
+                    continue;
+                }
+                let list1 = self.binomial((k + l) / 2, p);
+                let list2 = self.binomial(k + l - (k + l) / 2, p);
+                let outside = self.binomial(n - k - l, t - 2 * p);
+                best = best.min(self.collision(list1, list2, p, l, outside));
+            }
+        }
+        best
+    }
+
+    /// Returns log<sub>2</sub> of the work factor of an algorithm which merges two lists
+    /// of vectors of weight p on l bits and checks the collisions on the remaining bits
+    fn collision(&self, list1: f64, list2: f64, p: usize, l: usize, outside: f64) -> f64 {
+        let build = add(list1, list2) + log2(l);
+        let collisions = list1 + list2 - l as f64;
+        let check = collisions + log2(2 * p) + log2(self.n - self.k);
+        let cost = add(self.gauss(), add(build, check));
+        self.work(cost, list1 + list2 + outside)
+    }
+
+    fn bjmm(&self) -> f64 {
+        let (n, k, t) = (self.n, self.k, self.t);
+        let mut best = f64::INFINITY;
+        for l in 0..n - k + 1 {
+            let kl = k + l;
+            for p in (0..P_MAX.min(t).min(kl) + 1).step_by(2) {
+                if t - p > n - kl {
+                    continue;
+                }
+                let solutions = self.binomial(kl, p) + self.binomial(n - kl, t - p);
+                for e in 0..E_MAX.min(kl - p) + 1 {
+                    // Each half of the solution has weight p1 and is split in two base vectors
+                    let p1 = p / 2 + e;
+                    if p1 % 2 != 0 || p1 > kl / 2 {
+                        continue;
+                    }
+                    let base = self.binomial(kl / 2, p1 / 2);
+                    let representations = self.binomial(p, p / 2) + self.binomial(kl - p, e);
+                    let r = representations.floor().max(0.0).min(l as f64);
+
+                    // Four base lists, two merged lists on r bits, final merge on l - r bits
+                    let merged = 2.0 * base - r;
+                    let cost = [
+                        self.gauss(),
+                        2.0 + base + log2(r as usize),
+                        1.0 + merged + log2(p1),
+                        2.0 * merged - (l as f64 - r) + log2(p) + log2(n - k),
+                    ]
+                    .iter()
+                    .fold(f64::NEG_INFINITY, |x, &y| add(x, y));
+
+                    // Only a fraction 2^r / R of the representations is found
+                    let found = solutions + (r - representations).min(0.0);
+                    best = best.min(self.work(cost, found));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Returns log<sub>2</sub> (2<sup>x</sup> + 2<sup>y</sup>)
+fn add(x: f64, y: f64) -> f64 {
+    let (max, min) = if x > y { (x, y) } else { (y, x) };
+    if min == f64::NEG_INFINITY {
+        max
+    } else {
+        max + (1.0 + (min - max).exp2()).log2()
+    }
+}
+
+/// Returns log<sub>2</sub> max(x, 1), the cost of handling x bits
+fn log2(x: usize) -> f64 {
+    (x.max(1) as f64).log2()
+}
@@ -0,0 +1,254 @@
+//! Known-answer tests
+//!
+//! A known-answer test file is a text file made of a header giving the
+//! parameters n, t, m and the field polynomial (see [`ParameterSet`]),
+//! followed by records separated by blank lines.
+//! Each record holds, in hexadecimal:
+//! - count: index of the record (in decimal)
+//! - seed: the key generation seed (see [`keygen_from_seed()`])
+//! - pk: the public key (see [`PublicKey`]::[`to_bytes()`])
+//! - sk: the secret key (see [`SecretKey`]::[`to_bytes()`](../struct.SecretKey.html#method.to_bytes))
+//! - m: the plaintext (see [`RowVec`]::[`to_bytes()`](../../matrix/struct.RowVec.html#method.to_bytes))
+//! - e: the error vector
+//! - c: the ciphertext
+//!
+//! The seed of record i is drawn from a ChaCha20 generator initialized with a master seed.
+//! The plaintext and the error vector are drawn from a ChaCha20 generator
+//! initialized with a hash of the record seed.
+//! Verification replays every record and compares the results byte for byte.
+//!
+//! [`ParameterSet`]: ../params/struct.ParameterSet.html
+//! [`keygen_from_seed()`]: ../params/struct.ParameterSet.html#method.keygen_from_seed
+//! [`PublicKey`]: ../struct.PublicKey.html
+//! [`to_bytes()`]: ../struct.PublicKey.html#method.to_bytes
+//! [`SecretKey`]: ../struct.SecretKey.html
+//! [`RowVec`]: ../../matrix/struct.RowVec.html
+
+use log::debug;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::{convert::TryInto, fmt::Write, fs, sync::Arc};
+
+use super::{hash, ParameterSet, SecretKey, SEED_LEN};
+use crate::{
+    finite_field::{F2m, Field, F2},
+    matrix::RowVec,
+    Error, Result,
+};
+
+/// Hash prefix for the derivation of the encryption seed
+const ENCRYPTION: u8 = 8;
+
+/// A known-answer test record
+#[derive(Debug, Eq, PartialEq)]
+pub struct Record {
+    pub count: usize,
+    pub seed: [u8; SEED_LEN],
+    pub pk: Vec<u8>,
+    pub sk: Vec<u8>,
+    pub m: Vec<u8>,
+    pub e: Vec<u8>,
+    pub c: Vec<u8>,
+}
+
+impl Record {
+    /// Computes the record of the given index and seed
+    pub fn new(params: &ParameterSet, count: usize, seed: &[u8; SEED_LEN]) -> Self {
+        let f2 = Arc::new(F2::generate(()));
+        let (pk, sk) = params.keygen_from_seed(seed);
+
+        let mut rng = ChaCha20Rng::from_seed(hash::hash(ENCRYPTION, &[seed]));
+        let m = RowVec::random_with_rng(&mut rng, f2, pk.k());
+        let c = pk.encrypt_with_rng(&mut rng, &m);
+        let e = &c + &m * &pk.sgp();
+        debug!("KAT record #{}: error vector:{}", count, e);
+
+        Self {
+            count,
+            seed: *seed,
+            pk: pk.to_bytes(),
+            sk: sk.to_bytes(),
+            m: m.to_bytes(),
+            e: e.to_bytes(),
+            c: c.to_bytes(),
+        }
+    }
+}
+
+/// Generates count records for the given parameters from the master seed
+///
+/// Returns the content of the known-answer test file.
+pub fn generate(params: &ParameterSet, master_seed: &[u8; SEED_LEN], count: usize) -> String {
+    let mut rng = ChaCha20Rng::from_seed(*master_seed);
+    let mut kat = String::new();
+    writeln!(kat, "# McEliece known-answer tests: {}", params.name).unwrap();
+    writeln!(kat, "n = {}", params.n).unwrap();
+    writeln!(kat, "t = {}", params.t).unwrap();
+    writeln!(kat, "m = {}", params.m).unwrap();
+    writeln!(kat, "poly = {:X}", params.poly).unwrap();
+    for i in 0..count {
+        let record = Record::new(params, i, &rng.gen());
+        writeln!(kat).unwrap();
+        writeln!(kat, "count = {}", record.count).unwrap();
+        writeln!(kat, "seed = {}", to_hex(&record.seed)).unwrap();
+        writeln!(kat, "pk = {}", to_hex(&record.pk)).unwrap();
+        writeln!(kat, "sk = {}", to_hex(&record.sk)).unwrap();
+        writeln!(kat, "m = {}", to_hex(&record.m)).unwrap();
+        writeln!(kat, "e = {}", to_hex(&record.e)).unwrap();
+        writeln!(kat, "c = {}", to_hex(&record.c)).unwrap();
+    }
+    kat
+}
+
+/// Verifies the content of a known-answer test file
+///
+/// Every record is recomputed from its seed and compared to the file.
+/// Decrypting the ciphertext with the secret key must also give back the plaintext.
+/// Returns the number of verified records.
+///
+/// # Errors
+///
+/// Returns an error if the file is malformed, if its header does not describe valid parameters,
+/// or describes the first record that does not match.
+pub fn verify(kat: &str) -> Result<usize> {
+    let (params, records) = parse(kat)?;
+    for record in &records {
+        let expected = Record::new(&params, record.count, &record.seed);
+        for &(name, value, expected) in &[
+            ("pk", &record.pk, &expected.pk),
+            ("sk", &record.sk, &expected.sk),
+            ("m", &record.m, &expected.m),
+            ("e", &record.e, &expected.e),
+            ("c", &record.c, &expected.c),
+        ] {
+            if value != expected {
+                return Err(Error::KnownAnswerMismatch {
+                    count: record.count,
+                    field: name,
+                });
+            }
+        }
+
+        let sk = SecretKey::from_bytes(&record.sk)?;
+        let (_, c) = RowVec::from_bytes(&record.c)?;
+        if sk.decrypt(&c).to_bytes() != record.m {
+            return Err(Error::KnownAnswerMismatch {
+                count: record.count,
+                field: "decrypted m",
+            });
+        }
+        debug!("KAT record #{} verified", record.count);
+    }
+    Ok(records.len())
+}
+
+/// Writes count records for the given parameters to a file
+pub fn write(
+    file_name: &str,
+    params: &ParameterSet,
+    master_seed: &[u8; SEED_LEN],
+    count: usize,
+) -> Result<()> {
+    fs::write(file_name, generate(params, master_seed, count))?;
+    Ok(())
+}
+
+/// Verifies a known-answer test file
+pub fn verify_file(file_name: &str) -> Result<usize> {
+    verify(&fs::read_to_string(file_name)?)
+}
+
+/// Parses a known-answer test file into parameters and records
+///
+/// The first block of lines is the header, the following ones are records.
+fn parse(kat: &str) -> Result<(ParameterSet, Vec<Record>)> {
+    let mut params = None;
+    let mut records = Vec::new();
+    let mut fields: Vec<(&str, &str)> = Vec::new();
+    for line in kat.lines().chain(std::iter::once("")) {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.is_empty() {
+            if !fields.is_empty() {
+                match params {
+                    None => params = Some(parse_header(&fields)?),
+                    Some(_) => records.push(parse_record(&fields)?),
+                }
+                fields.clear();
+            }
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(Error::Malformed("Expected a line 'key = value'"))?;
+        fields.push((key.trim(), value.trim()));
+    }
+    Ok((params.ok_or(Error::MissingField("header"))?, records))
+}
+
+/// Parses the header into parameters
+///
+/// If m and the field polynomial are missing, the default field for n and t is used
+/// (see [`ParameterSet::new()`](../params/struct.ParameterSet.html#method.new)).
+/// The parameters and the field are checked before any key is built from them.
+fn parse_header(fields: &[(&str, &str)]) -> Result<ParameterSet> {
+    let n = parse_number(get(fields, "n")?)?;
+    let t = parse_number(get(fields, "t")?)?;
+    let mut params = ParameterSet::new(n, t);
+    if let Ok(poly) = get(fields, "poly") {
+        params.poly = u32::from_str_radix(poly, 16)
+            .map_err(|_| Error::Malformed("Invalid field polynomial"))?;
+        params.m = parse_number(get(fields, "m")?)?;
+    }
+    params.check().map_err(Error::InvalidParameters)?;
+    F2m::try_from_poly(params.poly)?;
+    Ok(params)
+}
+
+fn parse_record(fields: &[(&str, &str)]) -> Result<Record> {
+    let seed = from_hex(get(fields, "seed")?)?;
+    Ok(Record {
+        count: parse_number(get(fields, "count")?)?,
+        seed: seed
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::Malformed("Invalid seed length"))?,
+        pk: from_hex(get(fields, "pk")?)?,
+        sk: from_hex(get(fields, "sk")?)?,
+        m: from_hex(get(fields, "m")?)?,
+        e: from_hex(get(fields, "e")?)?,
+        c: from_hex(get(fields, "c")?)?,
+    })
+}
+
+fn get<'a>(fields: &[(&str, &'a str)], name: &'static str) -> Result<&'a str> {
+    fields
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| *value)
+        .ok_or(Error::MissingField(name))
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str) -> Result<T> {
+    s.parse()
+        .map_err(|_| Error::Malformed("Invalid decimal number"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(Error::Malformed("Invalid hexadecimal string"));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| Error::Malformed("Invalid hexadecimal string"))
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+//! Niederreiter key encapsulation mechanism
+//!
+//! The sender draws a random error vector e of weight t and sends its syndrome
+//! He<sup>T</sup> where H = (I<sub>n-k</sub> | R<sup>T</sup>) is the parity-check matrix
+//! of the public code SGP = (R | I<sub>k</sub>).
+//! The shared secret is the hash of e and of the ciphertext.
+//! The secret key holder recovers e with the [`Decoder`] of a [`DecryptionContext`].
+//! See Bernstein, D. J., Chou, T., Lange, T., et al. (2017),
+//! Classic McEliece: conservative code-based cryptography.
+//!
+//! [`Decoder`]: ../../goppa/decoder/trait.Decoder.html
+//! [`DecryptionContext`]: ../context/struct.DecryptionContext.html
+
+use log::debug;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+use super::{context::DecryptionContext, hash, PublicKey, SecretKey};
+use crate::{
+    finite_field::{Field, F2},
+    matrix::{BitMat, BitVec, RowVec},
+};
+
+/// Syndrome of the error vector (n - k bits)
+pub type Ciphertext = RowVec<F2>;
+
+/// Session key derived from the error vector
+pub type SharedSecret = [u8; 32];
+
+/// Generates a random error vector and encapsulates it with the public key
+///
+/// Returns the ciphertext along with the shared secret.
+pub fn encapsulate(pk: &PublicKey) -> (Ciphertext, SharedSecret) {
+    encapsulate_with_rng(&mut rand::thread_rng(), pk)
+}
+
+/// Like encapsulate, except the error vector is drawn from rng
+pub fn encapsulate_with_rng<R>(rng: &mut R, pk: &PublicKey) -> (Ciphertext, SharedSecret)
+where
+    R: CryptoRng + RngCore,
+{
+    let f2 = Arc::new(F2::generate(()));
+    let e = RowVec::random_with_weight_with_rng(rng, f2, pk.n(), pk.t());
+    debug!("Error vector:{}", e);
+
+    let c = syndrome(pk.r(), &e);
+    debug!("Ciphertext:{}", c);
+
+    let k = hash::hash(1, &[&e.to_bytes(), &c.to_bytes()]);
+    (c, k)
+}
+
+/// Recovers the shared secret from the ciphertext with the secret key
+///
+/// If the ciphertext is not n - k bits long or is not the syndrome of a vector of weight t,
+/// the returned value is derived from the secret rejection value instead
+/// (implicit rejection).
+pub fn decapsulate(sk: &SecretKey, c: &Ciphertext) -> SharedSecret {
+    decapsulate_with_context(&sk.context(), c)
+}
+
+/// Like decapsulate, with the values precomputed by a decryption context
+pub fn decapsulate_with_context(ctx: &DecryptionContext, c: &Ciphertext) -> SharedSecret {
+    let sk = ctx.secret_key();
+    let n = sk.p().len();
+    let k = sk.s().rows();
+    if c.cols() != n - k {
+        debug!("Ciphertext of {} bits instead of {}", c.cols(), n - k);
+        return hash::hash(0, &[sk.rejection(), &c.to_bytes()]);
+    }
+
+    // The word y = (c | 0) has syndrome c: y + e is a codeword of the public code
+    let mut y = RowVec::zero(c.field(), n);
+    for i in 0..n - k {
+        y[i] = c[i];
+    }
+    debug!("Word of syndrome c:{}", y);
+
+    match ctx.error(&y) {
+        Ok(e) if e.weight() == sk.goppa().poly().degree() => {
+            debug!("Error vector:{}", e);
+            hash::hash(1, &[&e.to_bytes(), &c.to_bytes()])
+        }
+        _ => hash::hash(0, &[sk.rejection(), &c.to_bytes()]),
+    }
+}
+
+/// Computes the syndrome He<sup>T</sup> = e<sub>1</sub> + e<sub>2</sub>R
+/// of e = (e<sub>1</sub> | e<sub>2</sub>) where e<sub>1</sub> has n - k bits
+fn syndrome(r: &BitMat, e: &RowVec<F2>) -> RowVec<F2> {
+    let k = r.rows();
+    let n = k + r.cols();
+    let e = BitVec::from(e);
+    let e1 = e.extract_cols(&(0..n - k).collect::<Vec<usize>>());
+    let e2 = e.extract_cols(&(n - k..n).collect::<Vec<usize>>());
+    RowVec::from(&(e1 + &e2 * r))
+}
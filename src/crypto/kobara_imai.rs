@@ -0,0 +1,277 @@
+//! CCA2-secure encryption using the Kobara-Imai &gamma; conversion
+//!
+//! This conversion is specific to McEliece: part of the randomized message
+//! is carried by the error vector, part of it by the plaintext vector
+//! and the rest is sent in the clear.
+//!
+//! To encrypt a message m with a random r:
+//! - y<sub>1</sub> = Gen(r) &oplus; (m || Const)
+//! - y<sub>2</sub> = r &oplus; H(y<sub>1</sub>)
+//! - (y<sub>5</sub> || y<sub>4</sub> || y<sub>3</sub>) = (y<sub>2</sub> || y<sub>1</sub>)
+//!   where y<sub>3</sub> has k bits and y<sub>4</sub> has
+//!   &lfloor;log<sub>2</sub> C(n, t)&rfloor; bits
+//! - the ciphertext is the McEliece encryption of y<sub>3</sub>
+//!   with error vector Conv(y<sub>4</sub>), followed by y<sub>5</sub>
+//!
+//! Conv maps an integer to a word of weight t using the combinatorial number system.
+//! The message is padded (a 0x80 byte followed by zeros) so that
+//! y<sub>2</sub> || y<sub>1</sub> has at least k + &lfloor;log<sub>2</sub> C(n, t)&rfloor; bits.
+//! See Kobara, K., & Imai, H. (2001),
+//! Semantically secure McEliece public-key cryptosystems - conversions for McEliece PKC.
+//! Public Key Cryptography PKC 2001, 19-35.
+
+use log::debug;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
+
+use super::{hash, PublicKey, SecretKey};
+use crate::{
+    finite_field::{Field, F2},
+    matrix::RowVec,
+    Error, Result,
+};
+
+/// Length in bytes of the random r
+const R_LEN: usize = hash::HASH_LEN;
+
+/// Public constant appended to the message
+const CONST: [u8; 32] = [0; 32];
+
+/// Hash prefix for Gen
+const GEN: u8 = 5;
+
+/// Hash prefix for H
+const HASH: u8 = 6;
+
+impl PublicKey {
+    /// Encrypts a message of arbitrary length
+    ///
+    /// The ciphertext layout is:
+    /// - bytes 0-a: McEliece encryption of y<sub>3</sub>
+    ///   (see [`RowVec`]::[`to_bytes()`])
+    /// - bytes a-b: y<sub>5</sub> (padded with zero bits to a whole number of bytes)
+    ///
+    /// [`RowVec`]: ../matrix/struct.RowVec.html
+    /// [`to_bytes()`]: ../matrix/struct.RowVec.html#method.to_bytes
+    pub fn encrypt_kobara_imai(&self, m: &[u8]) -> Vec<u8> {
+        self.encrypt_kobara_imai_with_rng(&mut rand::thread_rng(), m)
+    }
+
+    /// Like encrypt_kobara_imai, except the random r is drawn from rng
+    pub fn encrypt_kobara_imai_with_rng<R>(&self, rng: &mut R, m: &[u8]) -> Vec<u8>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let f2 = Arc::new(F2::generate(()));
+        let n = self.n();
+        let k = self.k();
+        let l4 = conv_bits(n, self.t);
+
+        let mut m1 = m.to_vec();
+        m1.push(0x80);
+        while 8 * (R_LEN + m1.len() + CONST.len()) < l4 + k {
+            m1.push(0);
+        }
+        m1.extend_from_slice(&CONST);
+
+        let r: [u8; R_LEN] = rng.gen();
+        let y1 = xor(&hash::expand(GEN, &[&r], m1.len()), &m1);
+        let mut y = xor(&r, &hash::hash(HASH, &[&y1]));
+        y.extend(y1);
+
+        let bits = to_bits(&y);
+        let l5 = bits.len() - l4 - k;
+        let e = conv(&bits[l5..l5 + l4], n, self.t);
+        let y3 = RowVec::new(f2, bits[l5 + l4..].iter().map(|&b| b as u32).collect());
+        debug!("Plaintext y3:{}", y3);
+
+        let mut c = self.encrypt_with_error(&y3, &e).to_bytes();
+        c.extend(from_bits(&bits[..l5]));
+        c
+    }
+}
+
+impl SecretKey {
+    /// Decrypts a ciphertext produced by [`encrypt_kobara_imai()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Truncated`] if the ciphertext is too short
+    /// and [`Error::DimensionMismatch`] if its first part does not match the code length.
+    /// Any other rejection (decoding failure, invalid padding or constant)
+    /// is reported as [`Error::InvalidCiphertext`] so as not to tell them apart.
+    ///
+    /// [`Error::Truncated`]: ../enum.Error.html#variant.Truncated
+    /// [`Error::DimensionMismatch`]: ../enum.Error.html#variant.DimensionMismatch
+    /// [`Error::InvalidCiphertext`]: ../enum.Error.html#variant.InvalidCiphertext
+    ///
+    /// [`encrypt_kobara_imai()`]: struct.PublicKey.html#method.encrypt_kobara_imai
+    pub fn decrypt_kobara_imai(&self, c: &[u8]) -> Result<Vec<u8>> {
+        let n = self.p.len();
+        let k = self.s.rows();
+        let t = self.goppa.poly().degree();
+        if c.len() < 4 + crate::div_ceil(n, 8) {
+            return Err(Error::Truncated("ciphertext"));
+        }
+        let (read, c1) = RowVec::from_bytes(c)?;
+        if c1.cols() != n {
+            return Err(Error::DimensionMismatch);
+        }
+        let y5 = &c[read..];
+
+        // y5 has as many bits as needed for y5 || y4 || y3 to fill whole bytes
+        let l4 = conv_bits(n, t);
+        let l5 = match (8 - (l4 + k) % 8) % 8 {
+            0 => 8 * y5.len(),
+            _ if y5.is_empty() => return Err(Error::Truncated("ciphertext")),
+            r => 8 * (y5.len() - 1) + r,
+        };
+        if (l5 + l4 + k) / 8 < R_LEN + 1 + CONST.len() {
+            return Err(Error::Truncated("ciphertext"));
+        }
+
+        self.decrypt_kobara_imai_checked(&c1, y5, l4, l5)
+            .ok_or(Error::InvalidCiphertext)
+    }
+
+    /// Decrypts the ciphertext c1 || y5 once its lengths are checked
+    ///
+    /// Returns None whatever the reason of the rejection.
+    fn decrypt_kobara_imai_checked(
+        &self,
+        c1: &RowVec<F2>,
+        y5: &[u8],
+        l4: usize,
+        l5: usize,
+    ) -> Option<Vec<u8>> {
+        let (y3, e) = match self.context().try_decrypt_with_error(c1) {
+            Ok(decrypted) => decrypted,
+            Err(e) => {
+                debug!("{}: reject the ciphertext", e);
+                return None;
+            }
+        };
+        if e.weight() != self.goppa.poly().degree() {
+            return None;
+        }
+        let y4 = conv_inverse(&e, l4)?;
+
+        let mut bits = to_bits(y5);
+        bits.truncate(l5);
+        if from_bits(&bits) != y5 {
+            return None;
+        }
+        bits.extend(y4);
+        bits.extend(y3.data().iter().map(|&b| b as u8));
+
+        let y = from_bits(&bits);
+        let (y2, y1) = y.split_at(R_LEN);
+        let r = xor(y2, &hash::hash(HASH, &[y1]));
+        let m1 = xor(y1, &hash::expand(GEN, &[&r], y1.len()));
+        let (m1, constant) = m1.split_at(m1.len() - CONST.len());
+        if constant != CONST {
+            return None;
+        }
+
+        match m1.iter().rposition(|&b| b != 0) {
+            Some(i) if m1[i] == 0x80 => Some(m1[..i].to_vec()),
+            _ => None,
+        }
+    }
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+/// Converts bytes to bits (most significant bit first)
+fn to_bits(bytes: &[u8]) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(8 * bytes.len());
+    for byte in bytes {
+        for shift in (0..8).rev() {
+            bits.push((byte >> shift) & 1);
+        }
+    }
+    bits
+}
+
+/// Converts bits to bytes (most significant bit first), padding the last byte with zeros
+fn from_bits(bits: &[u8]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (i, b)| byte | (b << (7 - i)))
+        })
+        .collect()
+}
+
+/// Computes the binomial coefficient C(n, k)
+fn binomial(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    let mut b = BigUint::one();
+    for i in 0..k {
+        b = b * (n - i) / (i + 1);
+    }
+    b
+}
+
+/// Returns &lfloor;log<sub>2</sub> C(n, t)&rfloor;, the number of bits encoded in an error vector
+fn conv_bits(n: usize, t: usize) -> usize {
+    binomial(n, t).bits() as usize - 1
+}
+
+/// Maps bits to a word of length n and weight t
+///
+/// The integer x whose binary decomposition is given by bits
+/// is written as C(c<sub>t</sub>, t) + ... + C(c<sub>1</sub>, 1)
+/// with n > c<sub>t</sub> > ... > c<sub>1</sub> &ge; 0.
+/// The word is the one whose support is {c<sub>1</sub>, ..., c<sub>t</sub>}.
+fn conv(bits: &[u8], n: usize, t: usize) -> RowVec<F2> {
+    let f2 = Arc::new(F2::generate(()));
+    let mut x = bits
+        .iter()
+        .fold(BigUint::zero(), |x, &b| (x << 1usize) + BigUint::from(b));
+    let mut e = RowVec::zero(Arc::clone(&f2), n);
+    let mut c = n;
+    for i in (1..t + 1).rev() {
+        c -= 1;
+        let mut b = binomial(c, i);
+        while b > x {
+            // C(c - 1, i) = C(c, i) * (c - i) / c
+            b = b * (c - i) / c;
+            c -= 1;
+        }
+        x -= b;
+        e[c] = f2.one();
+    }
+    e
+}
+
+/// Inverse of conv()
+///
+/// Returns None if the integer encoded by the word does not fit on len bits.
+fn conv_inverse(e: &RowVec<F2>, len: usize) -> Option<Vec<u8>> {
+    let mut x = BigUint::zero();
+    let mut i = 0;
+    for c in 0..e.cols() {
+        if e[c] != 0 {
+            i += 1;
+            x += binomial(c, i);
+        }
+    }
+    if x.bits() as usize > len {
+        return None;
+    }
+    Some(
+        (0..len)
+            .rev()
+            .map(|j| if x.bit(j as u64) { 1 } else { 0 })
+            .collect(),
+    )
+}
@@ -1,14 +1,20 @@
 //! The McEliece cryptosystem
 
 use log::debug;
-use std::rc::Rc;
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::sync::Arc;
 
 use crate::{
     finite_field::{F2m, Field, F2},
     goppa::Goppa,
-    matrix::{Mat, Perm, RowVec},
+    matrix::{BitMat, BitVec, Perm, RowVec},
+    Error, Result,
 };
 
+pub use context::DecryptionContext;
+pub use params::ParameterSet;
+
 /// Public key of the McEliece cryptosystem
 ///
 /// A public key is a couple (SGP, t) where:
@@ -16,10 +22,13 @@ use crate::{
 ///   (see struct [`SecretKey`] for more)
 /// - t is the correction capacity of the Goppa code (and also the degree of the Goppa polynomial)
 ///
+/// S and P are chosen so that SGP is in standard form (R | I<sub>k</sub>).
+/// Only the k x (n - k) matrix R is stored.
+///
 /// [`SecretKey`]: struct.SecretKey.html
 #[derive(Debug, Eq, PartialEq)]
 pub struct PublicKey {
-    sgp: Mat<F2>,
+    r: BitMat,
     t: usize,
 }
 
@@ -32,6 +41,8 @@ pub struct PublicKey {
 ///
 /// Instead of storing P, we save the corresponding element of the symmetric group.  
 /// info_set is the information set of G.  
+/// rejection is a random secret value used for implicit rejection
+/// by the CCA2-secure modes: an invalid ciphertext decrypts to a hash of this value.  
 /// See <https://en.wikipedia.org/wiki/McEliece_cryptosystem>
 /// for a brief description of the McEliece cryptosystem.  
 /// For more details, see Engelbert, D., Overbeck, R., & Schmidt, A. (2007),
@@ -39,52 +50,150 @@ pub struct PublicKey {
 /// Journal of Mathematical Cryptology JMC, 1(2), 151-199.
 #[derive(Eq, PartialEq)]
 pub struct SecretKey {
-    s: Mat<F2>,
+    s: BitMat,
     goppa: Goppa<F2m>,
     info_set: Vec<usize>,
     p: Perm,
+    rejection: [u8; hash::HASH_LEN],
 }
 
+/// Length in bytes of a key generation seed
+pub const SEED_LEN: usize = 32;
+
 pub fn keygen(n: usize, t: usize) -> (PublicKey, SecretKey) {
-    let q = if t == 1 && n.is_power_of_two() {
-        2 * n
-    } else {
-        n.next_power_of_two()
-    };
-    let fq = Rc::new(F2m::generate(q));
-    let goppa = Goppa::random(fq, n, t);
+    keygen_with_rng(&mut rand::thread_rng(), n, t)
+}
+
+/// Generates a key pair deterministically from a seed
+///
+/// The seed initializes a ChaCha20 generator from which all the randomness is drawn:
+/// the same parameters and seed always yield the same key pair.
+/// Storing the seed is thus enough to recover the secret key (see [`KeySeed`]).
+///
+/// [`KeySeed`]: seed/struct.KeySeed.html
+pub fn keygen_from_seed(n: usize, t: usize, seed: &[u8; SEED_LEN]) -> (PublicKey, SecretKey) {
+    keygen_with_rng(&mut ChaCha20Rng::from_seed(*seed), n, t)
+}
+
+/// Like keygen, except all randomness is drawn from rng
+pub fn keygen_with_rng<R>(rng: &mut R, n: usize, t: usize) -> (PublicKey, SecretKey)
+where
+    R: CryptoRng + RngCore,
+{
+    ParameterSet::new(n, t).keygen_with_rng(rng)
+}
+
+/// Generates a key pair for a Goppa code of length n and degree t on field fq
+pub(crate) fn keygen_in_field<R>(
+    rng: &mut R,
+    fq: Arc<F2m>,
+    n: usize,
+    t: usize,
+) -> (PublicKey, SecretKey)
+where
+    R: CryptoRng + RngCore,
+{
+    let goppa = Goppa::random_with_rng(rng, fq, n, t);
     debug!("{}", goppa);
 
-    let xyz = goppa.parity_check_xyz();
-    let f2 = Rc::new(F2::generate(()));
-    let (g, info_set) = Goppa::generator_from_xyz(&xyz, Rc::clone(&f2));
+    let (g, info_set) = goppa.generator_with_information_set();
     debug!("Generator matrix G:{}", g);
     debug!("Information set of generator matrix G:\n{:?}\n", info_set);
 
     let k = g.rows();
-    let s = Mat::invertible_random(f2, k);
+    let s = BitMat::invertible_random_with_rng(rng, k);
     debug!("Code dimension k = {}", k);
     debug!("Singular matrix S:{}", s);
 
-    let p = Perm::random(n);
+    let p = Perm::random_with_rng(rng, n);
     debug!("Permutation P:\n{:?}\n", p);
 
-    let sgp = &s * &g * &p;
+    // Bring SGP to standard form U * SGP * P1 and update S and P accordingly
+    let (u, sgp, p1) = (&s * &g * &p).standard_form().unwrap();
+    let s = u * s;
+    let p = p * p1;
     debug!("Perturbed generator matrix ~G:{}", sgp);
 
-    let pk = PublicKey { sgp, t };
+    let r = sgp.extract_cols(&(0..n - k).collect::<Vec<usize>>());
+
+    let rejection = rng.gen();
+
+    let pk = PublicKey { r, t };
     let sk = SecretKey {
         s,
         goppa,
         info_set,
         p,
+        rejection,
     };
     (pk, sk)
 }
 
+/// Checks that the secret key is well-formed and that the public key is its own
+///
+/// # Errors
+///
+/// Returns an [`InvalidParameters`] error if:
+/// - the Goppa polynomial is not irreducible
+/// - the Goppa set contains a root of the Goppa polynomial
+/// - S is not invertible
+/// - the information set does not select the identity in the generator matrix G
+/// - SGP is not in standard form
+///
+/// and a [`KeyMismatch`] error if SGP is not the public generator matrix.
+///
+/// [`InvalidParameters`]: ../enum.Error.html#variant.InvalidParameters
+/// [`KeyMismatch`]: ../enum.Error.html#variant.KeyMismatch
+pub fn check_keypair(pk: &PublicKey, sk: &SecretKey) -> Result<()> {
+    let goppa = &sk.goppa;
+    if !goppa.poly().is_irreducible() {
+        return Err(Error::InvalidParameters(
+            "Goppa polynomial is not irreducible",
+        ));
+    }
+    let f = goppa.field();
+    if goppa
+        .set()
+        .iter()
+        .any(|&a| goppa.poly().eval(a) == f.zero())
+    {
+        return Err(Error::InvalidParameters(
+            "Goppa set contains a root of the Goppa polynomial",
+        ));
+    }
+    if !sk.s.is_invertible() {
+        return Err(Error::InvalidParameters("Matrix S is not invertible"));
+    }
+    let g = goppa.generator_matrix();
+    let k = g.rows();
+    if sk.info_set.len() != k || g.extract_cols(&sk.info_set) != BitMat::identity(k) {
+        return Err(Error::InvalidParameters("Invalid information set"));
+    }
+    if sk.public_key_from_generator(&g)? != *pk {
+        return Err(Error::KeyMismatch);
+    }
+    Ok(())
+}
+
 impl PublicKey {
-    pub fn sgp(&self) -> &Mat<F2> {
-        &self.sgp
+    /// Returns the non-identity part R of SGP = (R | I<sub>k</sub>)
+    pub fn r(&self) -> &BitMat {
+        &self.r
+    }
+
+    /// Builds the public generator matrix SGP = (R | I<sub>k</sub>)
+    pub fn sgp(&self) -> BitMat {
+        BitMat::hconcat(&self.r, &BitMat::identity(self.k()))
+    }
+
+    /// Returns the code dimension k
+    pub fn k(&self) -> usize {
+        self.r.rows()
+    }
+
+    /// Returns the code length n
+    pub fn n(&self) -> usize {
+        self.r.rows() + self.r.cols()
     }
 
     pub fn t(&self) -> usize {
@@ -92,18 +201,33 @@ impl PublicKey {
     }
 
     pub fn encrypt(&self, m: &RowVec<F2>) -> RowVec<F2> {
-        let c = Goppa::<F2m>::g_encode(&self.sgp, m);
-        debug!("Encoded plaintext:{}", c);
+        self.encrypt_with_rng(&mut rand::thread_rng(), m)
+    }
 
-        let e = RowVec::random_with_weight(m.field(), self.sgp.cols(), self.t);
+    /// Like encrypt, except the error vector is drawn from rng
+    pub fn encrypt_with_rng<R>(&self, rng: &mut R, m: &RowVec<F2>) -> RowVec<F2>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let e = RowVec::random_with_weight_with_rng(rng, m.field(), self.n(), self.t);
+        self.encrypt_with_error(m, &e)
+    }
+
+    /// Encrypts the plaintext with the given error vector
+    ///
+    /// The codeword m(R | I<sub>k</sub>) is (mR | m).
+    pub(crate) fn encrypt_with_error(&self, m: &RowVec<F2>, e: &RowVec<F2>) -> RowVec<F2> {
+        let m = BitVec::from(m);
+        let c = BitVec::hconcat(&(&m * &self.r), &m);
+        debug!("Encoded plaintext:{}", c);
         debug!("Error vector:{}", e);
 
-        c + e
+        RowVec::from(&c) + e
     }
 }
 
 impl SecretKey {
-    pub fn s(&self) -> &Mat<F2> {
+    pub fn s(&self) -> &BitMat {
         &self.s
     }
 
@@ -119,20 +243,70 @@ impl SecretKey {
         &self.p
     }
 
-    pub fn decrypt(&self, c: &RowVec<F2>) -> RowVec<F2> {
-        let c1 = c * self.p.inverse();
-        let m1 = self.goppa.decode(&c1);
-        debug!("Decoded codeword mSG:{}", m1);
+    pub fn rejection(&self) -> &[u8; hash::HASH_LEN] {
+        &self.rejection
+    }
+
+    /// Recomputes the public key SGP from the Goppa code, S and P
+    ///
+    /// # Panics
+    ///
+    /// Panics if SGP is not in standard form, which [`keygen()`] guarantees.
+    /// See [`check_keypair()`] to validate a secret key read from elsewhere.
+    ///
+    /// [`keygen()`]: fn.keygen.html
+    /// [`check_keypair()`]: fn.check_keypair.html
+    pub fn public_key(&self) -> PublicKey {
+        self.try_public_key().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like public_key, except an inconsistent secret key is reported as an error
+    pub fn try_public_key(&self) -> Result<PublicKey> {
+        self.public_key_from_generator(&self.goppa.generator_matrix())
+    }
 
-        let ms = m1.extract_cols(&self.info_set);
-        debug!(
-            "Use information set {:?} to extract mS:{}",
-            self.info_set, ms
-        );
+    /// Computes the public key SGP given the generator matrix G of the Goppa code
+    fn public_key_from_generator(&self, g: &BitMat) -> Result<PublicKey> {
+        let n = self.goppa.len();
+        let k = g.rows();
+        if self.s.rows() != k || self.p.len() != n {
+            return Err(Error::DimensionMismatch);
+        }
+        let sgp = &self.s * g * &self.p;
+        if !sgp.is_standard_form() {
+            return Err(Error::InvalidParameters("SGP is not in standard form"));
+        }
+        Ok(PublicKey {
+            r: sgp.extract_cols(&(0..n - k).collect::<Vec<usize>>()),
+            t: self.goppa.poly().degree(),
+        })
+    }
+
+    pub fn decrypt(&self, c: &RowVec<F2>) -> RowVec<F2> {
+        self.decrypt_with_error(c).0
+    }
 
-        let m = ms * self.s.inverse().unwrap();
-        m
+    /// Decrypts the ciphertext and also returns the error vector found by the decoder
+    pub(crate) fn decrypt_with_error(&self, c: &RowVec<F2>) -> (RowVec<F2>, RowVec<F2>) {
+        self.context().decrypt_with_error(c)
     }
 }
 
+mod hash;
+
+pub mod armor;
+pub mod cca2;
+pub mod container;
+pub mod context;
+pub mod hybrid;
 pub mod io;
+pub mod isd;
+pub mod kat;
+pub mod kem;
+pub mod kobara_imai;
+pub mod params;
+pub mod passphrase;
+pub mod seed;
+
+#[cfg(feature = "serde")]
+mod serde;
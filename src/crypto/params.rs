@@ -0,0 +1,203 @@
+//! Named parameter sets
+//!
+//! The Classic McEliece sets come from Bernstein, D. J., Chou, T., Lange, T., et al. (2017),
+//! Classic McEliece: conservative code-based cryptography.
+//! The toy sets are only meant for tests and demonstrations.
+
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+};
+
+use super::{hash, PublicKey, SecretKey, SEED_LEN};
+use crate::{
+    finite_field::{f2m, F2m},
+    goppa,
+};
+
+/// Parameters of the McEliece cryptosystem
+///
+/// The Goppa code has length n and corrects t errors.
+/// Its support lies in the field F<sub>2<sup>m</sup></sub> = F<sub>2</sub>\[x\]/(poly)
+/// (see [`F2m::from_poly()`] for the representation of poly).
+///
+/// [`F2m::from_poly()`]: ../../finite_field/struct.F2m.html#method.from_poly
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParameterSet {
+    pub name: &'static str,
+    pub n: usize,
+    pub t: usize,
+    pub m: u32,
+    pub poly: u32,
+    /// NIST security category (0 for toy sets)
+    pub security_level: u32,
+}
+
+/// Named parameter sets
+pub const PARAMETER_SETS: [ParameterSet; 8] = [
+    ParameterSet {
+        name: "toy64",
+        n: 64,
+        t: 4,
+        m: 6,
+        poly: 0x43,
+        security_level: 0,
+    },
+    ParameterSet {
+        name: "toy256",
+        n: 256,
+        t: 12,
+        m: 8,
+        poly: 0x11D,
+        security_level: 0,
+    },
+    ParameterSet {
+        name: "toy1024",
+        n: 1024,
+        t: 50,
+        m: 10,
+        poly: 0x409,
+        security_level: 0,
+    },
+    ParameterSet {
+        name: "mceliece348864",
+        n: 3488,
+        t: 64,
+        m: 12,
+        poly: 0x1009,
+        security_level: 1,
+    },
+    ParameterSet {
+        name: "mceliece460896",
+        n: 4608,
+        t: 96,
+        m: 13,
+        poly: 0x201B,
+        security_level: 3,
+    },
+    ParameterSet {
+        name: "mceliece6688128",
+        n: 6688,
+        t: 128,
+        m: 13,
+        poly: 0x201B,
+        security_level: 5,
+    },
+    ParameterSet {
+        name: "mceliece6960119",
+        n: 6960,
+        t: 119,
+        m: 13,
+        poly: 0x201B,
+        security_level: 5,
+    },
+    ParameterSet {
+        name: "mceliece8192128",
+        n: 8192,
+        t: 128,
+        m: 13,
+        poly: 0x201B,
+        security_level: 5,
+    },
+];
+
+impl ParameterSet {
+    /// Builds unnamed parameters for a code of length n correcting t errors
+    ///
+    /// The field is the smallest one that can hold the code support
+    /// and is defined by the default polynomial (see [`primitive_poly()`]).
+    ///
+    /// [`primitive_poly()`]: ../../finite_field/f2m/fn.primitive_poly.html
+    pub fn new(n: usize, t: usize) -> Self {
+        let q = if t == 1 && n.is_power_of_two() {
+            2 * n
+        } else {
+            n.next_power_of_two()
+        };
+        Self {
+            name: "custom",
+            n,
+            t,
+            m: q.trailing_zeros(),
+            poly: f2m::primitive_poly(q),
+            security_level: 0,
+        }
+    }
+
+    /// Looks up a parameter set by name
+    pub fn from_name(name: &str) -> Option<Self> {
+        PARAMETER_SETS.iter().find(|p| p.name == name).copied()
+    }
+
+    /// Checks that the parameters define a valid Goppa code
+    ///
+    /// See [`goppa::check_parameters()`](../../goppa/fn.check_parameters.html).
+    pub fn check(&self) -> Result<(), &'static str> {
+        if !(2..=16).contains(&self.m) {
+            return Err("m must be at least 2 and at most 16");
+        }
+        if 31 - self.poly.leading_zeros() != self.m {
+            return Err("Field polynomial must be of degree m");
+        }
+        goppa::check_parameters(1 << self.m, self.n, self.t)
+    }
+
+    /// Returns the code dimension k = n - mt
+    pub fn k(&self) -> usize {
+        self.n - self.m as usize * self.t
+    }
+
+    /// Returns the length in bytes of an encoded public key
+    ///
+    /// See [`PublicKey::to_bytes()`](../struct.PublicKey.html#method.to_bytes).
+    pub fn public_key_len(&self) -> usize {
+        let k = self.k();
+        4 + 4 + crate::div_ceil(k * (self.n - k), 8) + 4
+    }
+
+    /// Returns the length in bytes of an encoded secret key
+    ///
+    /// See [`SecretKey::to_bytes()`](../struct.SecretKey.html#method.to_bytes).
+    pub fn secret_key_len(&self) -> usize {
+        let k = self.k();
+        let s = 4 + 4 + crate::div_ceil(k * k, 8);
+        let goppa = 4 + 4 + 4 * (self.t + 1) + crate::div_ceil(1 << self.m, 8);
+        4 + s + goppa + 4 * k + 4 + 4 * self.n + hash::HASH_LEN
+    }
+
+    /// Generates the field F<sub>2<sup>m</sup></sub>
+    pub fn field(&self) -> F2m {
+        F2m::from_poly(self.poly)
+    }
+
+    pub fn keygen(&self) -> (PublicKey, SecretKey) {
+        self.keygen_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a key pair deterministically from a seed
+    ///
+    /// See [`keygen_from_seed()`](../fn.keygen_from_seed.html).
+    pub fn keygen_from_seed(&self, seed: &[u8; SEED_LEN]) -> (PublicKey, SecretKey) {
+        self.keygen_with_rng(&mut ChaCha20Rng::from_seed(*seed))
+    }
+
+    /// Like keygen, except all randomness is drawn from rng
+    pub fn keygen_with_rng<R>(&self, rng: &mut R) -> (PublicKey, SecretKey)
+    where
+        R: CryptoRng + RngCore,
+    {
+        super::keygen_in_field(rng, Arc::new(self.field()), self.n, self.t)
+    }
+}
+
+impl Display for ParameterSet {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (n={}, t={}, m={}, poly={:#X}, level {})",
+            self.name, self.n, self.t, self.m, self.poly, self.security_level
+        )
+    }
+}
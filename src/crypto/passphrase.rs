@@ -0,0 +1,273 @@
+//! Encryption of secret keys under a passphrase
+//!
+//! A key is derived from the passphrase and a random salt with the memory-hard
+//! function Argon2id and the encoded secret key is encrypted and authenticated
+//! with ChaCha20-Poly1305.
+//! The encrypted key is saved in a [`container`] of type
+//! [`EncryptedSecretKey`] whose payload has the following layout:
+//! - bytes 0-3: memory cost of Argon2id in KiB
+//! - bytes 4-7: number of passes of Argon2id
+//! - bytes 8-11: degree of parallelism of Argon2id
+//! - bytes 12-27: salt
+//! - bytes 28-39: nonce
+//! - bytes 40-x: encrypted secret key followed by its 16 bytes tag
+//!
+//! Bytes 0-39 are authenticated along with the secret key.
+//! Since they can only be authenticated once the key is derived,
+//! costs above [`KdfParams::MAX`] are rejected beforehand.
+//!
+//! [`container`]: ../container/index.html
+//! [`EncryptedSecretKey`]: ../container/enum.ObjectType.html#variant.EncryptedSecretKey
+//! [`KdfParams::MAX`]: struct.KdfParams.html#associatedconstant.MAX
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::{CryptoRng, RngCore};
+use std::{
+    fs::File,
+    io::{Read, Write},
+};
+
+use super::{
+    container::{self, ObjectType},
+    SecretKey,
+};
+use crate::{Error, Result};
+
+/// Length in bytes of the salt
+const SALT_LEN: usize = 16;
+
+/// Length in bytes of a nonce
+const NONCE_LEN: usize = 12;
+
+/// Length in bytes of the derived key
+const KEY_LEN: usize = 32;
+
+/// Length in bytes of the authenticated header of the payload
+const HEADER_LEN: usize = 12 + SALT_LEN + NONCE_LEN;
+
+/// Costs of the Argon2id key derivation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KdfParams {
+    /// Memory size in KiB
+    pub m_cost: u32,
+    /// Number of passes
+    pub t_cost: u32,
+    /// Degree of parallelism
+    pub p_cost: u32,
+}
+
+/// The default costs are those recommended by the argon2 crate
+/// (19 MiB of memory, 2 passes, no parallelism)
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Highest costs accepted, sixteen times the default ones
+    /// (304 MiB of memory, 32 passes, 16 lanes)
+    pub const MAX: Self = Self {
+        m_cost: 16 * Params::DEFAULT_M_COST,
+        t_cost: 16 * Params::DEFAULT_T_COST,
+        p_cost: 16 * Params::DEFAULT_P_COST,
+    };
+
+    fn exceeds_max(&self) -> bool {
+        self.m_cost > Self::MAX.m_cost
+            || self.t_cost > Self::MAX.t_cost
+            || self.p_cost > Self::MAX.p_cost
+    }
+
+    fn derive_key(&self, passphrase: &[u8], salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LEN))
+            .map_err(|_| Error::InvalidParameters("Invalid key derivation costs"))?;
+        let mut key = [0; KEY_LEN];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, salt, &mut key)
+            .map_err(|_| Error::InvalidParameters("Invalid key derivation input"))?;
+        Ok(key)
+    }
+}
+
+/// Encrypts the bytes of a secret key under a passphrase
+pub(crate) fn lock<R>(
+    rng: &mut R,
+    plaintext: &[u8],
+    passphrase: &[u8],
+    kdf: &KdfParams,
+) -> Result<Vec<u8>>
+where
+    R: CryptoRng + RngCore,
+{
+    if kdf.exceeds_max() {
+        return Err(Error::InvalidParameters(
+            "Key derivation costs exceed the maximum",
+        ));
+    }
+    let mut vec = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+    for &x in &[kdf.m_cost, kdf.t_cost, kdf.p_cost] {
+        vec.extend_from_slice(&x.to_be_bytes());
+    }
+    vec.resize(HEADER_LEN, 0);
+    rng.fill_bytes(&mut vec[12..HEADER_LEN]);
+    let key = kdf.derive_key(passphrase, &vec[12..12 + SALT_LEN])?;
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(
+            Nonce::from_slice(&vec[12 + SALT_LEN..HEADER_LEN]),
+            Payload {
+                msg: plaintext,
+                aad: &vec,
+            },
+        )
+        .map_err(|_| Error::Malformed("Secret key is too long"))?;
+    vec.extend(ciphertext);
+    Ok(vec)
+}
+
+/// Decrypts the bytes of a secret key encrypted by [`lock()`](fn.lock.html)
+pub(crate) fn unlock(payload: &[u8], passphrase: &[u8]) -> Result<Vec<u8>> {
+    if payload.len() < HEADER_LEN {
+        return Err(Error::Truncated(ObjectType::EncryptedSecretKey.name()));
+    }
+    let kdf = KdfParams {
+        m_cost: crate::read_u32(payload, 0, "encrypted secret key")?,
+        t_cost: crate::read_u32(payload, 4, "encrypted secret key")?,
+        p_cost: crate::read_u32(payload, 8, "encrypted secret key")?,
+    };
+    if kdf.exceeds_max() {
+        return Err(Error::Malformed("Key derivation costs exceed the maximum"));
+    }
+    let key = kdf.derive_key(passphrase, &payload[12..12 + SALT_LEN])?;
+    ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(
+            Nonce::from_slice(&payload[12 + SALT_LEN..HEADER_LEN]),
+            Payload {
+                msg: &payload[HEADER_LEN..],
+                aad: &payload[..HEADER_LEN],
+            },
+        )
+        .map_err(|_| Error::WrongPassphrase)
+}
+
+impl SecretKey {
+    /// Saves secret key on disk, encrypted under a passphrase with the default costs
+    pub fn write_encrypted(&self, file_name: &str, passphrase: &[u8]) -> Result<()> {
+        self.write_encrypted_to(File::create(file_name)?, passphrase, &KdfParams::default())
+    }
+
+    /// Writes secret key encrypted under a passphrase to any writer
+    pub fn write_encrypted_to<W: Write>(
+        &self,
+        writer: W,
+        passphrase: &[u8],
+        kdf: &KdfParams,
+    ) -> Result<()> {
+        self.write_encrypted_to_with_rng(&mut rand::thread_rng(), writer, passphrase, kdf)
+    }
+
+    /// Like write_encrypted_to, except the salt and the nonce are drawn from rng
+    pub fn write_encrypted_to_with_rng<R, W>(
+        &self,
+        rng: &mut R,
+        writer: W,
+        passphrase: &[u8],
+        kdf: &KdfParams,
+    ) -> Result<()>
+    where
+        R: CryptoRng + RngCore,
+        W: Write,
+    {
+        let payload = lock(rng, &self.to_bytes(), passphrase, kdf)?;
+        container::write_to(
+            writer,
+            ObjectType::EncryptedSecretKey,
+            &self.params(),
+            &payload,
+        )
+    }
+
+    /// Writes secret key encrypted under a passphrase in [`armor`] to any writer
+    ///
+    /// [`armor`]: armor/index.html
+    pub fn write_encrypted_armored_to<W: Write>(
+        &self,
+        writer: W,
+        passphrase: &[u8],
+        kdf: &KdfParams,
+    ) -> Result<()> {
+        self.write_encrypted_armored_to_with_rng(&mut rand::thread_rng(), writer, passphrase, kdf)
+    }
+
+    /// Like write_encrypted_armored_to, except the salt and the nonce are drawn from rng
+    pub fn write_encrypted_armored_to_with_rng<R, W>(
+        &self,
+        rng: &mut R,
+        writer: W,
+        passphrase: &[u8],
+        kdf: &KdfParams,
+    ) -> Result<()>
+    where
+        R: CryptoRng + RngCore,
+        W: Write,
+    {
+        let payload = lock(rng, &self.to_bytes(), passphrase, kdf)?;
+        container::write_armored_to(
+            writer,
+            ObjectType::EncryptedSecretKey,
+            &self.params(),
+            &payload,
+        )
+    }
+
+    /// Reads a secret key saved on disk, encrypted under the passphrase or not
+    pub fn read_secret_key_with_passphrase(file_name: &str, passphrase: &[u8]) -> Result<Self> {
+        Self::read_from_with_passphrase(File::open(file_name)?, passphrase)
+    }
+
+    /// Reads a secret key, encrypted under the passphrase or not, until the end of the reader
+    ///
+    /// Fails with [`Error::WrongPassphrase`] if the passphrase does not decrypt the key.
+    ///
+    /// [`Error::WrongPassphrase`]: ../enum.Error.html#variant.WrongPassphrase
+    pub fn read_from_with_passphrase<R: Read>(reader: R, passphrase: &[u8]) -> Result<Self> {
+        Self::read_protected(reader, || Ok::<_, Error>(passphrase))
+    }
+
+    /// Reads a secret key saved on disk, asking prompt for the passphrase if it is encrypted
+    ///
+    /// Prompt is not called for a key stored in the clear,
+    /// so that encrypted and clear keys are read transparently.
+    pub fn read_secret_key_with_prompt<F, P, E>(
+        file_name: &str,
+        prompt: F,
+    ) -> std::result::Result<Self, E>
+    where
+        F: FnOnce() -> std::result::Result<P, E>,
+        P: AsRef<[u8]>,
+        E: From<Error>,
+    {
+        Self::read_from_with_prompt(File::open(file_name).map_err(Error::from)?, prompt)
+    }
+
+    /// Reads a secret key like [`read_secret_key_with_prompt()`] does, until the end of the reader
+    ///
+    /// [`read_secret_key_with_prompt()`]: #method.read_secret_key_with_prompt
+    pub fn read_from_with_prompt<R, F, P, E>(reader: R, prompt: F) -> std::result::Result<Self, E>
+    where
+        R: Read,
+        F: FnOnce() -> std::result::Result<P, E>,
+        P: AsRef<[u8]>,
+        E: From<Error>,
+    {
+        Self::read_protected(reader, prompt)
+    }
+}
@@ -0,0 +1,175 @@
+//! Secret keys stored as the seed they are derived from
+//!
+//! A key pair generated by [`keygen_from_seed()`] only depends on the parameters
+//! and on the seed. A [`KeySeed`] records both in a few bytes,
+//! and [`SecretKey::read_secret_key()`] derives the secret key again when it reads one.
+//!
+//! [`keygen_from_seed()`]: ../params/struct.ParameterSet.html#method.keygen_from_seed
+//! [`KeySeed`]: struct.KeySeed.html
+//! [`SecretKey::read_secret_key()`]: ../struct.SecretKey.html#method.read_secret_key
+
+use std::{
+    convert::TryInto,
+    fs::File,
+    io::{Read, Write},
+};
+
+use super::{
+    container::{self, CodeParams, ObjectType},
+    params::PARAMETER_SETS,
+    ParameterSet, PublicKey, SecretKey, SEED_LEN,
+};
+use crate::{finite_field::F2m, Error, Result};
+
+const KEY_SEED_LEN: usize = 16 + SEED_LEN;
+
+/// Parameters and seed of a key pair
+#[derive(Clone, Eq, PartialEq)]
+pub struct KeySeed {
+    params: ParameterSet,
+    seed: [u8; SEED_LEN],
+}
+
+impl KeySeed {
+    pub fn new(params: ParameterSet, seed: [u8; SEED_LEN]) -> Self {
+        Self { params, seed }
+    }
+
+    pub fn params(&self) -> &ParameterSet {
+        &self.params
+    }
+
+    pub fn seed(&self) -> &[u8; SEED_LEN] {
+        &self.seed
+    }
+
+    /// Derives the key pair from the seed
+    pub fn keygen(&self) -> (PublicKey, SecretKey) {
+        self.params.keygen_from_seed(&self.seed)
+    }
+
+    /// Derives the secret key from the seed
+    pub fn secret_key(&self) -> SecretKey {
+        self.keygen().1
+    }
+
+    /// Returns the code parameters of the keys, with the designed dimension n - mt
+    pub fn code_params(&self) -> CodeParams {
+        CodeParams {
+            n: self.params.n,
+            k: self.params.k(),
+            t: self.params.t,
+            poly: self.params.poly,
+        }
+    }
+
+    /// Encodes the parameters and the seed in bytes
+    ///
+    /// The encoded byte vector has the following layout:
+    /// - bytes 0-3: length n of the code
+    /// - bytes 4-7: correction capacity t
+    /// - bytes 8-11: degree m of the field polynomial
+    /// - bytes 12-15: field polynomial (see [`F2m::from_poly()`])
+    /// - bytes 16-48: seed
+    ///
+    /// [`F2m::from_poly()`]: ../../finite_field/struct.F2m.html#method.from_poly
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut vec = Vec::with_capacity(KEY_SEED_LEN);
+        for &x in &[
+            self.params.n as u32,
+            self.params.t as u32,
+            self.params.m,
+            self.params.poly,
+        ] {
+            vec.extend_from_slice(&x.to_be_bytes());
+        }
+        vec.extend_from_slice(&self.seed);
+        vec
+    }
+
+    /// Decodes bytes encoded with [`to_bytes()`]
+    ///
+    /// Parameters matching a named set are given its name.
+    /// Fails if the parameters do not define a valid code.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<Self> {
+        if vec.len() != KEY_SEED_LEN {
+            return Err(Error::Malformed("Invalid secret key seed length"));
+        }
+        let name = ObjectType::KeySeed.name();
+        let mut params = ParameterSet {
+            name: "custom",
+            n: crate::read_u32(vec, 0, name)? as usize,
+            t: crate::read_u32(vec, 4, name)? as usize,
+            m: crate::read_u32(vec, 8, name)?,
+            poly: crate::read_u32(vec, 12, name)?,
+            security_level: 0,
+        };
+        if let Some(named) = PARAMETER_SETS
+            .iter()
+            .find(|p| (p.n, p.t, p.m, p.poly) == (params.n, params.t, params.m, params.poly))
+        {
+            params = *named;
+        }
+        params.check().map_err(Error::InvalidParameters)?;
+        F2m::try_from_poly(params.poly)?;
+        let seed = vec[16..].try_into().unwrap();
+        Ok(Self { params, seed })
+    }
+
+    /// Saves the seed on disk
+    ///
+    /// The seed is encoded with [`to_bytes()`] and wrapped in a [`container`].
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    /// [`container`]: ../container/index.html
+    pub fn write(&self, file_name: &str) -> Result<()> {
+        self.write_to(File::create(file_name)?)
+    }
+
+    /// Writes the seed like [`write()`] does, to any writer
+    ///
+    /// [`write()`]: #method.write
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_to(
+            writer,
+            ObjectType::KeySeed,
+            &self.code_params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Writes the seed in [`armor`], to any writer
+    ///
+    /// [`armor`]: ../armor/index.html
+    pub fn write_armored_to<W: Write>(&self, writer: W) -> Result<()> {
+        container::write_armored_to(
+            writer,
+            ObjectType::KeySeed,
+            &self.code_params(),
+            &self.to_bytes(),
+        )
+    }
+
+    /// Reads a seed saved with [`write()`]
+    ///
+    /// [`write()`]: #method.write
+    pub fn read_seed(file_name: &str) -> Result<Self> {
+        Self::read_from(File::open(file_name)?)
+    }
+
+    /// Reads a seed like [`read_seed()`] does, until the end of the reader
+    ///
+    /// [`read_seed()`]: #method.read_seed
+    pub fn read_from<R: Read>(reader: R) -> Result<Self> {
+        let (params, payload) = container::read_from(reader, ObjectType::KeySeed)?;
+        let seed = Self::from_bytes(&payload)?;
+        if params.is_some_and(|params| params != seed.code_params()) {
+            return Err(Error::Malformed(
+                "Header does not match the secret key seed",
+            ));
+        }
+        Ok(seed)
+    }
+}
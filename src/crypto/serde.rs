@@ -0,0 +1,46 @@
+//! Serialization of keys with serde
+//!
+//! Keys are serialized as the bytes of their `to_bytes()` encoding,
+//! so that deserialization performs the checks of `from_bytes()`.
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{PublicKey, SecretKey};
+
+impl Serialize for PublicKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = crate::deserialize_bytes(deserializer)?;
+        PublicKey::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for SecretKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = crate::deserialize_bytes(deserializer)?;
+        SecretKey::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
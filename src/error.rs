@@ -0,0 +1,101 @@
+//! Errors of the fallible functions of the crate
+//!
+//! Constructors, arithmetic operators and deserializers panic on invalid input.
+//! Their `try_` variants and the deserializers (`from_bytes()`, `read_*()`)
+//! report the problem with an [`Error`](enum.Error.html) instead.
+
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+/// Error of the mceliece crate
+#[derive(Debug)]
+pub enum Error {
+    /// Matrix, vector or polynomial without any coefficient
+    Empty,
+    /// Operands whose dimensions don't match
+    DimensionMismatch,
+    /// Operands defined on different fields
+    FieldMismatch,
+    /// Parameters which do not define a supported finite field
+    InvalidField(&'static str),
+    /// Parameters which do not define a valid object (Goppa code, permutation, ...)
+    InvalidParameters(&'static str),
+    /// Encoded data ending before the object it holds (the object is named)
+    Truncated(&'static str),
+    /// Encoded data which do not describe a valid object
+    Malformed(&'static str),
+    /// Text file missing a required field (the field is named)
+    MissingField(&'static str),
+    /// Known-answer test record whose recomputation gives another value (the value is named)
+    KnownAnswerMismatch { count: usize, field: &'static str },
+    /// File holding another kind of object than the one expected
+    UnexpectedObject {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// File written in a format version this crate cannot read
+    UnsupportedVersion(u8),
+    /// File whose checksum does not match its content
+    ChecksumMismatch,
+    /// Received word at distance greater than t of the code
+    DecodingFailure,
+    /// Ciphertext rejected by a CCA2 conversion, whatever the reason
+    InvalidCiphertext,
+    /// Public key which is not the one of the secret key
+    KeyMismatch,
+    /// Secret key encrypted under a passphrase read without passphrase
+    PassphraseRequired,
+    /// Passphrase which does not decrypt the secret key, or encrypted key tampered with
+    WrongPassphrase,
+    /// Failure to read or write a file
+    Io(io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "Empty matrix, vector or polynomial"),
+            Error::DimensionMismatch => write!(f, "Dimensions don't match"),
+            Error::FieldMismatch => write!(f, "Fields don't match"),
+            Error::InvalidField(s) | Error::InvalidParameters(s) | Error::Malformed(s) => {
+                write!(f, "{}", s)
+            }
+            Error::Truncated(s) => write!(f, "Truncated {}", s),
+            Error::MissingField(s) => write!(f, "Missing field '{}'", s),
+            Error::KnownAnswerMismatch { count, field } => {
+                write!(f, "Record {}: {} does not match", count, field)
+            }
+            Error::UnexpectedObject { expected, found } => {
+                write!(f, "Expected a {}, found a {}", expected, found)
+            }
+            Error::UnsupportedVersion(v) => write!(f, "Unsupported format version {}", v),
+            Error::ChecksumMismatch => write!(f, "Checksum mismatch"),
+            Error::DecodingFailure => write!(f, "Too many errors to decode"),
+            Error::InvalidCiphertext => write!(f, "Invalid ciphertext"),
+            Error::KeyMismatch => write!(f, "Public and secret keys don't match"),
+            Error::PassphraseRequired => write!(f, "Secret key is encrypted under a passphrase"),
+            Error::WrongPassphrase => write!(f, "Wrong passphrase or corrupted secret key"),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
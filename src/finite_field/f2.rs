@@ -1,6 +1,6 @@
 //! The finite field of order 2
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::{CryptoRng, Rng, RngCore};
 
 use super::{CharacteristicTwo, F2FiniteExtension, Field, FiniteField};
 
@@ -128,7 +128,7 @@ impl Field for F2 {
     }
 
     /// Returns a random element of the field
-    fn random_element(&self, rng: &mut ThreadRng) -> Self::FieldElement {
+    fn random_element<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Self::FieldElement {
         rng.gen_range(0, 2)
     }
 }
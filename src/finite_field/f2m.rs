@@ -1,21 +1,145 @@
 //! Finite fields of characteristic 2
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::{CryptoRng, Rng, RngCore};
 
 use super::{CharacteristicTwo, F2FiniteExtension, Field, FiniteField};
+use crate::{Error, Result};
 
 /// Finite field of order 2<sup>m</sup>
 #[derive(Eq)]
 pub struct F2m {
     order: usize,
     m: u32,
+    poly: u32,
     exp: Vec<<Self as Field>::FieldElement>,
     log: Vec<u32>,
 }
 
 impl PartialEq for F2m {
     fn eq(&self, other: &Self) -> bool {
-        self.order == other.order
+        self.order == other.order && self.poly == other.poly
+    }
+}
+
+impl F2m {
+    /// Generates finite field F<sub>2</sub>\[x\]/(poly)
+    ///
+    /// The polynomial is given as a number whose binary representation
+    /// matches the nonzero coefficients of the polynomial
+    /// (e.g. 0x1009 for x<sup>12</sup> + x<sup>3</sup> + 1).
+    /// It need only be irreducible: if x is not a primitive element,
+    /// a primitive element is searched for.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if poly has degree less than 2 or greater than 16.
+    /// - Panics if poly is not irreducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mceliece::finite_field::{Field, FiniteField, F2m};
+    /// let f4096 = F2m::from_poly(0x1009);
+    /// assert_eq!(f4096.order(), 4096);
+    /// assert_eq!(f4096.poly(), 0x1009);
+    /// ```
+    pub fn from_poly(poly: u32) -> Self {
+        Self::try_from_poly(poly).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like from_poly, except an invalid polynomial is reported as an error
+    pub fn try_from_poly(poly: u32) -> Result<Self> {
+        if !(1 << 2..1 << 17).contains(&poly) {
+            return Err(Error::InvalidField(
+                "Polynomial degree must be at least 2 and at most 16",
+            ));
+        }
+        let m = 31 - poly.leading_zeros();
+        let order = 1 << m;
+        let mul = |mut a: u32, mut b: u32| {
+            let mut c = 0;
+            while b != 0 {
+                if b & 1 == 1 {
+                    c ^= a;
+                }
+                b >>= 1;
+                a <<= 1;
+                if a & order as u32 != 0 {
+                    a ^= poly;
+                }
+            }
+            c
+        };
+
+        let pow = |mut a: u32, mut e: u32| {
+            let mut b = 1;
+            while e != 0 {
+                if e & 1 == 1 {
+                    b = mul(b, a);
+                }
+                e >>= 1;
+                a = mul(a, a);
+            }
+            b
+        };
+
+        // Rabin's test: poly is irreducible if and only if x^(2^m) = x modulo poly
+        // and x^(2^(m/p)) - x is coprime with poly for every prime p dividing m
+        let frobenius = |i: u32| (0..i).fold(2, |a, _| mul(a, a));
+        let mut m_prime_factors = trial_division(m);
+        m_prime_factors.dedup();
+        if frobenius(m) != 2
+            || m_prime_factors
+                .iter()
+                .any(|&p| gcd_f2x(poly, frobenius(m / p) ^ 2) != 1)
+        {
+            return Err(Error::InvalidField("Polynomial is not irreducible"));
+        }
+
+        // Look for an element of multiplicative order q - 1,
+        // i.e. an element g such that g^((q-1)/p) != 1 for every prime p dividing q - 1
+        let mut q_prime_factors = trial_division(order as u32 - 1);
+        q_prime_factors.dedup();
+        let primitive = (2..order as u32)
+            .find(|&g| {
+                q_prime_factors
+                    .iter()
+                    .all(|&p| pow(g, (order as u32 - 1) / p) != 1)
+            })
+            .unwrap();
+        let mut exp = vec![1; order];
+        for i in 1..order - 1 {
+            exp[i] = mul(exp[i - 1], primitive);
+        }
+
+        let mut log = vec![0; order];
+        for (i, &elt) in exp.iter().enumerate() {
+            log[elt as usize] = i as u32;
+        }
+        Ok(Self {
+            order,
+            m,
+            poly,
+            exp,
+            log,
+        })
+    }
+
+    /// Like [`generate()`](#method.generate), except an invalid order is reported as an error
+    pub fn try_generate(order: usize) -> Result<Self> {
+        if !order.is_power_of_two() || !(4..=1 << 16).contains(&order) {
+            return Err(Error::InvalidField(
+                "Field order must be a power of 2 between 4 and 2^16",
+            ));
+        }
+        Ok(Self::generate(order))
+    }
+
+    /// Returns the polynomial defining the field
+    ///
+    /// See [`from_poly()`](#method.from_poly) for the representation.
+    pub fn poly(&self) -> u32 {
+        self.poly
     }
 }
 
@@ -44,11 +168,12 @@ impl Field for F2m {
     fn generate(order: Self::FieldParameters) -> Self {
         let (_, m) = match prime_power(order as u32) {
             Ok(r) => r,
-            Err(s) => panic!(s),
+            Err(s) => panic!("{}", s),
         };
         let mut f = Self {
             order,
             m,
+            poly: primitive_poly(order),
             exp: vec![0; order],
             log: vec![0; order],
         };
@@ -164,7 +289,7 @@ impl Field for F2m {
         }
     }
 
-    fn random_element(&self, rng: &mut ThreadRng) -> Self::FieldElement {
+    fn random_element<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Self::FieldElement {
         rng.gen_range(0, self.order as u32)
     }
 }
@@ -239,7 +364,7 @@ pub fn primitive_poly(order: usize) -> u32 {
             16 => 0x110B,
             _ => panic!("m must be at least 2 and at most 16"),
         },
-        Err(s) => panic!(s),
+        Err(s) => panic!("{}", s),
     }
 }
 
@@ -262,6 +387,18 @@ pub fn prime_power(q: u32) -> std::result::Result<(u32, u32), &'static str> {
     Ok((p, m))
 }
 
+/// Computes the gcd of two polynomials of F<sub>2</sub>\[x\] given as numbers
+fn gcd_f2x(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let deg_b = 31 - b.leading_zeros();
+        while a != 0 && 31 - a.leading_zeros() >= deg_b {
+            a ^= b << (31 - a.leading_zeros() - deg_b);
+        }
+        std::mem::swap(&mut a, &mut b);
+    }
+    a
+}
+
 /// Computes the prime factors of a nonzero integer by trial division  
 /// <https://en.wikipedia.org/wiki/Trial_division>
 /// ```
@@ -380,4 +517,35 @@ mod test {
             assert_eq!(f.mul(a, f.inv(a).unwrap()), i);
         }
     }
+
+    #[test]
+    fn from_poly_default() {
+        for m in 2..12 {
+            let f = F2m::generate(1 << m);
+            let g = F2m::from_poly(primitive_poly(1 << m));
+            assert!(f == g);
+            assert_eq!(f.exp, g.exp);
+            assert_eq!(f.log, g.log);
+        }
+    }
+
+    #[test]
+    fn from_poly_non_primitive() {
+        // x^4 + x^3 + x^2 + x + 1 is irreducible but x has order 5
+        let f = F2m::from_poly(0x1F);
+        assert_eq!(f.order(), 16);
+        assert!(f != F2m::generate(16));
+        for a in 1..16 {
+            let inv = f.inv(a).unwrap();
+            assert_eq!(f.mul(a, inv), f.one());
+        }
+        // Multiplying by x must follow the reduction x^4 = x^3 + x^2 + x + 1
+        assert_eq!(f.mul(0x2, 0x8), 0xF);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_poly_reducible() {
+        F2m::from_poly(0x15);
+    }
 }
@@ -1,6 +1,6 @@
 //! The finite field of order 7
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::{CryptoRng, Rng, RngCore};
 
 use super::{Field, FiniteField};
 
@@ -146,7 +146,7 @@ impl Field for F7 {
         }
     }
 
-    fn random_element(&self, rng: &mut ThreadRng) -> Self::FieldElement {
+    fn random_element<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Self::FieldElement {
         rng.gen_range(0, ORDER as u32)
     }
 }
@@ -1,15 +1,19 @@
 //! Finite fields of characteristic 2
 
-use rand::rngs::ThreadRng;
+use rand::{CryptoRng, RngCore};
 
+#[cfg(feature = "serde")]
+pub use self::serde::{DeserializeWithField, F2mRepr, SerdeField};
 pub use f2::F2;
 pub use f2m::F2m;
 pub use f7::F7;
 
 /// Field requires implementing Eq as field isomorphism
-pub trait Field: Eq {
+///
+/// Fields and their elements are shared between threads, hence the Send and Sync bounds.
+pub trait Field: Eq + Send + Sync {
     /// Field Element
-    type FieldElement: Copy + Eq;
+    type FieldElement: Copy + Eq + Send + Sync;
 
     /// Parameters for field generation
     type FieldParameters;
@@ -45,7 +49,7 @@ pub trait Field: Eq {
     fn inv(&self, a: Self::FieldElement) -> Option<Self::FieldElement>;
 
     /// Returns a random element of the field
-    fn random_element(&self, rng: &mut ThreadRng) -> Self::FieldElement;
+    fn random_element<R: CryptoRng + RngCore>(&self, rng: &mut R) -> Self::FieldElement;
 }
 
 pub trait FiniteField: Field {
@@ -115,3 +119,5 @@ pub trait F2FiniteExtension: CharacteristicTwo + FiniteField {
 pub mod f2;
 pub mod f2m;
 pub mod f7;
+#[cfg(feature = "serde")]
+pub(crate) mod serde;
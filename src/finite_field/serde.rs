@@ -0,0 +1,168 @@
+//! Serialization of fields with serde
+//!
+//! Matrices, polynomials and Goppa codes hold a shared handle on their field.
+//! They are serialized with a description of the field, its [`Repr`],
+//! and their coefficients as u32.
+//! Deserializing an object rebuilds its field from the description,
+//! unless a field is supplied with [`DeserializeWithField`]: the object then shares it,
+//! which saves rebuilding the tables of F<sub>2<sup>m</sup></sub> for every object.
+//!
+//! [`Repr`]: trait.SerdeField.html#associatedtype.Repr
+//! [`DeserializeWithField`]: trait.DeserializeWithField.html
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use std::{fmt::Debug, sync::Arc};
+
+use super::{F2FiniteExtension, F2m, Field, FiniteField, F2, F7};
+use crate::{Error, Result};
+
+/// Field that can be described in a serialized object
+pub trait SerdeField: Field + Sized {
+    /// Serialized description of the field
+    type Repr: Serialize + DeserializeOwned + Eq + Debug;
+
+    /// Describes the field
+    fn to_repr(&self) -> Self::Repr;
+
+    /// Rebuilds a field from its description
+    fn from_repr(repr: Self::Repr) -> Result<Self>;
+
+    /// Converts an element to the u32 it is serialized as
+    fn elt_to_repr(&self, a: Self::FieldElement) -> u32;
+
+    /// Converts a serialized u32 to an element, if it is one
+    fn repr_to_elt(&self, n: u32) -> Option<Self::FieldElement>;
+}
+
+impl SerdeField for F2 {
+    type Repr = ();
+
+    fn to_repr(&self) -> Self::Repr {}
+
+    fn from_repr(_repr: Self::Repr) -> Result<Self> {
+        Ok(F2::generate(()))
+    }
+
+    fn elt_to_repr(&self, a: Self::FieldElement) -> u32 {
+        self.elt_to_u32(a)
+    }
+
+    fn repr_to_elt(&self, n: u32) -> Option<Self::FieldElement> {
+        if (n as usize) < self.order() {
+            Some(self.u32_to_elt(n))
+        } else {
+            None
+        }
+    }
+}
+
+impl SerdeField for F7 {
+    type Repr = ();
+
+    fn to_repr(&self) -> Self::Repr {}
+
+    fn from_repr(_repr: Self::Repr) -> Result<Self> {
+        Ok(F7::generate(()))
+    }
+
+    fn elt_to_repr(&self, a: Self::FieldElement) -> u32 {
+        a
+    }
+
+    fn repr_to_elt(&self, n: u32) -> Option<Self::FieldElement> {
+        if (n as usize) < self.order() {
+            Some(n)
+        } else {
+            None
+        }
+    }
+}
+
+/// Description of a field F<sub>2<sup>m</sup></sub>
+///
+/// See [`F2m::from_poly()`] for the representation of the polynomial.
+///
+/// [`F2m::from_poly()`]: struct.F2m.html#method.from_poly
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct F2mRepr {
+    pub order: usize,
+    pub poly: u32,
+}
+
+impl SerdeField for F2m {
+    type Repr = F2mRepr;
+
+    fn to_repr(&self) -> Self::Repr {
+        F2mRepr {
+            order: self.order(),
+            poly: self.poly(),
+        }
+    }
+
+    fn from_repr(repr: Self::Repr) -> Result<Self> {
+        let f = F2m::try_from_poly(repr.poly)?;
+        if f.order() != repr.order {
+            return Err(Error::InvalidField("Order and polynomial degree differ"));
+        }
+        Ok(f)
+    }
+
+    fn elt_to_repr(&self, a: Self::FieldElement) -> u32 {
+        self.elt_to_u32(a)
+    }
+
+    fn repr_to_elt(&self, n: u32) -> Option<Self::FieldElement> {
+        if (n as usize) < self.order() {
+            Some(self.u32_to_elt(n))
+        } else {
+            None
+        }
+    }
+}
+
+/// Object that can be deserialized on a given field
+pub trait DeserializeWithField<'de, F>: Sized
+where
+    F: SerdeField,
+{
+    /// Deserializes the object on the given field
+    ///
+    /// Fails if the object was serialized on another field.
+    fn deserialize_with_field<D>(
+        field: Arc<F>,
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Returns the supplied field if it matches the description, or else builds it
+pub(crate) fn field<F>(repr: F::Repr, field: Option<Arc<F>>) -> Result<Arc<F>>
+where
+    F: SerdeField,
+{
+    match field {
+        Some(f) if f.to_repr() == repr => Ok(f),
+        Some(_) => Err(Error::FieldMismatch),
+        None => Ok(Arc::new(F::from_repr(repr)?)),
+    }
+}
+
+/// Converts elements to the u32 they are serialized as
+pub(crate) fn elts_to_repr<F>(f: &F, elts: &[F::FieldElement]) -> Vec<u32>
+where
+    F: SerdeField,
+{
+    elts.iter().map(|&a| f.elt_to_repr(a)).collect()
+}
+
+/// Converts serialized u32 to elements
+pub(crate) fn repr_to_elts<F>(f: &F, repr: &[u32]) -> Result<Vec<F::FieldElement>>
+where
+    F: SerdeField,
+{
+    repr.iter()
+        .map(|&n| f.repr_to_elt(n))
+        .collect::<Option<_>>()
+        .ok_or(Error::Malformed("Coefficient out of the field"))
+}
@@ -0,0 +1,111 @@
+//! Decoding of binary Goppa codes as alternant codes
+//!
+//! Since the Goppa polynomial g is irreducible, the binary Goppa codes
+//! of polynomials g and g<sup>2</sup> are equal.
+//! The latter is the alternant code of parity-check matrix
+//! (&alpha;<sub>j</sub><sup>i</sup> / g(&alpha;<sub>j</sub>)<sup>2</sup>)
+//! for i from 0 to 2t - 1, which corrects t errors:
+//! the error locator polynomial is the shortest linear recurrence
+//! satisfied by the 2t syndromes, found by the Berlekamp-Massey algorithm.
+//!
+//! This decoder is an alternative to Patterson's ([`decode()`])
+//! which avoids the inversion and the square root modulo g.
+//!
+//! [`decode()`]: ../struct.Goppa.html#method.decode
+
+use log::debug;
+use std::sync::Arc;
+
+use super::Goppa;
+use crate::{
+    finite_field::{F2FiniteExtension, Field, F2},
+    matrix::RowVec,
+    polynomial::{Poly, RootFinding},
+};
+
+impl<F> Goppa<F>
+where
+    F: F2FiniteExtension,
+{
+    /// Computes the 2t syndromes of the received word for the alternant code of g<sup>2</sup>
+    ///
+    /// Syndrome i is the sum of &alpha;<sub>j</sub><sup>i</sup> / g(&alpha;<sub>j</sub>)<sup>2</sup>
+    /// over the nonzero positions j of rcv.
+    pub fn alternant_syndrome(&self, rcv: &RowVec<F2>) -> Vec<F::FieldElement> {
+        let f = self.field();
+        self.alternant_syndrome_by(rcv, |j| f.inv(self.poly.eval(self.set[j])).unwrap())
+    }
+
+    /// Like alternant_syndrome, except g(&alpha;<sub>j</sub>)<sup>-1</sup> is read
+    /// from the table returned by [`g_inv()`](../struct.Goppa.html#method.g_inv)
+    /// instead of being computed
+    pub fn alternant_syndrome_from_g_inv(
+        &self,
+        g_inv: &[F::FieldElement],
+        rcv: &RowVec<F2>,
+    ) -> Vec<F::FieldElement> {
+        self.alternant_syndrome_by(rcv, |j| g_inv[j])
+    }
+
+    fn alternant_syndrome_by<G>(&self, rcv: &RowVec<F2>, g_inv: G) -> Vec<F::FieldElement>
+    where
+        G: Fn(usize) -> F::FieldElement,
+    {
+        let f = self.field();
+        let f2 = rcv.field();
+        let t2 = 2 * self.poly.degree();
+        let mut syndrome = vec![f.zero(); t2];
+        for j in 0..rcv.cols() {
+            if rcv[j] == f2.one() {
+                let a = self.set[j];
+                let g_inv = g_inv(j);
+                let mut x = f.mul(g_inv, g_inv);
+                for s in syndrome.iter_mut() {
+                    *s = f.add(*s, x);
+                    x = f.mul(x, a);
+                }
+            }
+        }
+        syndrome
+    }
+
+    /// Decodes the received word with the Berlekamp-Massey algorithm
+    ///
+    /// Corrects the same errors as [`decode()`](../struct.Goppa.html#method.decode).
+    pub fn decode_alternant(&self, rcv: &RowVec<F2>) -> RowVec<F2> {
+        self.decode_alternant_with(rcv, self.default_root_finding())
+    }
+
+    /// Like decode_alternant, except roots of the error locator polynomial are found by method
+    pub fn decode_alternant_with(&self, rcv: &RowVec<F2>, method: RootFinding) -> RowVec<F2> {
+        let f = self.field();
+        let syndrome = self.alternant_syndrome(rcv);
+        if syndrome.iter().all(|&s| s == f.zero()) {
+            return rcv.clone();
+        }
+
+        let sigma = self.alternant_locator(&syndrome);
+        rcv + self.error_from_locator(&sigma, rcv.field(), method)
+    }
+
+    /// Computes the error locator polynomial from the 2t syndromes of the alternant code
+    /// with the Berlekamp-Massey algorithm
+    pub(crate) fn alternant_locator(&self, syndrome: &[F::FieldElement]) -> Poly<F> {
+        let f = self.field();
+
+        // The connection polynomial is the product of the 1 - a x over the error positions a
+        let (c, l) = Poly::berlekamp_massey(Arc::clone(&f), syndrome);
+        debug!("C(x) = {}", c);
+
+        // Its reverse x^l C(1/x) has the error positions as roots (including 0)
+        let sigma = Poly::new(
+            Arc::clone(&f),
+            (0..=l)
+                .rev()
+                .map(|i| if i <= c.degree() { c[i] } else { f.zero() })
+                .collect(),
+        );
+        debug!("sigma(x) = {}", sigma);
+        sigma
+    }
+}
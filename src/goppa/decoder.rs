@@ -0,0 +1,251 @@
+//! Decoding algorithms of binary Goppa codes
+//!
+//! A [`Decoder`] computes the syndrome of a received word and finds the error vector
+//! of weight at most t with that syndrome, or reports a decoding failure.
+//! Two decoders are available:
+//! - [`Patterson`], from the syndrome XYZ r<sup>T</sup>
+//!   (see [`Goppa::decode()`](../struct.Goppa.html#method.decode))
+//! - [`BerlekampMassey`], from the 2t syndromes of the alternant code of g<sup>2</sup>
+//!   (see [`Goppa::decode_alternant()`](../struct.Goppa.html#method.decode_alternant))
+//!
+//! Both find the roots of the error locator polynomial with the chosen [`RootFinding`],
+//! or with the [`default_root_finding()`] of the code.
+//!
+//! Syndromes are computed from values of the code which do not depend on the received word,
+//! gathered in [`Precomputed`]: a decoder working on many words computes them once.
+//!
+//! [`Decoder`]: trait.Decoder.html
+//! [`Precomputed`]: struct.Precomputed.html
+//! [`Patterson`]: struct.Patterson.html
+//! [`BerlekampMassey`]: struct.BerlekampMassey.html
+//! [`RootFinding`]: ../../polynomial/enum.RootFinding.html
+//! [`default_root_finding()`]: ../struct.Goppa.html#method.default_root_finding
+
+use log::debug;
+use std::{fmt::Debug, sync::Arc};
+
+use super::Goppa;
+use crate::{
+    finite_field::{F2FiniteExtension, Field, F2},
+    matrix::{Mat, RowVec},
+    polynomial::{Poly, RootFinding},
+    Error, Result,
+};
+
+/// Values of a Goppa code the syndromes are computed from
+pub struct Precomputed<F>
+where
+    F: F2FiniteExtension,
+{
+    xyz: Mat<F>,
+    g_inv: Vec<F::FieldElement>,
+}
+
+impl<F> Precomputed<F>
+where
+    F: F2FiniteExtension,
+{
+    pub fn new(goppa: &Goppa<F>) -> Self {
+        Self {
+            xyz: goppa.parity_check_xyz(),
+            g_inv: goppa.g_inv(),
+        }
+    }
+
+    /// Returns the parity-check matrix XYZ of the Goppa code
+    pub fn xyz(&self) -> &Mat<F> {
+        &self.xyz
+    }
+
+    /// Returns g(&alpha;)<sup>-1</sup> for every element &alpha; of the Goppa set
+    pub fn g_inv(&self) -> &Vec<F::FieldElement> {
+        &self.g_inv
+    }
+}
+
+/// Algorithm decoding a binary Goppa code on field F
+pub trait Decoder<F>: Debug + Send + Sync
+where
+    F: F2FiniteExtension,
+{
+    /// Computes the syndrome of the received word the decoder works from
+    fn syndrome(
+        &self,
+        goppa: &Goppa<F>,
+        pre: &Precomputed<F>,
+        rcv: &RowVec<F2>,
+    ) -> Vec<F::FieldElement>;
+
+    /// Finds the error vector of weight at most t with the given syndrome
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DecodingFailure`](../../enum.Error.html#variant.DecodingFailure)
+    /// if there is no such vector.
+    fn decode_syndrome(&self, goppa: &Goppa<F>, syndrome: &[F::FieldElement])
+        -> Result<RowVec<F2>>;
+
+    /// Returns the method finding the roots of the error locator polynomial, if one was chosen
+    fn root_finding(&self) -> Option<RootFinding>;
+
+    /// Sets the method finding the roots of the error locator polynomial
+    fn set_root_finding(&mut self, method: RootFinding);
+
+    /// Finds the error vector of weight at most t of the received word
+    fn error(&self, goppa: &Goppa<F>, rcv: &RowVec<F2>) -> Result<RowVec<F2>> {
+        self.error_with(goppa, &Precomputed::new(goppa), rcv)
+    }
+
+    /// Like error, except the syndrome is computed from values precomputed for the code
+    fn error_with(
+        &self,
+        goppa: &Goppa<F>,
+        pre: &Precomputed<F>,
+        rcv: &RowVec<F2>,
+    ) -> Result<RowVec<F2>> {
+        self.decode_syndrome(goppa, &self.syndrome(goppa, pre, rcv))
+    }
+
+    /// Finds the codeword at distance at most t of the received word
+    fn decode(&self, goppa: &Goppa<F>, rcv: &RowVec<F2>) -> Result<RowVec<F2>> {
+        Ok(rcv + self.error(goppa, rcv)?)
+    }
+}
+
+/// Patterson decoder
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Patterson {
+    root_finding: Option<RootFinding>,
+}
+
+impl Patterson {
+    /// Decoder finding roots with the default root finding of the code
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decoder finding roots with the given method
+    pub fn with_root_finding(method: RootFinding) -> Self {
+        Self {
+            root_finding: Some(method),
+        }
+    }
+}
+
+impl<F> Decoder<F> for Patterson
+where
+    F: F2FiniteExtension,
+{
+    fn syndrome(
+        &self,
+        _goppa: &Goppa<F>,
+        pre: &Precomputed<F>,
+        rcv: &RowVec<F2>,
+    ) -> Vec<F::FieldElement> {
+        Goppa::syndrome_from_xyz(pre.xyz(), rcv).data().to_vec()
+    }
+
+    fn decode_syndrome(
+        &self,
+        goppa: &Goppa<F>,
+        syndrome: &[F::FieldElement],
+    ) -> Result<RowVec<F2>> {
+        if is_zero(goppa, syndrome) {
+            return Ok(RowVec::zero(Arc::new(F2::generate(())), goppa.len()));
+        }
+        let sigma = goppa.patterson_locator(syndrome);
+        locate(goppa, &sigma, self.root_finding)
+    }
+
+    fn root_finding(&self) -> Option<RootFinding> {
+        self.root_finding
+    }
+
+    fn set_root_finding(&mut self, method: RootFinding) {
+        self.root_finding = Some(method);
+    }
+}
+
+/// Berlekamp-Massey decoder of the alternant code of g<sup>2</sup>
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BerlekampMassey {
+    root_finding: Option<RootFinding>,
+}
+
+impl BerlekampMassey {
+    /// Decoder finding roots with the default root finding of the code
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decoder finding roots with the given method
+    pub fn with_root_finding(method: RootFinding) -> Self {
+        Self {
+            root_finding: Some(method),
+        }
+    }
+}
+
+impl<F> Decoder<F> for BerlekampMassey
+where
+    F: F2FiniteExtension,
+{
+    fn syndrome(
+        &self,
+        goppa: &Goppa<F>,
+        pre: &Precomputed<F>,
+        rcv: &RowVec<F2>,
+    ) -> Vec<F::FieldElement> {
+        goppa.alternant_syndrome_from_g_inv(pre.g_inv(), rcv)
+    }
+
+    fn decode_syndrome(
+        &self,
+        goppa: &Goppa<F>,
+        syndrome: &[F::FieldElement],
+    ) -> Result<RowVec<F2>> {
+        if is_zero(goppa, syndrome) {
+            return Ok(RowVec::zero(Arc::new(F2::generate(())), goppa.len()));
+        }
+        let sigma = goppa.alternant_locator(syndrome);
+        locate(goppa, &sigma, self.root_finding)
+    }
+
+    fn root_finding(&self) -> Option<RootFinding> {
+        self.root_finding
+    }
+
+    fn set_root_finding(&mut self, method: RootFinding) {
+        self.root_finding = Some(method);
+    }
+}
+
+fn is_zero<F>(goppa: &Goppa<F>, syndrome: &[F::FieldElement]) -> bool
+where
+    F: F2FiniteExtension,
+{
+    let f = goppa.field();
+    syndrome.iter().all(|&s| s == f.zero())
+}
+
+/// Returns the error vector located by sigma
+///
+/// Fails if sigma is of degree greater than t or does not split into distinct factors
+/// x - a where a belongs to the Goppa set.
+fn locate<F>(goppa: &Goppa<F>, sigma: &Poly<F>, method: Option<RootFinding>) -> Result<RowVec<F2>>
+where
+    F: F2FiniteExtension,
+{
+    let method = method.unwrap_or_else(|| goppa.default_root_finding());
+    let err = goppa.error_from_locator(sigma, Arc::new(F2::generate(())), method);
+    let degree = sigma.degree();
+    if degree > goppa.poly().degree() || err.weight() != degree {
+        debug!(
+            "Decoding failure: {} roots found for degree {}",
+            err.weight(),
+            degree
+        );
+        return Err(Error::DecodingFailure);
+    }
+    Ok(err)
+}
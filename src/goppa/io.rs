@@ -1,16 +1,14 @@
 //! Converts a Goppa code on F<sub>2<sup>m</sup></sub> into a byte vector and vice versa
 
 use log::debug;
-use std::{error::Error, result};
 
 use super::Goppa;
 use crate::{
     finite_field::{F2FiniteExtension, F2m, FiniteField},
     polynomial::Poly,
+    Error, Result,
 };
 
-type Result<T> = result::Result<T, Box<dyn Error>>;
-
 impl Goppa<F2m> {
     /// Encodes the Goppa code
     ///
@@ -48,11 +46,19 @@ impl Goppa<F2m> {
         vec
     }
 
+    /// Decodes bytes encoded with [`to_bytes()`] to a Goppa code
+    ///
+    /// Also returns the number of bytes read.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
     pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
         let (mut read, poly) = Poly::from_bytes(vec)?;
         debug!("Read polynomial:\n{}", poly);
 
         let f = poly.field();
+        if vec.len() < read + crate::div_ceil(f.order(), 8) {
+            return Err(Error::Truncated("Goppa code"));
+        }
         let mut set = Vec::new();
         let mut shift = 7;
         for j in 0..f.order() as u32 {
@@ -69,6 +75,6 @@ impl Goppa<F2m> {
         if shift != 7 {
             read += 1;
         }
-        Ok((read, Goppa::new(poly, set)))
+        Ok((read, Goppa::try_new(poly, set)?))
     }
 }
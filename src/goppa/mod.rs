@@ -1,16 +1,17 @@
 //! Binary irreducible Goppa codes
 
 use log::debug;
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 use std::{
     fmt::{Debug, Display, Formatter, Result},
-    rc::Rc,
+    sync::Arc,
 };
 
 use crate::{
     finite_field::{F2FiniteExtension, Field, F2},
-    matrix::{Mat, RowVec},
-    polynomial::Poly,
+    matrix::{BitMat, Mat, RowVec},
+    polynomial::{Poly, RootFinding},
+    Error,
 };
 
 /// Binary irreducible Goppa code
@@ -32,20 +33,20 @@ where
         let q = fq.order();
         let n = self.set.len();
         let t = self.poly.degree();
-        write!(
+        writeln!(
             f,
-            "Goppa code (L, g(x)) from F{} with n={} and t={}\n",
+            "Goppa code (L, g(x)) from F{} with n={} and t={}",
             q, n, t
         )?;
-        write!(f, "g(x) = {:?}\n", self.poly)?;
+        writeln!(f, "g(x) = {:?}", self.poly)?;
         if n == q {
-            write!(f, "L = F{}\n", q)
+            writeln!(f, "L = F{}", q)
         } else {
             write!(f, "L = [")?;
             for i in 0..n - 1 {
                 write!(f, "{:X}, ", fq.elt_to_u32(self.set[i]))?;
             }
-            write!(f, "{:X}]\n", fq.elt_to_u32(self.set[n - 1]))
+            writeln!(f, "{:X}]", fq.elt_to_u32(self.set[n - 1]))
         }
     }
 }
@@ -59,24 +60,51 @@ where
         let q = fq.order();
         let n = self.set.len();
         let t = self.poly.degree();
-        write!(
+        writeln!(
             f,
-            "Goppa code (L, g(x)) from F{} with n={} and t={}\n",
+            "Goppa code (L, g(x)) from F{} with n={} and t={}",
             q, n, t
         )?;
-        write!(f, "g(x) = {}\n", self.poly)?;
+        writeln!(f, "g(x) = {}", self.poly)?;
         if n == q {
-            write!(f, "L = F{}\n", q)
+            writeln!(f, "L = F{}", q)
         } else {
             write!(f, "L = [")?;
             for i in 0..n - 1 {
                 write!(f, "{}, ", fq.elt_to_str(self.set[i]))?;
             }
-            write!(f, "{}]\n", fq.elt_to_str(self.set[n - 1]))
+            writeln!(f, "{}]", fq.elt_to_str(self.set[n - 1]))
         }
     }
 }
 
+/// Checks that a binary irreducible Goppa code of length n and correction capacity t
+/// can be built on the field of order q = 2<sup>m</sup>
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - t is 0
+/// - n is greater than q
+/// - n = q and t = 1 (a Goppa code set cannot contain one of its roots)
+/// - n &le; mt (Goppa code dimension would be 0)
+pub fn check_parameters(q: usize, n: usize, t: usize) -> std::result::Result<(), &'static str> {
+    let m = q.trailing_zeros() as usize;
+    if t == 0 {
+        return Err("t must be at least 1");
+    }
+    if n > q {
+        return Err("n must be at most q");
+    }
+    if t == 1 && n == q {
+        return Err("n must be strictly less than q when Goppa polynomial is of degree 1");
+    }
+    if n <= m * t {
+        return Err("m * t must be less than n");
+    }
+    Ok(())
+}
+
 impl<F> Goppa<F>
 where
     F: F2FiniteExtension,
@@ -96,22 +124,38 @@ where
     /// - set contains a root of the polynomial
     /// - set elements are not ordered according to their u32 representation
     pub fn new(poly: Poly<F>, set: Vec<F::FieldElement>) -> Self {
+        Self::try_new(poly, set).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like new, except invalid input is reported as an error
+    ///
+    /// The set must not be empty either.
+    pub fn try_new(poly: Poly<F>, set: Vec<F::FieldElement>) -> crate::Result<Self> {
         if !poly.is_irreducible() {
-            panic!("Goppa polynomial is not irreducible");
+            return Err(Error::InvalidParameters(
+                "Goppa polynomial is not irreducible",
+            ));
+        }
+        if set.is_empty() {
+            return Err(Error::Empty);
         }
         let f = poly.field();
         for i in 0..set.len() - 1 {
             if f.elt_to_u32(set[i]) == f.elt_to_u32(set[i + 1]) {
-                panic!("Set elements must be different");
+                return Err(Error::InvalidParameters("Set elements must be different"));
             }
             if f.elt_to_u32(set[i]) > f.elt_to_u32(set[i + 1]) {
-                panic!("Set elements must be ordered according to their u32 representation");
+                return Err(Error::InvalidParameters(
+                    "Set elements must be ordered according to their u32 representation",
+                ));
             }
         }
         if poly.degree() == 1 && set.contains(&f.mul(f.inv(poly[1]).unwrap(), poly[0])) {
-            panic!("Set contains a root of the Goppa polynomial");
+            return Err(Error::InvalidParameters(
+                "Set contains a root of the Goppa polynomial",
+            ));
         }
-        Self { poly, set }
+        Ok(Self { poly, set })
     }
 
     /// Generates from field F a random binary irreducible Goppa code
@@ -119,25 +163,28 @@ where
     ///
     /// # Panics
     ///
-    /// Panics if:
-    /// - n is greater than the order of F
-    /// - n &le; t * log<sub>2</sub>|F| (Goppa code dimension would be 0)
-    /// - n = log<sub>2</sub>|F| and t = 1 (a Goppa code set cannot contain one of its roots)
-    pub fn random(field: Rc<F>, n: usize, t: usize) -> Self {
-        let poly = Poly::random_monic_irreducible(field, t);
-        let f = poly.field();
-        let q = f.order();
-        let m = f.characteristic_exponent() as usize;
+    /// Panics if the parameters are invalid (see [`check_parameters()`]).
+    ///
+    /// [`check_parameters()`]: fn.check_parameters.html
+    pub fn random(field: Arc<F>, n: usize, t: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), field, n, t)
+    }
 
-        if n > q {
-            panic!("n must be at most q");
-        }
-        if poly.degree() == 1 && n == q {
-            panic!("n must be strictly less than q when Goppa polynomial is of degree 1");
-        }
-        if n <= m * t {
-            panic!("m * t must be at most n");
+    /// Like random, except the Goppa code is drawn from rng
+    ///
+    /// # Panics
+    ///
+    /// See [`random()`](#method.random).
+    pub fn random_with_rng<R>(rng: &mut R, field: Arc<F>, n: usize, t: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        if let Err(s) = check_parameters(field.order(), n, t) {
+            panic!("{}", s);
         }
+        let poly = Poly::random_monic_irreducible_with_rng(rng, field, t);
+        let f = poly.field();
+        let q = f.order();
 
         let mut pool = Vec::with_capacity(q);
         for i in 0..q as u32 {
@@ -147,7 +194,6 @@ where
             let root = f.mul(f.inv(poly[1]).unwrap(), poly[0]);
             pool.swap_remove(f.elt_to_u32(root) as usize);
         }
-        let mut rng = rand::thread_rng();
         let mut set = Vec::with_capacity(n);
         for _i in 0..n {
             let index = rng.gen_range(0, pool.len());
@@ -163,6 +209,10 @@ where
         self.set.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
     pub fn poly(&self) -> &Poly<F> {
         &self.poly
     }
@@ -171,8 +221,8 @@ where
         &self.set
     }
 
-    pub fn field(&self) -> Rc<F> {
-        Rc::clone(&self.poly.field())
+    pub fn field(&self) -> Arc<F> {
+        Arc::clone(&self.poly.field())
     }
 
     pub fn parity_check_x(&self) -> Mat<F> {
@@ -192,7 +242,7 @@ where
         let n = self.len();
         let t = self.poly.degree();
 
-        let mut y = Mat::zero(Rc::clone(&f), t, n);
+        let mut y = Mat::zero(Arc::clone(&f), t, n);
         for i in 0..n {
             y[(0, i)] = f.one();
         }
@@ -205,84 +255,220 @@ where
         y
     }
 
+    /// Builds the n x n diagonal matrix Z
+    ///
+    /// The matrix is dense: [`parity_check_xyz()`](#method.parity_check_xyz)
+    /// computes XYZ without it and [`g_inv()`](#method.g_inv) returns its diagonal.
+    #[deprecated(note = "Z is diagonal: use g_inv() for its diagonal")]
     pub fn parity_check_z(&self) -> Mat<F> {
         let f = self.field();
         let n = self.len();
 
-        let mut z = Mat::zero(Rc::clone(&f), n, n);
-        for i in 0..n {
-            z[(i, i)] = f.inv(self.poly.eval(self.set[i])).unwrap();
+        let mut z = Mat::zero(Arc::clone(&f), n, n);
+        for (i, x) in self.g_inv().into_iter().enumerate() {
+            z[(i, i)] = x;
         }
         z
     }
 
+    /// Returns g(&alpha;)<sup>-1</sup> for every element &alpha; of the Goppa set,
+    /// i.e. the diagonal of Z
+    pub fn g_inv(&self) -> Vec<F::FieldElement> {
+        let f = self.field();
+        self.set
+            .iter()
+            .map(|&a| f.inv(self.poly.eval(a)).unwrap())
+            .collect()
+    }
+
+    /// Computes XYZ column by column without building X, Y or Z
+    ///
+    /// This takes O(tn) operations instead of the O(t<sup>2</sup>n) of X * Y.
     pub fn parity_check_xyz(&self) -> Mat<F> {
-        let x = self.parity_check_x();
-        let y = self.parity_check_y();
-        let z = self.parity_check_z();
-        x * y * z
+        let f = self.field();
+        let n = self.len();
+        let t = self.poly.degree();
+        let mut xyz = Mat::zero(Arc::clone(&f), t, n);
+        let mut col = Vec::with_capacity(t);
+        for j in 0..n {
+            self.xyz_column(self.set[j], &mut col);
+            for i in 0..t {
+                xyz[(i, j)] = col[i];
+            }
+        }
+        xyz
     }
 
-    pub fn parity_check_from_xyz(xyz: &Mat<F>, f2: Rc<F2>) -> Mat<F2> {
-        let mut h = xyz.binary(f2);
-        h.remove_redundant_rows();
+    /// Computes the alternant parity-check matrix YZ of coefficients
+    /// &alpha;<sub>j</sub><sup>i</sup> / g(&alpha;<sub>j</sub>)
+    ///
+    /// X being invertible, YZ and XYZ are parity-check matrices of the same code.
+    pub fn parity_check_alternant(&self) -> Mat<F> {
+        let f = self.field();
+        let n = self.len();
+        let t = self.poly.degree();
+        let mut yz = Mat::zero(Arc::clone(&f), t, n);
+        let mut col = Vec::with_capacity(t);
+        for j in 0..n {
+            self.alternant_column(self.set[j], &mut col);
+            for i in 0..t {
+                yz[(i, j)] = col[i];
+            }
+        }
+        yz
+    }
+
+    /// Computes the binary image of the alternant parity-check matrix YZ
+    ///
+    /// Each coefficient is decomposed on the canonical basis as by [`BitMat::binary()`]
+    /// but YZ itself is never stored.
+    ///
+    /// [`BitMat::binary()`]: ../matrix/struct.BitMat.html#method.binary
+    pub fn parity_check_alternant_binary(&self) -> BitMat {
+        let f = self.field();
+        let m = f.characteristic_exponent() as usize;
+        let n = self.len();
+        let t = self.poly.degree();
+        let mut h = BitMat::zero(m * t, n);
+        let mut col = Vec::with_capacity(t);
+        for j in 0..n {
+            self.alternant_column(self.set[j], &mut col);
+            for (i, &elt) in col.iter().enumerate() {
+                let elt_as_u32 = f.elt_to_u32(elt);
+                for k in 0..m {
+                    if (elt_as_u32 >> k) & 1 == 1 {
+                        h.set(m * i + k, j, true);
+                    }
+                }
+            }
+        }
         h
     }
 
-    pub fn parity_check_matrix(&self, f2: Rc<F2>) -> Mat<F2> {
-        let xyz = self.parity_check_xyz();
-        Self::parity_check_from_xyz(&xyz, f2)
+    /// Evaluates the Goppa polynomial at a by Horner's rule
+    ///
+    /// The intermediate values h<sub>0</sub> = g<sub>t</sub>, ...,
+    /// h<sub>i+1</sub> = a h<sub>i</sub> + g<sub>t-1-i</sub>
+    /// are the coefficients of (g(x) - g(a)) / (x - a) from the highest degree.
+    /// They are stored in col and g(a) = h<sub>t</sub> is returned.
+    fn horner(&self, a: F::FieldElement, col: &mut Vec<F::FieldElement>) -> F::FieldElement {
+        let f = self.field();
+        let t = self.poly.degree();
+        col.clear();
+        let mut h = self.poly[t];
+        for i in 0..t {
+            col.push(h);
+            h = f.add(f.mul(h, a), self.poly[t - 1 - i]);
+        }
+        h
     }
 
-    pub fn generator_from_xyz(xyz: &Mat<F>, f2: Rc<F2>) -> (Mat<F2>, Vec<usize>) {
-        let xyz2 = xyz.binary(f2);
-        let (hs, p) = xyz2.standard_parity_check_equivalent();
-        let gs = Self::generator_from_parity_check_standard(&hs);
-        let k = hs.cols() - hs.rows();
-        let information_set = p.data()[0..k].to_vec();
-        (gs * p.inverse(), information_set)
+    /// Stores in col the column of XYZ for element a of the Goppa set
+    ///
+    /// Row i of XYZ is the sum of g<sub>t-i+j</sub> a<sup>j</sup> / g(a) for j from 0 to i,
+    /// i.e. h<sub>i</sub> / g(a) (see [`horner()`](#method.horner)).
+    fn xyz_column(&self, a: F::FieldElement, col: &mut Vec<F::FieldElement>) {
+        let f = self.field();
+        let g_inv = f.inv(self.horner(a, col)).unwrap();
+        for x in col.iter_mut() {
+            *x = f.mul(*x, g_inv);
+        }
     }
 
-    pub fn generator_from_parity_check_standard(h: &Mat<F2>) -> Mat<F2> {
-        let f2 = h.field();
-        let n = h.cols();
-        let k = n - h.rows();
-        let mut g = Mat::zero(Rc::clone(&f2), k, n);
-        for i in 0..k {
-            g[(i, i)] = f2.one();
-            for j in k..n {
-                g[(i, j)] = f2.neg(h[(j - k, i)]);
-            }
+    /// Stores in col the column of YZ for element a of the Goppa set
+    fn alternant_column(&self, a: F::FieldElement, col: &mut Vec<F::FieldElement>) {
+        let f = self.field();
+        let mut x = f.inv(self.horner(a, col)).unwrap();
+        for c in col.iter_mut() {
+            *c = x;
+            x = f.mul(x, a);
         }
-        g
     }
 
-    pub fn generator_from_parity_check(h: &Mat<F2>) -> (Mat<F2>, Vec<usize>) {
+    pub fn parity_check_from_xyz(xyz: &Mat<F>) -> BitMat {
+        let mut h = BitMat::binary(xyz);
+        h.remove_redundant_rows();
+        h
+    }
+
+    /// Computes a binary parity-check matrix of full rank
+    ///
+    /// The binary image of YZ is built column by column
+    /// (see [`parity_check_alternant_binary()`](#method.parity_check_alternant_binary)).
+    pub fn parity_check_matrix(&self) -> BitMat {
+        let mut h = self.parity_check_alternant_binary();
+        h.remove_redundant_rows();
+        h
+    }
+
+    pub fn generator_from_xyz(xyz: &Mat<F>) -> (BitMat, Vec<usize>) {
+        Self::generator_from_parity_check(&BitMat::binary(xyz))
+    }
+
+    /// Takes a parity-check matrix H = (A | I<sub>n-k</sub>) in standard form
+    /// and returns the generator matrix (I<sub>k</sub> | A<sup>T</sup>)
+    pub fn generator_from_parity_check_standard(h: &BitMat) -> BitMat {
         let n = h.cols();
         let k = n - h.rows();
+        let a = h.extract_cols(&(0..k).collect::<Vec<usize>>());
+        BitMat::hconcat(&BitMat::identity(k), &a.transpose())
+    }
+
+    /// Computes a generator matrix from a parity-check matrix, possibly with redundant rows
+    ///
+    /// Also returns the information set on which the generator matrix is the identity.
+    pub fn generator_from_parity_check(h: &BitMat) -> (BitMat, Vec<usize>) {
         let (hs, p) = h.standard_parity_check_equivalent();
+        let k = hs.cols() - hs.rows();
         let gs = Goppa::<F>::generator_from_parity_check_standard(&hs);
         let information_set = p.data()[0..k].to_vec();
         (gs * p.inverse(), information_set)
     }
 
-    pub fn generator_matrix(&self, f2: Rc<F2>) -> Mat<F2> {
-        let xyz = self.parity_check_xyz();
-        Self::generator_from_xyz(&xyz, f2).0
+    /// Computes a generator matrix from the binary image of YZ
+    ///
+    /// See [`generator_with_information_set()`](#method.generator_with_information_set).
+    pub fn generator_matrix(&self) -> BitMat {
+        self.generator_with_information_set().0
+    }
+
+    /// Computes a generator matrix and the information set on which it is the identity
+    ///
+    /// The parity-check matrix is the binary image of YZ, built column by column
+    /// without any dense t x n matrix on F<sub>2<sup>m</sup></sub>.
+    pub fn generator_with_information_set(&self) -> (BitMat, Vec<usize>) {
+        Self::generator_from_parity_check(&self.parity_check_alternant_binary())
     }
 
+    /// Computes the syndrome XYZ r<sup>T</sup> column by column without building XYZ
+    ///
+    /// Only the columns of XYZ at the nonzero positions of r are computed.
     pub fn syndrome(&self, r: &RowVec<F2>) -> Mat<F> {
-        let xyz = self.parity_check_xyz();
-        Self::syndrome_from_xyz(&xyz, r)
+        let f = self.field();
+        let f2 = r.field();
+        let t = self.poly.degree();
+        let mut s = Mat::zero(Arc::clone(&f), t, 1);
+        let mut col = Vec::with_capacity(t);
+        for j in 0..r.cols() {
+            if r[j] == f2.one() {
+                self.xyz_column(self.set[j], &mut col);
+                for i in 0..t {
+                    s[(i, 0)] = f.add(s[(i, 0)], col[i]);
+                }
+            }
+        }
+        s
     }
 
+    /// Computes the syndrome XYZ r<sup>T</sup> as the sum of the columns of XYZ
+    /// at the nonzero positions of r
     pub fn syndrome_from_xyz(xyz: &Mat<F>, rcv: &RowVec<F2>) -> Mat<F> {
         let f2 = rcv.field();
         let f = xyz.field();
-        let mut s = Mat::zero(Rc::clone(&f), xyz.rows(), 1);
-        for i in 0..xyz.rows() {
-            for j in 0..rcv.cols() {
-                if rcv[j] == f2.one() {
+        let mut s = Mat::zero(Arc::clone(&f), xyz.rows(), 1);
+        for j in 0..rcv.cols() {
+            if rcv[j] == f2.one() {
+                for i in 0..xyz.rows() {
                     s[(i, 0)] = f.add(s[(i, 0)], xyz[(i, j)]);
                 }
             }
@@ -291,40 +477,79 @@ where
     }
 
     pub fn encode(&self, msg: &RowVec<F2>) -> RowVec<F2> {
-        let f2 = msg.field();
-        let g = self.generator_matrix(f2);
+        let g = self.generator_matrix();
         Self::g_encode(&g, msg)
     }
 
-    pub fn g_encode(g: &Mat<F2>, msg: &RowVec<F2>) -> RowVec<F2> {
+    pub fn g_encode(g: &BitMat, msg: &RowVec<F2>) -> RowVec<F2> {
         msg * g
     }
 
+    /// Decodes the received word
+    ///
+    /// Roots of the error locator polynomial are found by the
+    /// [`default_root_finding()`](#method.default_root_finding).
     pub fn decode(&self, rcv: &RowVec<F2>) -> RowVec<F2> {
-        let xyz = self.parity_check_xyz();
-        self.xyz_decode(&xyz, rcv)
+        self.decode_with(rcv, self.default_root_finding())
+    }
+
+    /// Like decode, except roots of the error locator polynomial are found by method
+    pub fn decode_with(&self, rcv: &RowVec<F2>, method: RootFinding) -> RowVec<F2> {
+        self.syndrome_decode(self.syndrome(rcv).data(), rcv, method)
     }
 
     pub fn xyz_decode(&self, xyz: &Mat<F>, rcv: &RowVec<F2>) -> RowVec<F2> {
-        let f = self.field();
-        let f2 = rcv.field();
-        let syndrome = Self::syndrome_from_xyz(xyz, rcv);
-        debug!("syndrome:{}", syndrome);
+        self.xyz_decode_with(xyz, rcv, self.default_root_finding())
+    }
 
-        let s_x = Poly::new(
-            Rc::clone(&f),
-            syndrome.data().iter().rev().cloned().collect(),
-        );
-        debug!("S(x) = {}", s_x);
+    /// Like xyz_decode, except roots of the error locator polynomial are found by method
+    pub fn xyz_decode_with(
+        &self,
+        xyz: &Mat<F>,
+        rcv: &RowVec<F2>,
+        method: RootFinding,
+    ) -> RowVec<F2> {
+        self.syndrome_decode(Self::syndrome_from_xyz(xyz, rcv).data(), rcv, method)
+    }
+
+    /// Returns the additive FFT if the Goppa set is the whole field, and Horner otherwise
+    pub fn default_root_finding(&self) -> RootFinding {
+        RootFinding::for_points(self.len(), self.field().order())
+    }
 
-        if s_x.is_zero() {
+    /// Decodes the received word given its syndrome XYZ rcv<sup>T</sup>
+    fn syndrome_decode(
+        &self,
+        syndrome: &[F::FieldElement],
+        rcv: &RowVec<F2>,
+        method: RootFinding,
+    ) -> RowVec<F2> {
+        let f = self.field();
+        if syndrome.iter().all(|&s| s == f.zero()) {
             return rcv.clone();
         }
+        rcv + self.error_from_locator(&self.patterson_locator(syndrome), rcv.field(), method)
+    }
+
+    /// Computes the error locator polynomial from a nonzero syndrome XYZ e<sup>T</sup>
+    /// with Patterson's algorithm
+    pub(crate) fn patterson_locator(&self, syndrome: &[F::FieldElement]) -> Poly<F> {
+        let f = self.field();
+        debug!(
+            "syndrome: {:?}",
+            syndrome
+                .iter()
+                .map(|&s| f.elt_to_u32(s))
+                .collect::<Vec<u32>>()
+        );
+
+        let s_x = Poly::new(Arc::clone(&f), syndrome.iter().rev().cloned().collect());
+        debug!("S(x) = {}", s_x);
 
         let mut t_x = s_x.inverse_modulo(&self.poly);
         debug!("T(x) = s(x)^-1 = {}", s_x);
 
-        t_x += Poly::x_n(Rc::clone(&f), 1);
+        t_x += Poly::x_n(Arc::clone(&f), 1);
         t_x.square_root_modulo(&self.poly);
         debug!("(T(x) + x)^(1/2) = {}", t_x);
 
@@ -337,28 +562,35 @@ where
         debug!("a(x)^2 = {}", a);
         debug!("b(x)^2 = {}", b);
 
-        b *= Poly::x_n(Rc::clone(&f), 1);
+        b *= Poly::x_n(Arc::clone(&f), 1);
         let sigma = a + b;
         debug!("sigma(x) = {}", sigma);
+        sigma
+    }
 
+    /// Returns the error vector whose support is made of the roots of sigma in the Goppa set
+    pub(crate) fn error_from_locator(
+        &self,
+        sigma: &Poly<F>,
+        f2: Arc<F2>,
+        method: RootFinding,
+    ) -> RowVec<F2> {
         let err = RowVec::new(
-            Rc::clone(&f2),
-            self.set
+            Arc::clone(&f2),
+            sigma
+                .find_roots(&self.set, method)
                 .iter()
-                .map(|x| {
-                    if sigma.eval(*x) == f.zero() {
-                        f2.one()
-                    } else {
-                        f2.zero()
-                    }
-                })
+                .map(|&is_root| if is_root { f2.one() } else { f2.zero() })
                 .collect(),
         );
         debug!("Error vector:{}", err);
-
-        let cdw = rcv + err;
-        cdw
+        err
     }
 }
 
+pub mod alternant;
+pub mod decoder;
 pub mod io;
+
+#[cfg(feature = "serde")]
+mod serde;
@@ -0,0 +1,85 @@
+//! Serialization of Goppa codes with serde
+//!
+//! A Goppa code is serialized as its field, the coefficients of its polynomial
+//! by increasing degree and its set.
+//! Deserialization checks the code as [`Goppa::try_new()`] does.
+//!
+//! [`Goppa::try_new()`]: ../struct.Goppa.html#method.try_new
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+use super::Goppa;
+use crate::{
+    finite_field::{serde as field_serde, DeserializeWithField, F2FiniteExtension, SerdeField},
+    polynomial::Poly,
+};
+
+#[derive(Deserialize, Serialize)]
+struct GoppaRepr<R> {
+    field: R,
+    poly: Vec<u32>,
+    set: Vec<u32>,
+}
+
+impl<F> Goppa<F>
+where
+    F: F2FiniteExtension + SerdeField,
+{
+    fn deserialize_on<'de, D>(field: Option<Arc<F>>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = GoppaRepr::<F::Repr>::deserialize(deserializer)?;
+        let field = field_serde::field(repr.field, field).map_err(D::Error::custom)?;
+        let poly =
+            field_serde::repr_to_elts(field.as_ref(), &repr.poly).map_err(D::Error::custom)?;
+        let set = field_serde::repr_to_elts(field.as_ref(), &repr.set).map_err(D::Error::custom)?;
+        let poly = Poly::try_new(field, poly).map_err(D::Error::custom)?;
+        Goppa::try_new(poly, set).map_err(D::Error::custom)
+    }
+}
+
+impl<F> Serialize for Goppa<F>
+where
+    F: F2FiniteExtension + SerdeField,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let f = self.field();
+        GoppaRepr {
+            field: f.to_repr(),
+            poly: (0..=self.poly.degree())
+                .map(|i| f.elt_to_repr(self.poly[i]))
+                .collect(),
+            set: field_serde::elts_to_repr(f.as_ref(), &self.set),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, F> Deserialize<'de> for Goppa<F>
+where
+    F: F2FiniteExtension + SerdeField,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(None, deserializer)
+    }
+}
+
+impl<'de, F> DeserializeWithField<'de, F> for Goppa<F>
+where
+    F: F2FiniteExtension + SerdeField,
+{
+    fn deserialize_with_field<D>(field: Arc<F>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(Some(field), deserializer)
+    }
+}
@@ -1,9 +1,60 @@
 pub mod crypto;
+pub mod error;
 pub mod finite_field;
 pub mod goppa;
 pub mod matrix;
 pub mod polynomial;
 
+pub use error::{Error, Result};
+
 fn div_ceil(a: usize, b: usize) -> usize {
     a / b + if a % b == 0 { 0 } else { 1 }
 }
+
+/// Reads the big-endian u32 at position i of vec
+///
+/// Returns Error::Truncated(what) if vec is too short, what being the object decoded.
+fn read_u32(vec: &[u8], i: usize, what: &'static str) -> Result<u32> {
+    match vec.get(i..i + 4) {
+        Some(bytes) => Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        None => Err(Error::Truncated(what)),
+    }
+}
+
+/// Deserializes a byte vector serialized either as bytes or as a sequence
+#[cfg(feature = "serde")]
+fn deserialize_bytes<'de, D>(deserializer: D) -> std::result::Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct BytesVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a byte array")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> std::result::Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> std::result::Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 16));
+            while let Some(byte) = seq.next_element()? {
+                v.push(byte);
+            }
+            Ok(v)
+        }
+    }
+
+    deserializer.deserialize_byte_buf(BytesVisitor)
+}
@@ -1,9 +1,24 @@
 use getopts::{Matches, Options};
 use main_error::MainError;
-use std::{env, rc::Rc};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    sync::Arc,
+};
 
 use mceliece::{
-    crypto::{self, PublicKey, SecretKey},
+    crypto::{
+        self,
+        container::{self, ObjectType},
+        hybrid,
+        isd::WorkFactors,
+        kat,
+        params::PARAMETER_SETS,
+        passphrase::KdfParams,
+        seed::KeySeed,
+        ParameterSet, PublicKey, SecretKey,
+    },
     finite_field::{Field, F2},
     matrix::RowVec,
 };
@@ -12,12 +27,25 @@ const GOPPA_N_MIN: usize = 3;
 const GOPPA_N_MAX: usize = 2048;
 const GOPPA_N_DEFAULT: usize = 1024;
 const GOPPA_T_DEFAULT: usize = 50;
+const PARAMS_N_MAX: usize = 1 << 16;
 
 const PLAINTEXT: &str = "plaintext.mce";
 const CIPHERTEXT: &str = "ciphertext.mce";
 const DECRYPTED: &str = "decrypted.mce";
 const PUBLIC_KEY: &str = "public_key.mce";
 const SECRET_KEY: &str = "secret_key.mce";
+const KAT: &str = "kat.txt";
+const KAT_COUNT_DEFAULT: usize = 10;
+const KAT_SEED_DEFAULT: [u8; crypto::SEED_LEN] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31,
+];
+const ENCRYPTED_EXTENSION: &str = ".enc";
+/// File name standing for the standard input or output
+const STDIO: &str = "-";
+/// Environment variable holding the passphrase of the secret key
+const PASSPHRASE_VAR: &str = "MCELIECE_PASSPHRASE";
+const DECRYPTED_EXTENSION: &str = ".dec";
 
 fn get_program(path: &str) -> &str {
     let i = match path.rfind('/') {
@@ -27,12 +55,25 @@ fn get_program(path: &str) -> &str {
     &path[i..]
 }
 
-fn get_code_params(matches: &Matches) -> Result<(usize, usize), MainError> {
+fn get_code_params(matches: &Matches) -> Result<ParameterSet, MainError> {
+    if let Some(name) = matches.opt_str("params") {
+        if matches.opt_present("n") || matches.opt_present("t") {
+            return Err("Option --params cannot be combined with -n or -t".into());
+        }
+        return ParameterSet::from_name(&name).ok_or_else(|| {
+            format!(
+                "Unknown parameter set '{}'\n\
+                 Try '--help' for the list of parameter sets.",
+                name
+            )
+            .into()
+        });
+    }
     let n = match matches.opt_str("n") {
         None => GOPPA_N_DEFAULT,
-        Some(length) => u32::from_str_radix(&length, 10)? as usize,
+        Some(length) => length.parse::<u32>()? as usize,
     };
-    if n < GOPPA_N_MIN || GOPPA_N_MAX < n {
+    if !(GOPPA_N_MIN..=GOPPA_N_MAX).contains(&n) {
         return Err(format!(
             "Code length n must be at least {} and at most {}",
             GOPPA_N_MIN, GOPPA_N_MAX,
@@ -41,27 +82,172 @@ fn get_code_params(matches: &Matches) -> Result<(usize, usize), MainError> {
     }
     let t = match matches.opt_str("t") {
         None => GOPPA_T_DEFAULT,
-        Some(correction) => u32::from_str_radix(&correction, 10)? as usize,
+        Some(correction) => correction.parse::<u32>()? as usize,
+    };
+    let params = ParameterSet::new(n, t);
+    params.check()?;
+    Ok(params)
+}
+
+/// Parses a range of values written as START[:END[:STEP]]
+fn get_range(range: &str) -> Result<Vec<usize>, MainError> {
+    let bounds = range
+        .split(':')
+        .map(|s| s.parse::<u32>().map(|x| x as usize))
+        .collect::<Result<Vec<usize>, _>>()?;
+    let (start, end, step) = match bounds[..] {
+        [start] => (start, start, 1),
+        [start, end] => (start, end, 1),
+        [start, end, step] => (start, end, step),
+        _ => return Err("Expected a range START[:END[:STEP]]".into()),
+    };
+    if start > end || step == 0 {
+        return Err("Range must satisfy START <= END and STEP > 0".into());
+    }
+    Ok((start..end + 1).step_by(step).collect())
+}
+
+/// Lists the parameter sets to estimate with the params command
+///
+/// Returns the named set if --params is given, the valid combinations of the ranges
+/// of -n and -t if either is given, and every named set otherwise.
+fn get_params_sweep(matches: &Matches) -> Result<Vec<ParameterSet>, MainError> {
+    if matches.opt_present("params") {
+        return Ok(vec![get_code_params(matches)?]);
+    }
+    if !matches.opt_present("n") && !matches.opt_present("t") {
+        return Ok(PARAMETER_SETS.to_vec());
+    }
+    let lengths = match matches.opt_str("n") {
+        None => vec![GOPPA_N_DEFAULT],
+        Some(range) => get_range(&range)?,
     };
-    let q = if t == 1 && n.is_power_of_two() {
-        2 * n
+    let corrections = match matches.opt_str("t") {
+        None => vec![GOPPA_T_DEFAULT],
+        Some(range) => get_range(&range)?,
+    };
+    if lengths
+        .iter()
+        .any(|&n| !(GOPPA_N_MIN..=PARAMS_N_MAX).contains(&n))
+    {
+        return Err(format!(
+            "Code length n must be at least {} and at most {}",
+            GOPPA_N_MIN, PARAMS_N_MAX,
+        )
+        .into());
+    }
+    let sweep: Vec<ParameterSet> = lengths
+        .iter()
+        .flat_map(|&n| corrections.iter().map(move |&t| ParameterSet::new(n, t)))
+        .filter(|params| params.check().is_ok())
+        .collect();
+    if sweep.is_empty() {
+        return Err("No valid parameters in the given ranges".into());
+    }
+    Ok(sweep)
+}
+
+/// Splits the arguments of encrypt-file and decrypt-file into key, input and optional output
+fn get_file_args<'a>(
+    files: &[&'a str],
+    key_file: &'a str,
+) -> Result<(&'a str, &'a str, Option<&'a str>), MainError> {
+    match files.len() {
+        1 => Ok((key_file, files[0], None)),
+        2 => Ok((files[0], files[1], None)),
+        3 => Ok((files[0], files[1], Some(files[2]))),
+        _ => Err("Expected arguments [KEY] INPUT [OUTPUT]".into()),
+    }
+}
+
+/// Opens a file for reading, or the standard input for "-"
+fn open_input(file: &str) -> Result<Box<dyn Read>, MainError> {
+    if file == STDIO {
+        Ok(Box::new(io::stdin()))
     } else {
-        n.next_power_of_two()
+        Ok(Box::new(BufReader::new(File::open(file)?)))
+    }
+}
+
+/// Creates a file for writing, or writes to the standard output for "-"
+fn create_output(file: &str) -> Result<Box<dyn Write>, MainError> {
+    if file == STDIO {
+        Ok(Box::new(io::stdout()))
+    } else {
+        Ok(Box::new(BufWriter::new(File::create(file)?)))
+    }
+}
+
+/// Checks that the standard input is read at most once
+fn check_inputs(inputs: &[&str]) -> Result<(), MainError> {
+    if inputs.iter().filter(|&&file| file == STDIO).count() > 1 {
+        return Err(format!("Only one input can be read from '{}'", STDIO).into());
+    }
+    Ok(())
+}
+
+/// Returns the passphrase of the environment, or else prompts for it
+///
+/// A new passphrase is prompted twice.
+fn get_passphrase(new: bool) -> Result<String, MainError> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    let prompt = |prompt| {
+        rpassword::prompt_password(prompt).map_err(|e| {
+            format!(
+                "Cannot prompt for the passphrase ({}), set {}",
+                e, PASSPHRASE_VAR
+            )
+        })
     };
-    let m = q.trailing_zeros();
-    if n <= m as usize * t {
-        return Err("The ratio n/t is not large enough. Pick a larger n or a smaller t.".into());
+    let passphrase = prompt("Passphrase: ")?;
+    if new {
+        if passphrase.is_empty() {
+            return Err("Passphrase cannot be empty".into());
+        }
+        if prompt("Confirm passphrase: ")? != passphrase {
+            return Err("Passphrases do not match".into());
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Reads a secret key, asking for its passphrase if it is encrypted
+fn read_secret_key(file: &str) -> Result<SecretKey, MainError> {
+    SecretKey::read_from_with_prompt(open_input(file)?, || get_passphrase(false))
+}
+
+/// Parses a key generation seed written as 64 hexadecimal digits
+fn get_seed(seed: &str) -> Result<[u8; crypto::SEED_LEN], MainError> {
+    if seed.len() != 2 * crypto::SEED_LEN || !seed.is_ascii() {
+        return Err(format!(
+            "Seed must be made of {} hexadecimal digits",
+            2 * crypto::SEED_LEN
+        )
+        .into());
+    }
+    let mut bytes = [0; crypto::SEED_LEN];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&seed[2 * i..2 * i + 2], 16)?;
     }
-    Ok((n, t))
+    Ok(bytes)
 }
 
 fn print_help(program: &str, opts: Options) {
     let brief = format!(
-        "Usage: {0} keygen [-n N] [-t T] [PK] [SK]\n\
+        "Usage: {0} keygen [-n N] [-t T] [--params NAME] [--seed SEED [--seed-only]] [--passphrase] [PK] [SK]\n\
          {0} encrypt [PK] [PLAINTEXT] [CIPHERTEXT]\n\
          {0} decrypt [SK] [CIPHERTEXT] [DECRYPTED]\n\
          {0} plaintext [PK] [PLAINTEXT]\n\
-         Encrypts information using the McEliece cryptosystem.",
+         {0} keycheck [PK] [SK]\n\
+         {0} encrypt-file [PK] INPUT [OUTPUT]\n\
+         {0} decrypt-file [SK] INPUT [OUTPUT]\n\
+         {0} kat-gen [-n N] [-t T] [--params NAME] [--seed SEED] [--count COUNT] [KAT]\n\
+         {0} kat-verify [KAT]\n\
+         {0} params [-n N[:N[:STEP]]] [-t T[:T[:STEP]]] [--params NAME]\n\
+         Encrypts information using the McEliece cryptosystem.\n\
+         The file name '-' stands for the standard input or output.",
         program
     );
     print!("{}", opts.usage(&brief));
@@ -78,7 +264,7 @@ fn main() -> Result<(), MainError> {
         "length",
         format!(
             "Set Goppa code length (default: {}),\n\
-             keygen command only",
+             keygen, kat-gen and params commands only",
             GOPPA_N_DEFAULT
         )
         .as_str(),
@@ -89,12 +275,67 @@ fn main() -> Result<(), MainError> {
         "correction",
         format!(
             "Set Goppa code correction capacity (default: {}),\n\
-             keygen command only",
+             keygen, kat-gen and params commands only",
             GOPPA_T_DEFAULT
         )
         .as_str(),
         "T",
     );
+    opts.optopt(
+        "",
+        "params",
+        format!(
+            "Use a named parameter set instead of -n and -t:\n\
+             {},\n\
+             keygen, kat-gen and params commands only",
+            PARAMETER_SETS
+                .iter()
+                .map(|p| p.name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )
+        .as_str(),
+        "NAME",
+    );
+    opts.optopt(
+        "",
+        "seed",
+        "Derive the keys from a seed of 64 hexadecimal digits,\n\
+         keygen and kat-gen commands only",
+        "SEED",
+    );
+    opts.optflag(
+        "",
+        "seed-only",
+        "Save the secret key as its seed, keygen command with --seed only",
+    );
+    opts.optopt(
+        "",
+        "count",
+        format!(
+            "Set number of known-answer test records (default: {}),\n\
+             kat-gen command only",
+            KAT_COUNT_DEFAULT
+        )
+        .as_str(),
+        "COUNT",
+    );
+    opts.optflag(
+        "",
+        "armor",
+        "Write keys, plaintexts and ciphertexts as ASCII-armored text,\n\
+         keygen, encrypt, decrypt and plaintext commands only",
+    );
+    opts.optflag(
+        "",
+        "passphrase",
+        format!(
+            "Encrypt the secret key under a passphrase read from {}\n\
+             or prompted for, keygen command only",
+            PASSPHRASE_VAR
+        )
+        .as_str(),
+    );
     opts.optflag("v", "verbose", "Detail created files");
     let matches = opts.parse(&args[1..]).map_err(|e| e.to_string())?;
     if matches.opt_present("h") {
@@ -102,7 +343,13 @@ fn main() -> Result<(), MainError> {
         return Ok(());
     }
     let verbose = matches.opt_present("v");
-    let command = match matches.free.get(0) {
+    let armor = matches.opt_present("armor");
+    let write_vector = if armor {
+        container::write_vector_armored_to::<Box<dyn Write>>
+    } else {
+        container::write_vector_to::<Box<dyn Write>>
+    };
+    let command = match matches.free.first() {
         Some(cmd) => cmd.as_str(),
         None => {
             return Err(format!(
@@ -116,14 +363,48 @@ fn main() -> Result<(), MainError> {
     let files: Vec<&str> = matches.free.iter().skip(1).map(|s| s.as_str()).collect();
     match command {
         "keygen" => {
-            let (n, t) = get_code_params(&matches)?;
-            let pk_file = files.get(0).unwrap_or(&PUBLIC_KEY);
+            let params = get_code_params(&matches)?;
+            let pk_file = files.first().unwrap_or(&PUBLIC_KEY);
             let sk_file = files.get(1).unwrap_or(&SECRET_KEY);
-            let (pk, sk) = crypto::keygen(n, t);
-            pk.write(pk_file)?;
-            sk.write(sk_file)?;
+            let passphrase = if matches.opt_present("passphrase") {
+                Some(get_passphrase(true)?)
+            } else {
+                None
+            };
+            let seed = match matches.opt_str("seed") {
+                None => None,
+                Some(seed) => Some(KeySeed::new(params, get_seed(&seed)?)),
+            };
+            let seed_only = matches.opt_present("seed-only");
+            if seed_only && (seed.is_none() || passphrase.is_some()) {
+                return Err("--seed-only requires --seed and excludes --passphrase".into());
+            }
+            let (pk, sk) = match &seed {
+                None => params.keygen(),
+                Some(seed) => seed.keygen(),
+            };
+            let kdf = KdfParams::default();
+            match (seed, armor, passphrase) {
+                (Some(seed), false, _) if seed_only => seed.write_to(create_output(sk_file)?)?,
+                (Some(seed), true, _) if seed_only => {
+                    seed.write_armored_to(create_output(sk_file)?)?
+                }
+                (_, false, None) => sk.write_to(create_output(sk_file)?)?,
+                (_, true, None) => sk.write_armored_to(create_output(sk_file)?)?,
+                (_, false, Some(p)) => {
+                    sk.write_encrypted_to(create_output(sk_file)?, p.as_bytes(), &kdf)?
+                }
+                (_, true, Some(p)) => {
+                    sk.write_encrypted_armored_to(create_output(sk_file)?, p.as_bytes(), &kdf)?
+                }
+            }
+            if armor {
+                pk.write_armored_to(create_output(pk_file)?)?;
+            } else {
+                pk.write_to(create_output(pk_file)?)?;
+            }
             if verbose {
-                println!(
+                eprintln!(
                     "Wrote public key to '{}'.\n\
                      Wrote secret key to '{}'.",
                     pk_file, sk_file
@@ -132,48 +413,170 @@ fn main() -> Result<(), MainError> {
             Ok(())
         }
         "encrypt" => {
-            let pk_file = files.get(0).unwrap_or(&PUBLIC_KEY);
+            let pk_file = files.first().unwrap_or(&PUBLIC_KEY);
             let ptxt_file = files.get(1).unwrap_or(&PLAINTEXT);
             let ctxt_file = files.get(2).unwrap_or(&CIPHERTEXT);
-            let pk = PublicKey::read_public_key(pk_file)?;
-            let m = RowVec::read_vector(ptxt_file)?;
-            if pk.sgp().rows() != m.cols() {
-                return Err(
-                    "Plaintext length does not match code dimension from public key".into(),
-                );
-            }
+            check_inputs(&[pk_file, ptxt_file])?;
+            let pk = PublicKey::read_from(open_input(pk_file)?)?;
+            let m = container::read_vector_from(
+                open_input(ptxt_file)?,
+                ObjectType::Plaintext,
+                &pk.params(),
+            )?;
             let c = pk.encrypt(&m);
-            c.write(ctxt_file)?;
+            write_vector(
+                create_output(ctxt_file)?,
+                ObjectType::Ciphertext,
+                &pk.params(),
+                &c,
+            )?;
             if verbose {
-                println!("Wrote ciphertext to '{}'.", ctxt_file);
+                eprintln!("Wrote ciphertext to '{}'.", ctxt_file);
             }
             Ok(())
         }
         "decrypt" => {
-            let sk_file = files.get(0).unwrap_or(&SECRET_KEY);
+            let sk_file = files.first().unwrap_or(&SECRET_KEY);
             let ctxt_file = files.get(1).unwrap_or(&CIPHERTEXT);
             let dec_file = files.get(2).unwrap_or(&DECRYPTED);
-            let sk = SecretKey::read_secret_key(sk_file)?;
-            let c = RowVec::read_vector(ctxt_file)?;
-            if sk.p().len() != c.cols() {
-                return Err("Ciphertext length does not match code length from secret key".into());
-            }
+            check_inputs(&[sk_file, ctxt_file])?;
+            let sk = read_secret_key(sk_file)?;
+            let c = container::read_vector_from(
+                open_input(ctxt_file)?,
+                ObjectType::Ciphertext,
+                &sk.params(),
+            )?;
             let m = sk.decrypt(&c);
-            m.write(dec_file)?;
+            write_vector(
+                create_output(dec_file)?,
+                ObjectType::Plaintext,
+                &sk.params(),
+                &m,
+            )?;
             if verbose {
-                println!("Wrote decrypted text to '{}'.", dec_file);
+                eprintln!("Wrote decrypted text to '{}'.", dec_file);
             }
             Ok(())
         }
         "plaintext" => {
-            let pk_file = files.get(0).unwrap_or(&PUBLIC_KEY);
+            let pk_file = files.first().unwrap_or(&PUBLIC_KEY);
             let ptxt_file = files.get(1).unwrap_or(&PLAINTEXT);
-            let k = PublicKey::read_code_dimension(pk_file)?;
-            let f2 = Rc::new(F2::generate(()));
-            let m = RowVec::random(f2, k);
-            m.write(ptxt_file)?;
+            let params = PublicKey::read_params_from(open_input(pk_file)?)?;
+            let f2 = Arc::new(F2::generate(()));
+            let m = RowVec::random(f2, params.k);
+            write_vector(
+                create_output(ptxt_file)?,
+                ObjectType::Plaintext,
+                &params,
+                &m,
+            )?;
+            if verbose {
+                eprintln!("Wrote plaintext to '{}'.", ptxt_file);
+            }
+            Ok(())
+        }
+        "encrypt-file" => {
+            let (pk_file, input, output) = get_file_args(&files, PUBLIC_KEY)?;
+            check_inputs(&[pk_file, input])?;
+            let output = match output {
+                Some(output) => output.to_string(),
+                None if input == STDIO => STDIO.to_string(),
+                None => format!("{}{}", input, ENCRYPTED_EXTENSION),
+            };
+            let pk = PublicKey::read_from(open_input(pk_file)?)?;
+            hybrid::encrypt(&pk, open_input(input)?, create_output(&output)?)?;
+            if verbose {
+                eprintln!("Wrote encrypted file to '{}'.", output);
+            }
+            Ok(())
+        }
+        "decrypt-file" => {
+            let (sk_file, input, output) = get_file_args(&files, SECRET_KEY)?;
+            check_inputs(&[sk_file, input])?;
+            let output = match output {
+                Some(output) => output.to_string(),
+                None if input == STDIO => STDIO.to_string(),
+                None => match input.strip_suffix(ENCRYPTED_EXTENSION) {
+                    Some(stem) if !stem.is_empty() => stem.to_string(),
+                    _ => format!("{}{}", input, DECRYPTED_EXTENSION),
+                },
+            };
+            let sk = read_secret_key(sk_file)?;
+            hybrid::decrypt(&sk, open_input(input)?, create_output(&output)?)?;
+            if verbose {
+                eprintln!("Wrote decrypted file to '{}'.", output);
+            }
+            Ok(())
+        }
+        "keycheck" => {
+            let pk_file = files.first().unwrap_or(&PUBLIC_KEY);
+            let sk_file = files.get(1).unwrap_or(&SECRET_KEY);
+            check_inputs(&[pk_file, sk_file])?;
+            let pk = PublicKey::read_from(open_input(pk_file)?)?;
+            let sk = read_secret_key(sk_file)?;
+            crypto::check_keypair(&pk, &sk)?;
+            eprintln!("Keys '{}' and '{}' make a valid pair.", pk_file, sk_file);
+            Ok(())
+        }
+        "kat-gen" => {
+            let params = get_code_params(&matches)?;
+            let seed = match matches.opt_str("seed") {
+                None => KAT_SEED_DEFAULT,
+                Some(seed) => get_seed(&seed)?,
+            };
+            let count = match matches.opt_str("count") {
+                None => KAT_COUNT_DEFAULT,
+                Some(count) => count.parse()?,
+            };
+            let kat_file = files.first().unwrap_or(&KAT);
+            kat::write(kat_file, &params, &seed, count)?;
             if verbose {
-                println!("Wrote plaintext to '{}'.", ptxt_file);
+                eprintln!("Wrote {} known-answer tests to '{}'.", count, kat_file);
+            }
+            Ok(())
+        }
+        "kat-verify" => {
+            let kat_file = files.first().unwrap_or(&KAT);
+            let count = kat::verify_file(kat_file)?;
+            eprintln!("Verified {} known-answer tests from '{}'.", count, kat_file);
+            Ok(())
+        }
+        "params" => {
+            let sweep = get_params_sweep(&matches)?;
+            println!(
+                "{:<16} {:>6} {:>4} {:>3} {:>6} {:>10} {:>10} {:>7} {:>7} {:>7} {:>7} {:>7} {:>8}",
+                "name",
+                "n",
+                "t",
+                "m",
+                "k",
+                "pk bytes",
+                "sk bytes",
+                "Prange",
+                "LB",
+                "Stern",
+                "Dumer",
+                "BJMM",
+                "security"
+            );
+            for params in sweep {
+                let wf = WorkFactors::new(params.n, params.k(), params.t);
+                println!(
+                    "{:<16} {:>6} {:>4} {:>3} {:>6} {:>10} {:>10} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>7.1} {:>8.1}",
+                    params.name,
+                    params.n,
+                    params.t,
+                    params.m,
+                    params.k(),
+                    params.public_key_len(),
+                    params.secret_key_len(),
+                    wf.prange,
+                    wf.lee_brickell,
+                    wf.stern,
+                    wf.dumer,
+                    wf.bjmm,
+                    wf.security()
+                );
             }
             Ok(())
         }
@@ -0,0 +1,146 @@
+//! Gaussian elimination on packed matrices
+//!
+//! Every elimination goes through the reduced row echelon form computed with
+//! the method of the Four Russians (M4RI).
+
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::BitMat;
+use crate::matrix::Perm;
+
+impl BitMat {
+    /// Compute, if possible, (U, S, P) with U invertible, S standard form and P permutation
+    /// such that S = U * self * P
+    ///
+    /// The columns of the identity part of S are the pivot columns
+    /// of the reduced row echelon form of self.
+    pub fn standard_form(&self) -> Option<(Self, Self, Perm)> {
+        let m = self.rows;
+        let n = self.cols;
+        if m > n {
+            return None;
+        }
+
+        // Reducing (self | I) to (U * self | U) keeps track of the row operations
+        let mut augmented = BitMat::hconcat(self, &BitMat::identity(m));
+        let pivots = augmented.reduced_row_echelon_form();
+        if pivots[m - 1] >= n {
+            return None;
+        }
+        let u = augmented.extract_cols(&(n..n + m).collect::<Vec<usize>>());
+        let p = Self::pivots_last(n, &pivots);
+        let s = augmented.extract_cols(p.data());
+        Some((u, s, p))
+    }
+
+    /// Takes a parity-check matrix H possibly with redundant rows.
+    /// Returns (S, P) with S standard form and P permutation such that
+    /// SP<sup>-1</sup> is a (full rank) parity-check matrix of the code
+    /// (S defines an equivalent code).
+    pub fn standard_parity_check_equivalent(&self) -> (Self, Perm) {
+        let m = self.rows;
+        let n = self.cols;
+        if m > n {
+            panic!(
+                "Matrix must have at most as many rows as columns for parity-check decomposition"
+            );
+        }
+        let mut h = self.clone();
+        let pivots = h.reduced_row_echelon_form();
+        let h = h.extract_rows(&(0..pivots.len()).collect::<Vec<usize>>());
+        let p = Self::pivots_last(n, &pivots);
+        (&h * &p, p)
+    }
+
+    /// Inverse computation via Gauss-Jordan elimination of (self | I)
+    pub fn inverse(&self) -> Option<Self> {
+        if self.rows != self.cols {
+            return None;
+        }
+        let n = self.rows;
+        let mut augmented = BitMat::hconcat(self, &BitMat::identity(n));
+        let pivots = augmented.reduced_row_echelon_form();
+        if pivots[n - 1] >= n {
+            return None;
+        }
+        Some(augmented.extract_cols(&(n..2 * n).collect::<Vec<usize>>()))
+    }
+
+    /// Generates a random invertible matrix
+    ///
+    /// First generates a random matrix then reduces it to triangular form,
+    /// creating a pivot whenever a column lacks one.
+    /// Keeps track of the applied transformations via an invertible matrix u.
+    /// Returns u as our random invertible matrix.
+    pub fn invertible_random(n: usize) -> Self {
+        Self::invertible_random_with_rng(&mut rand::thread_rng(), n)
+    }
+
+    /// Like invertible_random, except the matrix is drawn from rng
+    pub fn invertible_random_with_rng<R>(rng: &mut R, n: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut mat = BitMat::random_with_rng(rng, n, n);
+        let mut u = BitMat::identity(n);
+        for j in 0..n {
+            // Find a pivot in column j or create it
+            let i = match (j..n).find(|&i| mat.get(i, j)) {
+                Some(i) => i,
+                None => {
+                    let i = rng.gen_range(j, n);
+                    mat.set(i, j, true);
+                    i
+                }
+            };
+            mat.swap_rows(i, j);
+            u.swap_rows(i, j);
+
+            // Zero coefficients under the pivot and mirror operation on matrix u
+            for i in j + 1..n {
+                if mat.get(i, j) {
+                    mat.add_rows(i, j);
+                    u.add_rows(i, j);
+                }
+            }
+        }
+        u
+    }
+
+    /// Removes rows that are linear combinations of the others
+    ///
+    /// The remaining rows span the same space but need not be rows of the original matrix.
+    pub fn remove_redundant_rows(&mut self) {
+        let rank = self.reduced_row_echelon_form().len();
+        *self = self.extract_rows(&(0..rank).collect::<Vec<usize>>());
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().reduced_row_echelon_form().len()
+    }
+
+    pub fn is_invertible(&self) -> bool {
+        self.rows == self.cols && self.rows == self.rank()
+    }
+
+    pub fn is_standard_form(&self) -> bool {
+        let m = self.rows;
+        let n = self.cols;
+        if m > n {
+            return false;
+        }
+        (0..m).all(|i| (n - m..n).all(|j| self.get(i, j) == (n + i == m + j)))
+    }
+
+    /// Returns the permutation of the n columns which puts the pivot columns last,
+    /// in order, and keeps the order of the other columns
+    fn pivots_last(n: usize, pivots: &[usize]) -> Perm {
+        let mut is_pivot = vec![false; n];
+        for &j in pivots {
+            is_pivot[j] = true;
+        }
+        let mut cols: Vec<usize> = (0..n).filter(|&j| !is_pivot[j]).collect();
+        cols.extend_from_slice(pivots);
+        Perm::new(cols)
+    }
+}
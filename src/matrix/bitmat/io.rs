@@ -0,0 +1,54 @@
+//! Converts a packed matrix into a byte vector and vice versa
+//!
+//! The layout is the one of [`Mat<F2>`](../struct.Mat.html#method.to_bytes)
+//! so that both types can read each other's encodings.
+
+use super::BitMat;
+use crate::{Error, Result};
+
+impl BitMat {
+    /// Encodes the matrix in bytes
+    ///
+    /// We start by encoding numbers of rows and columns on four bytes each.
+    /// The matrix data follows, row by row, eight coefficients per byte
+    /// starting from the most significant bit.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let len = 4 + 4 + crate::div_ceil(self.rows * self.cols, 8);
+        let mut vec = Vec::with_capacity(len);
+        vec.extend_from_slice(&(self.rows as u32).to_be_bytes());
+        vec.extend_from_slice(&(self.cols as u32).to_be_bytes());
+        vec.resize(len, 0);
+        let mut bit = 0;
+        for i in 0..self.rows {
+            for j in self.ones(i) {
+                let pos = bit + j;
+                vec[8 + pos / 8] |= 0x80 >> (pos % 8);
+            }
+            bit += self.cols;
+        }
+        vec
+    }
+
+    /// Decodes bytes encoded with [`to_bytes()`] to a matrix
+    ///
+    /// Also returns the number of bytes read.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
+        let rows = crate::read_u32(vec, 0, "matrix")? as usize;
+        let cols = crate::read_u32(vec, 4, "matrix")? as usize;
+        let read = 8 + crate::div_ceil(rows * cols, 8);
+        if rows == 0 || cols == 0 {
+            return Err(Error::Empty);
+        } else if vec.len() < read {
+            return Err(Error::Truncated("matrix"));
+        }
+        let mut mat = BitMat::zero(rows, cols);
+        for pos in 0..rows * cols {
+            if vec[8 + pos / 8] & (0x80 >> (pos % 8)) != 0 {
+                mat.set(pos / cols, pos % cols, true);
+            }
+        }
+        Ok((read, mat))
+    }
+}
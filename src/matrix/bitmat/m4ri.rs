@@ -0,0 +1,151 @@
+//! Method of the Four Russians
+//!
+//! Both algorithms precompute the 2<sup>k</sup> linear combinations of k rows
+//! so that a single table lookup and one row addition replace up to k row additions.
+//! - M4RM multiplies matrices (Arlazarov, V. L., Dinic, E. A., Kronrod, M. A., & Faradzev, I. A. (1970),
+//!   On economical construction of the transitive closure of an oriented graph).
+//! - M4RI reduces a matrix to reduced row echelon form (Bard, G. V. (2007),
+//!   Algorithms for solving linear and polynomial systems of equations over finite fields
+//!   with applications to cryptanalysis).
+
+use super::{BitMat, WORD_BITS};
+use crate::matrix::par;
+
+/// Number of rows combined in a table (a divisor of the word size)
+const K: usize = 8;
+
+/// Products whose left operand has fewer rows than this do not amortize the tables
+const M4RM_MIN_ROWS: usize = 64;
+
+/// Table of the 2<sup>k</sup> linear combinations of k rows
+///
+/// Combination i is the sum of the rows whose index is a bit set in i.
+struct Table {
+    stride: usize,
+    data: Vec<u64>,
+}
+
+impl Table {
+    fn new(stride: usize) -> Self {
+        Self {
+            stride,
+            data: vec![0; (1 << K) * stride],
+        }
+    }
+
+    /// Fills the table with the combinations of the given rows of mat
+    fn fill(&mut self, mat: &BitMat, rows: &[usize]) {
+        let s = self.stride;
+        for i in 1..1usize << rows.len() {
+            let low = i.trailing_zeros() as usize;
+            let (head, tail) = self.data.split_at_mut(i * s);
+            let prev = &head[(i & (i - 1)) * s..(i & (i - 1)) * s + s];
+            for ((x, y), z) in tail[..s].iter_mut().zip(prev).zip(mat.row(rows[low])) {
+                *x = y ^ z;
+            }
+        }
+    }
+
+    fn get(&self, i: usize) -> &[u64] {
+        &self.data[i * self.stride..(i + 1) * self.stride]
+    }
+}
+
+impl BitMat {
+    /// Computes a * b into self with the method of the Four Russians (M4RM)
+    ///
+    /// Rows of the product are updated in parallel with the `parallel` feature.
+    pub(crate) fn mul_m4rm(&mut self, a: &Self, b: &Self) {
+        if a.rows < M4RM_MIN_ROWS {
+            return self.mul_naive(a, b);
+        }
+        for x in self.data.iter_mut() {
+            *x = 0;
+        }
+        let mut table = Table::new(b.stride);
+        for block in (0..b.rows).step_by(K) {
+            let rows: Vec<usize> = (block..b.rows.min(block + K)).collect();
+            table.fill(b, &rows);
+            let mask = (1 << rows.len()) - 1;
+            let table = &table;
+            par::for_each_row(&mut self.data, self.stride, |i, row| {
+                let index = (a.row(i)[block / WORD_BITS] >> (block % WORD_BITS)) as usize & mask;
+                if index != 0 {
+                    for (x, y) in row.iter_mut().zip(table.get(index)) {
+                        *x ^= y;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Reduces the matrix to reduced row echelon form with the method of the Four Russians (M4RI)
+    ///
+    /// Rows are reduced by each block of pivots in parallel with the `parallel` feature.
+    ///
+    /// Returns the pivot columns: row i has its leading 1 in column pivots\[i\]
+    /// and every other row is zero in that column.
+    pub fn reduced_row_echelon_form(&mut self) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut table = Table::new(self.stride);
+        let mut col = 0;
+        while col < self.cols && pivots.len() < self.rows {
+            // Find up to K pivots, starting from column col
+            let r = pivots.len();
+            let mut block = Vec::new();
+            while col < self.cols && block.len() < K && r + block.len() < self.rows {
+                if let Some(row) = self.find_pivot(r, &block, col) {
+                    self.swap_rows(row, r + block.len());
+                    for q in 0..block.len() {
+                        if self.get(r + q, col) {
+                            self.add_rows(r + q, r + block.len());
+                        }
+                    }
+                    block.push(col);
+                }
+                col += 1;
+            }
+            if block.is_empty() {
+                break;
+            }
+
+            // Clear the pivot columns in all other rows
+            let rows: Vec<usize> = (r..r + block.len()).collect();
+            table.fill(self, &rows);
+            let (table, block) = (&table, &block);
+            par::for_each_row(&mut self.data, self.stride, |i, row| {
+                if rows.contains(&i) {
+                    return;
+                }
+                let index = block
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &j)| row[j / WORD_BITS] >> (j % WORD_BITS) & 1 == 1)
+                    .fold(0, |index, (q, _)| index | 1 << q);
+                if index != 0 {
+                    for (x, y) in row.iter_mut().zip(table.get(index)) {
+                        *x ^= y;
+                    }
+                }
+            });
+            pivots.extend(block);
+        }
+        pivots
+    }
+
+    /// Looks for a row, from row r + block.len() on, with a 1 in column col
+    /// once reduced by the pivot rows r, r + 1, ... of the pivot columns in block
+    fn find_pivot(&mut self, r: usize, block: &[usize], col: usize) -> Option<usize> {
+        for i in r + block.len()..self.rows {
+            for (q, &j) in block.iter().enumerate() {
+                if self.get(i, j) {
+                    self.add_rows(i, r + q);
+                }
+            }
+            if self.get(i, col) {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,352 @@
+//! Matrices on F<sub>2</sub> packed 64 coefficients per word
+//!
+//! Coefficient (i, j) is bit j % 64 of word j / 64 of row i.
+//! Rows are padded to a whole number of words and padding bits are always zero.
+
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
+
+use super::{par, BitVec, Mat};
+use crate::finite_field::{F2FiniteExtension, Field, F2};
+
+/// Number of coefficients per word
+pub(crate) const WORD_BITS: usize = 64;
+
+/// Matrix with coefficients in F<sub>2</sub>, stored as rows of bits
+#[derive(Clone, Eq, PartialEq)]
+pub struct BitMat {
+    rows: usize,
+    cols: usize,
+    stride: usize,
+    data: Vec<u64>,
+}
+
+impl BitMat {
+    /// Creates a new matrix whose coefficients are all zero
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of either rows or columns is zero.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        if rows == 0 || cols == 0 {
+            panic!("Empty matrix");
+        }
+        let stride = crate::div_ceil(cols, WORD_BITS);
+        Self {
+            rows,
+            cols,
+            stride,
+            data: vec![0; rows * stride],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut id = Self::zero(n, n);
+        for i in 0..n {
+            id.set(i, i, true);
+        }
+        id
+    }
+
+    pub fn random(rows: usize, cols: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), rows, cols)
+    }
+
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R>(rng: &mut R, rows: usize, cols: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut mat = Self::zero(rows, cols);
+        for word in mat.data.iter_mut() {
+            *word = rng.gen();
+        }
+        mat.clear_padding();
+        mat
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the words of row i
+    pub fn row(&self, i: usize) -> &[u64] {
+        &self.data[i * self.stride..(i + 1) * self.stride]
+    }
+
+    pub(crate) fn row_mut(&mut self, i: usize) -> &mut [u64] {
+        &mut self.data[i * self.stride..(i + 1) * self.stride]
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        debug_assert!(i < self.rows && j < self.cols);
+        self.data[i * self.stride + j / WORD_BITS] >> (j % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, bit: bool) {
+        debug_assert!(i < self.rows && j < self.cols);
+        let word = &mut self.data[i * self.stride + j / WORD_BITS];
+        let mask = 1 << (j % WORD_BITS);
+        if bit {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    pub fn flip(&mut self, i: usize, j: usize) {
+        debug_assert!(i < self.rows && j < self.cols);
+        self.data[i * self.stride + j / WORD_BITS] ^= 1 << (j % WORD_BITS);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().all(|&word| word == 0)
+    }
+
+    /// Returns the number of nonzero coefficients
+    pub fn weight(&self) -> usize {
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Adds row src to row dst, one word at a time
+    pub fn add_rows(&mut self, dst: usize, src: usize) {
+        if dst == src {
+            panic!("Cannot add a row to itself");
+        }
+        let s = self.stride;
+        let (dst_row, src_row) = if dst < src {
+            let (head, tail) = self.data.split_at_mut(src * s);
+            (&mut head[dst * s..(dst + 1) * s], &tail[..s])
+        } else {
+            let (head, tail) = self.data.split_at_mut(dst * s);
+            (&mut tail[..s], &head[src * s..(src + 1) * s])
+        };
+        for (x, y) in dst_row.iter_mut().zip(src_row) {
+            *x ^= y;
+        }
+    }
+
+    pub fn swap_rows(&mut self, row1: usize, row2: usize) {
+        if row1 == row2 {
+            return;
+        }
+        let s = self.stride;
+        let (low, high) = (row1.min(row2), row1.max(row2));
+        let (head, tail) = self.data.split_at_mut(high * s);
+        head[low * s..(low + 1) * s].swap_with_slice(&mut tail[..s]);
+    }
+
+    pub fn swap_cols(&mut self, col1: usize, col2: usize) {
+        if col1 == col2 {
+            return;
+        }
+        for i in 0..self.rows {
+            let (a, b) = (self.get(i, col1), self.get(i, col2));
+            if a != b {
+                self.flip(i, col1);
+                self.flip(i, col2);
+            }
+        }
+    }
+
+    /// Creates a new matrix by taking the chosen rows in the given order
+    pub fn extract_rows(&self, rows: &[usize]) -> Self {
+        let mut res = Self::zero(rows.len(), self.cols);
+        for (i, &row) in rows.iter().enumerate() {
+            res.row_mut(i).copy_from_slice(self.row(row));
+        }
+        res
+    }
+
+    /// Creates a new matrix by taking the chosen columns in the given order
+    pub fn extract_cols(&self, cols: &[usize]) -> Self {
+        let mut res = Self::zero(self.rows, cols.len());
+        for i in 0..self.rows {
+            for (j, &col) in cols.iter().enumerate() {
+                if self.get(i, col) {
+                    res.set(i, j, true);
+                }
+            }
+        }
+        res
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::zero(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in self.ones(i) {
+                t.set(j, i, true);
+            }
+        }
+        t
+    }
+
+    pub fn hconcat(a: &Self, b: &Self) -> Self {
+        if a.rows != b.rows {
+            panic!("number of rows do no match");
+        }
+        let mut ab = Self::zero(a.rows, a.cols + b.cols);
+        for i in 0..a.rows {
+            ab.row_mut(i)[..a.stride].copy_from_slice(a.row(i));
+            for j in b.ones(i) {
+                ab.set(i, a.cols + j, true);
+            }
+        }
+        ab
+    }
+
+    pub fn vconcat(a: &Self, b: &Self) -> Self {
+        if a.cols != b.cols {
+            panic!("number of columns do not match");
+        }
+        let mut data = a.data.clone();
+        data.extend_from_slice(&b.data);
+        Self {
+            rows: a.rows + b.rows,
+            cols: a.cols,
+            stride: a.stride,
+            data,
+        }
+    }
+
+    /// Returns the indices of the nonzero coefficients of row i
+    pub fn ones(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(i).iter().enumerate().flat_map(|(w, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(w * WORD_BITS + bit)
+            })
+        })
+    }
+
+    /// Computes (self * v<sup>T</sup>)<sup>T</sup> for a row vector v, e.g. a syndrome
+    ///
+    /// # Panics
+    ///
+    /// Panics if v does not have as many coefficients as self has columns.
+    pub fn mul_transpose(&self, v: &BitVec) -> BitVec {
+        if v.cols() != self.cols {
+            panic!("Cannot multiply matrix by vector: dimensions don't match");
+        }
+        let mut res = BitVec::zero(self.rows);
+        for i in 0..self.rows {
+            let parity = self
+                .row(i)
+                .iter()
+                .zip(v.0.row(0))
+                .fold(0, |acc, (x, y)| acc ^ (x & y).count_ones());
+            if parity & 1 == 1 {
+                res.set(i, true);
+            }
+        }
+        res
+    }
+
+    /// Computes a * b into self
+    ///
+    /// Uses the method of the Four Russians (M4RM) unless a has few rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions don't match.
+    pub fn mul(&mut self, a: &Self, b: &Self) {
+        if self.rows != a.rows || a.cols != b.rows || b.cols != self.cols {
+            panic!("Cannot multiply matrices: dimensions don't match");
+        }
+        self.mul_m4rm(a, b);
+    }
+
+    /// Computes a * b into self, adding a row of b for each nonzero coefficient of a
+    fn mul_naive(&mut self, a: &Self, b: &Self) {
+        par::for_each_row(&mut self.data, self.stride, |i, row| {
+            for x in row.iter_mut() {
+                *x = 0;
+            }
+            for k in a.ones(i) {
+                for (x, y) in row.iter_mut().zip(b.row(k)) {
+                    *x ^= y;
+                }
+            }
+        });
+    }
+
+    /// Takes a t * n matrix on F<sub>2<sup>m</sup></sub>
+    /// and outputs a mt * n matrix on F<sub>2</sub>
+    /// by decomposing each coefficient on the canonical basis
+    ///
+    /// Like [`Mat::binary()`](../struct.Mat.html#method.binary), except the result is packed.
+    pub fn binary<F>(mat: &Mat<F>) -> Self
+    where
+        F: F2FiniteExtension,
+    {
+        let f = mat.field();
+        let m = f.characteristic_exponent() as usize;
+        let mut bin = Self::zero(m * mat.rows(), mat.cols());
+        for i in 0..mat.rows() {
+            for j in 0..mat.cols() {
+                let elt_as_u32 = f.elt_to_u32(mat[(i, j)]);
+                for k in 0..m {
+                    if (elt_as_u32 >> k) & 1 == 1 {
+                        bin.set(m * i + k, j, true);
+                    }
+                }
+            }
+        }
+        bin
+    }
+
+    /// Zeroes the bits beyond the last column
+    fn clear_padding(&mut self) {
+        let used = self.cols % WORD_BITS;
+        if used != 0 {
+            let mask = (1 << used) - 1;
+            for i in 0..self.rows {
+                self.data[(i + 1) * self.stride - 1] &= mask;
+            }
+        }
+    }
+}
+
+impl From<&Mat<F2>> for BitMat {
+    fn from(mat: &Mat<F2>) -> Self {
+        let mut bits = Self::zero(mat.rows(), mat.cols());
+        for i in 0..mat.rows() {
+            for j in 0..mat.cols() {
+                if mat[(i, j)] == 1 {
+                    bits.set(i, j, true);
+                }
+            }
+        }
+        bits
+    }
+}
+
+impl From<&BitMat> for Mat<F2> {
+    fn from(bits: &BitMat) -> Self {
+        let f2 = Arc::new(F2::generate(()));
+        let mut mat = Mat::zero(f2, bits.rows, bits.cols);
+        for i in 0..bits.rows {
+            for j in bits.ones(i) {
+                mat[(i, j)] = 1;
+            }
+        }
+        mat
+    }
+}
+
+mod gauss;
+mod io;
+mod m4ri;
+mod traits;
@@ -0,0 +1,151 @@
+use std::{
+    fmt::{Debug, Display, Formatter, Result},
+    ops::{Add, AddAssign, Mul},
+};
+
+use super::BitMat;
+use crate::{
+    finite_field::F2,
+    matrix::{BitVec, Perm, RowVec},
+};
+
+impl Add for BitMat {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self += &other;
+        self
+    }
+}
+
+impl Add<&BitMat> for BitMat {
+    type Output = Self;
+
+    fn add(mut self, other: &Self) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl Add for &BitMat {
+    type Output = BitMat;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.clone() + other
+    }
+}
+
+impl AddAssign<&BitMat> for BitMat {
+    fn add_assign(&mut self, other: &Self) {
+        if self.rows != other.rows || self.cols != other.cols {
+            panic!("Cannot add matrices: dimensions don't match");
+        }
+        for (x, y) in self.data.iter_mut().zip(&other.data) {
+            *x ^= y;
+        }
+    }
+}
+
+impl Mul for BitMat {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        &self * &other
+    }
+}
+
+impl Mul<&BitMat> for BitMat {
+    type Output = Self;
+
+    fn mul(self, other: &Self) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<BitMat> for &BitMat {
+    type Output = BitMat;
+
+    fn mul(self, other: BitMat) -> Self::Output {
+        self * &other
+    }
+}
+
+impl Mul for &BitMat {
+    type Output = BitMat;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut prod = BitMat::zero(self.rows, other.cols);
+        BitMat::mul(&mut prod, self, other);
+        prod
+    }
+}
+
+impl Mul<Perm> for BitMat {
+    type Output = Self;
+
+    fn mul(self, other: Perm) -> Self::Output {
+        &self * &other
+    }
+}
+
+impl Mul<&Perm> for BitMat {
+    type Output = Self;
+
+    fn mul(self, other: &Perm) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<Perm> for &BitMat {
+    type Output = BitMat;
+
+    fn mul(self, other: Perm) -> Self::Output {
+        self * &other
+    }
+}
+
+impl Mul<&Perm> for &BitMat {
+    type Output = BitMat;
+
+    fn mul(self, perm: &Perm) -> Self::Output {
+        if self.cols != perm.len() {
+            panic!("Cannot multiply matrices: dimensions don't match");
+        }
+        self.extract_cols(perm.data())
+    }
+}
+
+impl Mul<&BitMat> for RowVec<F2> {
+    type Output = Self;
+
+    fn mul(self, other: &BitMat) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<&BitMat> for &RowVec<F2> {
+    type Output = RowVec<F2>;
+
+    fn mul(self, other: &BitMat) -> Self::Output {
+        RowVec::from(&(&BitVec::from(self) * other))
+    }
+}
+
+impl Debug for BitMat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BitMat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f)?;
+        for i in 0..self.rows {
+            let row: Vec<&str> = (0..self.cols)
+                .map(|j| if self.get(i, j) { "1" } else { "0" })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
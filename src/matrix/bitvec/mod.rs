@@ -0,0 +1,135 @@
+//! Row vectors on F<sub>2</sub> packed 64 coefficients per word
+
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
+
+use super::{BitMat, RowVec};
+use crate::{
+    finite_field::{Field, F2},
+    Error, Result,
+};
+
+/// Row vector with coefficients in F<sub>2</sub>, stored as a packed matrix of one row
+#[derive(Clone, Eq, PartialEq)]
+pub struct BitVec(pub BitMat);
+
+impl BitVec {
+    pub fn zero(cols: usize) -> Self {
+        BitVec(BitMat::zero(1, cols))
+    }
+
+    pub fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    pub fn get(&self, j: usize) -> bool {
+        self.0.get(0, j)
+    }
+
+    pub fn set(&mut self, j: usize, bit: bool) {
+        self.0.set(0, j, bit)
+    }
+
+    pub fn flip(&mut self, j: usize) {
+        self.0.flip(0, j)
+    }
+
+    pub fn weight(&self) -> usize {
+        self.0.weight()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Returns the indices of the nonzero coefficients
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.ones(0)
+    }
+
+    pub fn random(n: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), n)
+    }
+
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R: CryptoRng + RngCore>(rng: &mut R, n: usize) -> Self {
+        BitVec(BitMat::random_with_rng(rng, 1, n))
+    }
+
+    pub fn random_with_weight(n: usize, w: usize) -> Self {
+        Self::random_with_weight_with_rng(&mut rand::thread_rng(), n, w)
+    }
+
+    /// Like random_with_weight, except the vector is drawn from rng
+    pub fn random_with_weight_with_rng<R>(rng: &mut R, n: usize, w: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut vec = BitVec::zero(n);
+        let mut cols: Vec<usize> = (0..n).collect();
+        for _i in 0..w {
+            let index = rng.gen_range(0, cols.len());
+            vec.set(cols.swap_remove(index), true);
+        }
+        vec
+    }
+
+    pub fn hconcat(a: &Self, b: &Self) -> Self {
+        BitVec(BitMat::hconcat(&a.0, &b.0))
+    }
+
+    pub fn extract_cols(&self, cols: &[usize]) -> Self {
+        BitVec(self.0.extract_cols(cols))
+    }
+
+    /// Encodes the vector in bytes
+    ///
+    /// The layout is the one of [`RowVec<F2>`](../struct.RowVec.html#method.to_bytes):
+    /// the length of the vector on four bytes, then the coefficients (eight per byte).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut vec = self.0.to_bytes();
+        vec.drain(0..4);
+        vec
+    }
+
+    /// Decodes bytes encoded with [`to_bytes()`] to a vector
+    ///
+    /// Also returns the number of bytes read.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
+        let cols = crate::read_u32(vec, 0, "vector")? as usize;
+        let read = 4 + crate::div_ceil(cols, 8);
+        if cols == 0 {
+            return Err(Error::Empty);
+        } else if vec.len() < read {
+            return Err(Error::Truncated("vector"));
+        }
+        let mut bitvec = BitVec::zero(cols);
+        for j in 0..cols {
+            if vec[4 + j / 8] & (0x80 >> (j % 8)) != 0 {
+                bitvec.set(j, true);
+            }
+        }
+        Ok((read, bitvec))
+    }
+}
+
+impl From<&RowVec<F2>> for BitVec {
+    fn from(vec: &RowVec<F2>) -> Self {
+        BitVec(BitMat::from(&vec.0))
+    }
+}
+
+impl From<&BitVec> for RowVec<F2> {
+    fn from(vec: &BitVec) -> Self {
+        let f2 = Arc::new(F2::generate(()));
+        let mut rowvec = RowVec::zero(f2, vec.cols());
+        for j in vec.ones() {
+            rowvec[j] = 1;
+        }
+        rowvec
+    }
+}
+
+mod traits;
@@ -0,0 +1,81 @@
+use std::{
+    fmt::{Debug, Display, Formatter, Result},
+    ops::{Add, AddAssign, Mul},
+};
+
+use super::BitVec;
+use crate::matrix::{BitMat, Perm};
+
+impl Add for BitVec {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        BitVec(self.0 + &other.0)
+    }
+}
+
+impl Add<&BitVec> for BitVec {
+    type Output = Self;
+
+    fn add(self, other: &Self) -> Self::Output {
+        BitVec(self.0 + &other.0)
+    }
+}
+
+impl Add for &BitVec {
+    type Output = BitVec;
+
+    fn add(self, other: Self) -> Self::Output {
+        BitVec(&self.0 + &other.0)
+    }
+}
+
+impl AddAssign<&BitVec> for BitVec {
+    fn add_assign(&mut self, other: &Self) {
+        self.0 += &other.0;
+    }
+}
+
+impl Mul<&BitMat> for BitVec {
+    type Output = Self;
+
+    fn mul(self, other: &BitMat) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<&BitMat> for &BitVec {
+    type Output = BitVec;
+
+    fn mul(self, other: &BitMat) -> Self::Output {
+        BitVec(&self.0 * other)
+    }
+}
+
+impl Mul<&Perm> for BitVec {
+    type Output = Self;
+
+    fn mul(self, other: &Perm) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<&Perm> for &BitVec {
+    type Output = BitVec;
+
+    fn mul(self, other: &Perm) -> Self::Output {
+        BitVec(&self.0 * other)
+    }
+}
+
+impl Debug for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Display for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,8 +1,8 @@
-use rand::Rng;
-use std::rc::Rc;
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
 
 use super::{Mat, RowVec, SubMat};
-use crate::finite_field::{Field};
+use crate::finite_field::Field;
 
 #[derive(Eq, PartialEq)]
 pub struct ColVec<F>(pub Mat<F>)
@@ -13,15 +13,15 @@ impl<F> ColVec<F>
 where
     F: Field,
 {
-    pub fn new(field: Rc<F>, data: Vec<F::FieldElement>) -> Self {
+    pub fn new(field: Arc<F>, data: Vec<F::FieldElement>) -> Self {
         ColVec(Mat::new(field, data.len(), 1, data))
     }
 
-    pub fn zero(field: Rc<F>, rows: usize) -> Self {
+    pub fn zero(field: Arc<F>, rows: usize) -> Self {
         ColVec(Mat::zero(field, rows, 1))
     }
 
-    pub fn field(&self) -> Rc<F> {
+    pub fn field(&self) -> Arc<F> {
         self.0.field()
     }
 
@@ -47,12 +47,24 @@ where
         weight
     }
 
-    pub fn random(field: Rc<F>, n: usize) -> Self {
-        ColVec(Mat::random(field, n, 1))
+    pub fn random(field: Arc<F>, n: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), field, n)
     }
 
-    pub fn random_with_weight(field: Rc<F>, n: usize, w: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R: CryptoRng + RngCore>(rng: &mut R, field: Arc<F>, n: usize) -> Self {
+        ColVec(Mat::random_with_rng(rng, field, n, 1))
+    }
+
+    pub fn random_with_weight(field: Arc<F>, n: usize, w: usize) -> Self {
+        Self::random_with_weight_with_rng(&mut rand::thread_rng(), field, n, w)
+    }
+
+    /// Like random_with_weight, except the vector is drawn from rng
+    pub fn random_with_weight_with_rng<R>(rng: &mut R, field: Arc<F>, n: usize, w: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
         let mut vec = ColVec::zero(field, n);
         let mut rows = Vec::with_capacity(n);
         for i in 0..n {
@@ -62,7 +74,7 @@ where
         for _i in 0..w {
             let mut elt;
             loop {
-                elt = vec.field().random_element(&mut rng);
+                elt = vec.field().random_element(rng);
                 if elt != vec.field().zero() {
                     break;
                 }
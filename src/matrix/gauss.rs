@@ -1,7 +1,7 @@
-use rand::Rng;
-use std::rc::Rc;
+use rand::{CryptoRng, Rng, RngCore};
+use std::{ops::Range, sync::Arc};
 
-use super::{Mat, Perm};
+use super::{par, Mat, Perm};
 use crate::finite_field::Field;
 
 impl<F> Mat<F>
@@ -9,7 +9,7 @@ where
     F: Field,
 {
     pub fn row_echelon_form(&mut self) -> Vec<usize> {
-        let f = Rc::clone(&self.field);
+        let f = Arc::clone(&self.field);
         let n = self.rows;
         let m = self.cols;
         let mut row_pivot = 0;
@@ -39,18 +39,8 @@ where
             self[(row_pivot, col_pivot)] = f.one();
 
             // Adjust all rows below pivot's row
-            for k in row_pivot + 1..n {
-                if self[(k, col_pivot)] == f.zero() {
-                    continue;
-                }
-                for l in col_pivot + 1..m {
-                    self[(k, l)] = f.sub(
-                        self[(k, l)],
-                        f.mul(self[(k, col_pivot)], self[(row_pivot, l)]),
-                    );
-                }
-                self[(k, col_pivot)] = f.zero();
-            }
+            let lambdas = self.column_in_rows(col_pivot, row_pivot + 1..n);
+            self.eliminate(row_pivot, &lambdas);
 
             row_pivot += 1;
             col_pivot += 1;
@@ -59,7 +49,7 @@ where
     }
 
     pub fn reduced_row_echelon_form(&mut self) -> Vec<usize> {
-        let f = Rc::clone(&self.field);
+        let f = Arc::clone(&self.field);
         let n = self.rows;
         let m = self.cols;
         let max_set_of_independant_rows = self.row_echelon_form(); // note that all pivots are 1
@@ -75,18 +65,8 @@ where
             }
 
             // Eliminate all non zero elements in the pivot's column
-            for i in (0..row_pivot).rev() {
-                if self[(i, col_pivot)] == f.zero() {
-                    continue;
-                }
-
-                for k in col_pivot..m {
-                    self[(i, k)] = f.sub(
-                        self[(i, k)],
-                        f.mul(self[(i, col_pivot)], self[(row_pivot, col_pivot)]),
-                    );
-                }
-            }
+            let lambdas = self.column_in_rows(col_pivot, 0..row_pivot);
+            self.eliminate(row_pivot, &lambdas);
         }
         max_set_of_independant_rows
     }
@@ -100,7 +80,7 @@ where
         if m > n {
             return None;
         }
-        let mut u = Mat::identity(Rc::clone(&f), m);
+        let mut u = Mat::identity(Arc::clone(&f), m);
         let mut h = self.clone();
         let mut p = Perm::identity(n);
         let mut col = n; // index of the column to check for a pivot
@@ -153,13 +133,9 @@ where
             }
 
             // Nullify the rest of the column and update matrix U accordingly
-            for i in 0..m {
-                if h[(i, j)] != f.zero() && i != j + m - n {
-                    let lambda = f.neg(h[(i, j)]);
-                    h.combine_rows(i, lambda, j + m - n);
-                    u.combine_rows(i, lambda, j + m - n);
-                }
-            }
+            let lambdas = h.column_in_rows(j, 0..m);
+            h.eliminate(j + m - n, &lambdas);
+            u.eliminate(j + m - n, &lambdas);
         }
         Some((u, h, p))
     }
@@ -167,14 +143,21 @@ where
     //TODO: can the random drawing of the information set be optimized ? Right now the same column can be picked and discarded multiple times.
     /// Like standard_form, except the information set is picked at random
     pub fn random_standard_form(&self) -> Option<(Self, Self, Perm)> {
+        self.random_standard_form_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Like random_standard_form, except the information set is drawn from rng
+    pub fn random_standard_form_with_rng<R>(&self, rng: &mut R) -> Option<(Self, Self, Perm)>
+    where
+        R: CryptoRng + RngCore,
+    {
         let f = self.field();
         let m = self.rows;
         let n = self.cols;
         if m > n {
             return None;
         }
-        let mut rng = rand::thread_rng();
-        let mut u = Mat::identity(Rc::clone(&f), m);
+        let mut u = Mat::identity(Arc::clone(&f), m);
         let mut h = self.clone();
         let mut p = Perm::identity(n);
         let mut pivot_candidates = vec![0; n];
@@ -189,10 +172,8 @@ where
             let mut pivot = false;
             let mut row_pivot = 0;
             let mut col_pivot = 0;
-            pivot_candidates.resize(j + 1, 0);
-            for i in 0..j + 1 {
-                pivot_candidates[i] = i;
-            }
+            pivot_candidates.clear();
+            pivot_candidates.extend(0..j + 1);
 
             while !pivot && !pivot_candidates.is_empty() {
                 let index = rng.gen_range(0, pivot_candidates.len()); // index of the column to check for a pivot
@@ -233,25 +214,33 @@ where
             }
 
             // Nullify the rest of the column and update matrix U accordingly
-            for i in 0..m {
-                if h[(i, j)] != f.zero() && i != j + m - n {
-                    let lambda = f.neg(h[(i, j)]);
-                    h.combine_rows(i, lambda, j + m - n);
-                    u.combine_rows(i, lambda, j + m - n);
-                }
-            }
+            let lambdas = h.column_in_rows(j, 0..m);
+            h.eliminate(j + m - n, &lambdas);
+            u.eliminate(j + m - n, &lambdas);
         }
         Some((u, h, p))
     }
 
     pub fn parity_check_random_standard_form(&self, u: &mut Self, h: &mut Self, p: &mut Perm) {
+        self.parity_check_random_standard_form_with_rng(&mut rand::thread_rng(), u, h, p);
+    }
+
+    /// Like parity_check_random_standard_form, except the information set is drawn from rng
+    pub fn parity_check_random_standard_form_with_rng<R>(
+        &self,
+        rng: &mut R,
+        u: &mut Self,
+        h: &mut Self,
+        p: &mut Perm,
+    ) where
+        R: CryptoRng + RngCore,
+    {
         let f = self.field();
         let m = self.rows;
         let n = self.cols;
         if m > n {
             panic!("Parity-check matrix must have at least as many columns as rows")
         }
-        let mut rng = rand::thread_rng();
         u.copy_identity();
         h.copy(self);
         p.copy_identity();
@@ -301,13 +290,9 @@ where
             }
 
             // Nullify the rest of the column and update matrix U accordingly
-            for i in 0..m {
-                if h[(i, j)] != f.zero() && i != j + m - n {
-                    let lambda = f.neg(h[(i, j)]);
-                    h.combine_rows(i, lambda, j + m - n);
-                    u.combine_rows(i, lambda, j + m - n);
-                }
-            }
+            let lambdas = h.column_in_rows(j, 0..m);
+            h.eliminate(j + m - n, &lambdas);
+            u.eliminate(j + m - n, &lambdas);
         }
     }
 
@@ -353,7 +338,7 @@ where
             }
 
             if !pivot {
-                h.remove_rows(&(0..j - (n - m) + 1).collect());
+                h.remove_rows(&(0..j - (n - m) + 1).collect::<Vec<usize>>());
                 return (h, p);
             }
 
@@ -373,12 +358,8 @@ where
             }
 
             // Nullify the rest of the column and update matrix U accordingly
-            for i in 0..m {
-                if h[(i, j)] != f.zero() && i != j + m - n {
-                    let lambda = f.neg(h[(i, j)]);
-                    h.combine_rows(i, lambda, j + m - n);
-                }
-            }
+            let lambdas = h.column_in_rows(j, 0..m);
+            h.eliminate(j + m - n, &lambdas);
         }
         (h, p)
     }
@@ -394,7 +375,7 @@ where
         let f = self.field();
         let n = self.rows;
         let mut mat = self.clone();
-        let mut inv: Self = Mat::identity(Rc::clone(&f), n);
+        let mut inv: Self = Mat::identity(Arc::clone(&f), n);
         let mut p = 0; // pivot's row and pivot's column
 
         while p < n {
@@ -426,23 +407,10 @@ where
 
             // Adjust all rows below pivot's row: L(k) = L(k) - c(k,p) * L(p)
             // where L(k) is the kth row and c(k,p) is the coefficient [k,p] of our matrix
-            for k in p + 1..n {
-                if mat[(k, p)] == f.zero() {
-                    continue;
-                }
-
-                let lambda = mat[(k, p)];
-                mat[(k, p)] = f.zero();
-                for l in p + 1..n {
-                    // first p+1 columns are zero
-                    mat[(k, l)] = f.sub(mat[(k, l)], f.mul(lambda, mat[(p, l)]));
-                }
-
-                // Mimic operation on matrix 'inv'
-                for l in 0..n {
-                    inv[(k, l)] = f.sub(inv[(k, l)], f.mul(lambda, inv[(p, l)]));
-                }
-            }
+            // and mimic operation on matrix 'inv'
+            let lambdas = mat.column_in_rows(p, p + 1..n);
+            mat.eliminate(p, &lambdas);
+            inv.eliminate(p, &lambdas);
 
             p += 1;
         }
@@ -450,19 +418,12 @@ where
         // Matrix 'mat' is now in triangular form
 
         for j in (0..n).rev() {
-            for i in (0..j).rev() {
-                // Perform the row operation to set c(i, j) to 0:
-                // L(i) = L(i) - c(i, j) * L(j)
-                // We don't actually need to operate on the original matrix here.
-                // Mimic the row operation on matrix 'inv'.
-                if mat[(i, j)] == f.zero() {
-                    continue;
-                }
-
-                for l in 0..n {
-                    inv[(i, l)] = f.sub(inv[(i, l)], f.mul(mat[(i, j)], inv[(j, l)]));
-                }
-            }
+            // Perform the row operations to set c(i, j) to 0 for i < j:
+            // L(i) = L(i) - c(i, j) * L(j)
+            // We don't actually need to operate on the original matrix here.
+            // Mimic the row operations on matrix 'inv'.
+            let lambdas = mat.column_in_rows(j, 0..j);
+            inv.eliminate(j, &lambdas);
         }
         Some(inv)
     }
@@ -472,10 +433,17 @@ where
     /// First generates a random matrix then applies to it the standard form algorithm.
     /// Keeps track of the applied transformations via an invertible matrix u.
     /// Returns u as our random invertible matrix.
-    pub fn invertible_random(f: Rc<F>, n: usize) -> Self {
-        let mut rng = rand::thread_rng();
-        let mut mat = Mat::random(Rc::clone(&f), n, n);
-        let mut u = Mat::identity(Rc::clone(&f), n);
+    pub fn invertible_random(f: Arc<F>, n: usize) -> Self {
+        Self::invertible_random_with_rng(&mut rand::thread_rng(), f, n)
+    }
+
+    /// Like invertible_random, except the matrix is drawn from rng
+    pub fn invertible_random_with_rng<R>(rng: &mut R, f: Arc<F>, n: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut mat = Mat::random_with_rng(rng, Arc::clone(&f), n, n);
+        let mut u = Mat::identity(Arc::clone(&f), n);
 
         // Loop on columns
         for j in 0..n {
@@ -489,7 +457,7 @@ where
             if i == n {
                 i = rng.gen_range(j, n);
                 loop {
-                    mat[(i, j)] = f.random_element(&mut rng);
+                    mat[(i, j)] = f.random_element(rng);
                     if mat[(i, j)] != f.zero() {
                         break;
                     }
@@ -507,17 +475,9 @@ where
             u.mul_row(j, inv_pivot);
 
             // Zero coefficients under the pivot and mirror operation on matrix u
-            for i in j + 1..n {
-                if mat[(i, j)] == f.zero() {
-                    continue;
-                }
-                let lambda = mat[(i, j)];
-                mat[(i, j)] = f.zero();
-                for k in j + 1..n {
-                    mat[(i, k)] = f.sub(mat[(i, k)], f.mul(lambda, mat[(j, k)]));
-                }
-                u.combine_rows(i, lambda, j);
-            }
+            let lambdas = mat.column_in_rows(j, j + 1..n);
+            mat.eliminate(j, &lambdas);
+            u.eliminate(j, &lambdas);
         }
         u
     }
@@ -592,6 +552,33 @@ where
         }
     }
 
+    /// Returns the coefficients of column j in the given rows and zero in the others
+    fn column_in_rows(&self, j: usize, rows: Range<usize>) -> Vec<F::FieldElement> {
+        let mut lambdas = vec![self.field.zero(); self.rows];
+        for i in rows {
+            lambdas[i] = self[(i, j)];
+        }
+        lambdas
+    }
+
+    /// Modifies every row i other than p such that row i = row i - lambdas\[i\] * row p
+    ///
+    /// Rows are updated in parallel with the `parallel` feature.
+    fn eliminate(&mut self, p: usize, lambdas: &[F::FieldElement]) {
+        let f = &*self.field;
+        let cols = self.cols;
+        let pivot_row = self.data[p * cols..(p + 1) * cols].to_vec();
+        par::for_each_row(&mut self.data, cols, |i, row| {
+            let lambda = lambdas[i];
+            if i == p || lambda == f.zero() {
+                return;
+            }
+            for (x, &y) in row.iter_mut().zip(&pivot_row) {
+                *x = f.sub(*x, f.mul(lambda, y));
+            }
+        });
+    }
+
     /// Modifies row1 such that row1 = row1 + lambda * row2
     pub fn combine_rows(&mut self, row1: usize, lambda: F::FieldElement, row2: usize) {
         for j in 0..self.cols {
@@ -1,11 +1,12 @@
 //! Converts a matrix on F<sub>2</sub> into a byte vector and vice versa
 
-use std::{convert::TryInto, error::Error, rc::Rc};
+use std::sync::Arc;
 
 use super::Mat;
-use crate::finite_field::{Field, F2};
-
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use crate::{
+    finite_field::{Field, F2},
+    Error, Result,
+};
 
 impl Mat<F2> {
     /// Encodes the matrix in bytes
@@ -41,9 +42,14 @@ impl Mat<F2> {
     ///
     /// [`to_bytes()`]: #method.to_bytes
     pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
-        let rows = u32::from_be_bytes(vec[0..4].try_into()?) as usize;
-        let cols = u32::from_be_bytes(vec[4..8].try_into()?) as usize;
-        let f2 = Rc::new(F2::generate(()));
+        let rows = crate::read_u32(vec, 0, "matrix")? as usize;
+        let cols = crate::read_u32(vec, 4, "matrix")? as usize;
+        if rows == 0 || cols == 0 {
+            return Err(Error::Empty);
+        } else if vec.len() < 8 + crate::div_ceil(rows * cols, 8) {
+            return Err(Error::Truncated("matrix"));
+        }
+        let f2 = Arc::new(F2::generate(()));
         let mut mat = Mat::zero(f2, rows, cols);
         let mut read = 8;
         let mut shift = 7;
@@ -1,9 +1,15 @@
 //! Matrices on a field
 
-use std::rc::Rc;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
 
-use crate::finite_field::{F2FiniteExtension, Field, F2};
+use crate::{
+    finite_field::{F2FiniteExtension, Field, F2},
+    Error, Result,
+};
 
+pub use bitmat::BitMat;
+pub use bitvec::BitVec;
 pub use colvec::ColVec;
 pub use perm::Perm;
 pub use rowvec::RowVec;
@@ -15,7 +21,7 @@ pub struct Mat<F>
 where
     F: Field,
 {
-    field: Rc<F>,
+    field: Arc<F>,
     rows: usize,
     cols: usize,
     data: Vec<F::FieldElement>,
@@ -32,18 +38,28 @@ where
     /// # Panics
     ///
     /// Panics if the matrix is empty or if there are not exactly rows * cols coefficients.
-    pub fn new(field: Rc<F>, rows: usize, cols: usize, data: Vec<F::FieldElement>) -> Self {
+    pub fn new(field: Arc<F>, rows: usize, cols: usize, data: Vec<F::FieldElement>) -> Self {
+        Self::try_new(field, rows, cols, data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like new, except invalid input is reported as an error
+    pub fn try_new(
+        field: Arc<F>,
+        rows: usize,
+        cols: usize,
+        data: Vec<F::FieldElement>,
+    ) -> Result<Self> {
         if rows == 0 || cols == 0 || data.is_empty() {
-            panic!("Empty matrix");
+            return Err(Error::Empty);
         } else if data.len() != rows * cols {
-            panic!("Dimensions do not match the number of coefficients");
+            return Err(Error::DimensionMismatch);
         }
-        Self {
+        Ok(Self {
             field,
             rows,
             cols,
             data,
-        }
+        })
     }
 
     /// Creates a new matrix whose coefficients are all zero
@@ -51,7 +67,7 @@ where
     /// # Panics
     ///
     /// Panics if the number of either rows or columns is zero.
-    pub fn zero(field: Rc<F>, rows: usize, cols: usize) -> Self {
+    pub fn zero(field: Arc<F>, rows: usize, cols: usize) -> Self {
         if rows == 0 || cols == 0 {
             panic!("Empty matrix");
         }
@@ -81,8 +97,8 @@ where
         }
     }
 
-    pub fn field(&self) -> Rc<F> {
-        Rc::clone(&self.field)
+    pub fn field(&self) -> Arc<F> {
+        Arc::clone(&self.field)
     }
 
     pub fn rows(&self) -> usize {
@@ -97,12 +113,19 @@ where
         &self.data
     }
 
-    pub fn random(field: Rc<F>, n: usize, m: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn random(field: Arc<F>, n: usize, m: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), field, n, m)
+    }
+
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R>(rng: &mut R, field: Arc<F>, n: usize, m: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
         let mut mat = Self::zero(field, n, m);
         for i in 0..n {
             for j in 0..m {
-                mat[(i, j)] = mat.field.random_element(&mut rng);
+                mat[(i, j)] = mat.field.random_element(rng);
             }
         }
         mat
@@ -120,7 +143,7 @@ where
     }
 
     /// Creates a new matrix by taking the chosen rows in the given order
-    pub fn extract_rows(&self, rows: &Vec<usize>) -> Self {
+    pub fn extract_rows(&self, rows: &[usize]) -> Self {
         let mut res = Mat::zero(self.field(), rows.len(), self.cols);
         for i in 0..rows.len() {
             for j in 0..self.cols {
@@ -131,7 +154,7 @@ where
     }
 
     /// Creates a new matrix by taking the chosen columns in the given order
-    pub fn extract_cols(&self, cols: &Vec<usize>) -> Self {
+    pub fn extract_cols(&self, cols: &[usize]) -> Self {
         let mut res = Mat::zero(self.field(), self.rows, cols.len());
         for j in 0..cols.len() {
             for i in 0..res.rows {
@@ -141,7 +164,7 @@ where
         res
     }
 
-    pub fn identity(field: Rc<F>, n: usize) -> Self {
+    pub fn identity(field: Arc<F>, n: usize) -> Self {
         let mut id = Self::zero(field, n, n);
         for i in 0..n {
             id[(i, i)] = id.field.one();
@@ -164,8 +187,8 @@ where
     /// # Panics
     ///
     /// Panics if the vector contains an index that does not match any row of the matrix
-    pub fn keep_rows(&mut self, rows: &Vec<usize>) {
-        let mut rrows = rows.clone();
+    pub fn keep_rows(&mut self, rows: &[usize]) {
+        let mut rrows = rows.to_vec();
         rrows.sort_by(|a, b| a.cmp(b).reverse());
         if *rrows.first().unwrap() >= self.rows {
             panic!("invalid row index");
@@ -187,8 +210,8 @@ where
     /// # Panics
     ///
     /// Panics if the vector contains an index that does not match any row of the matrix
-    pub fn remove_rows(&mut self, rows: &Vec<usize>) {
-        let mut rrows = rows.clone();
+    pub fn remove_rows(&mut self, rows: &[usize]) {
+        let mut rrows = rows.to_vec();
         rrows.sort_by(|a, b| a.cmp(b).reverse());
         if *rrows.first().unwrap() >= self.rows {
             panic!("invalid row index");
@@ -199,6 +222,9 @@ where
         }
     }
 
+    /// Computes a * b into self
+    ///
+    /// Rows of the product are computed in parallel with the `parallel` feature.
     pub fn mul(&mut self, a: &Self, b: &Self) {
         if self.field != a.field || a.field != b.field {
             panic!("Cannot multiply matrices: fields don't match");
@@ -206,21 +232,57 @@ where
             panic!("Cannot multiply matrices: dimensions don't match");
         }
 
-        let f = a.field();
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                self[(i, j)] = f.zero();
-                for k in 0..a.cols {
-                    self[(i, j)] = f.add(self[(i, j)], f.mul(a[(i, k)], b[(k, j)]));
+        // Row i of the product is the combination of the rows of b by the coefficients of row i of a
+        let f = &*a.field;
+        let cols = self.cols;
+        par::for_each_row(&mut self.data, cols, |i, row| {
+            for x in row.iter_mut() {
+                *x = f.zero();
+            }
+            for k in 0..a.cols {
+                let lambda = a[(i, k)];
+                if lambda == f.zero() {
+                    continue;
+                }
+                for (x, &y) in row.iter_mut().zip(&b.data[k * cols..(k + 1) * cols]) {
+                    *x = f.add(*x, f.mul(lambda, y));
                 }
             }
+        });
+    }
+
+    /// Like `self + other`, except mismatched operands are reported as an error
+    pub fn try_add(&self, other: &Self) -> Result<Self> {
+        self.check_operand(other, self.rows == other.rows && self.cols == other.cols)?;
+        Ok(self + other)
+    }
+
+    /// Like `self - other`, except mismatched operands are reported as an error
+    pub fn try_sub(&self, other: &Self) -> Result<Self> {
+        self.check_operand(other, self.rows == other.rows && self.cols == other.cols)?;
+        Ok(self - other)
+    }
+
+    /// Like `self * other`, except mismatched operands are reported as an error
+    pub fn try_mul(&self, other: &Self) -> Result<Self> {
+        self.check_operand(other, self.cols == other.rows)?;
+        Ok(self * other)
+    }
+
+    fn check_operand(&self, other: &Self, dimensions_match: bool) -> Result<()> {
+        if self.field != other.field {
+            Err(Error::FieldMismatch)
+        } else if !dimensions_match {
+            Err(Error::DimensionMismatch)
+        } else {
+            Ok(())
         }
     }
 
     /// Takes a t * n matrix on F<sub>2<sup>m</sup></sub>
     /// and outputs a mt * n matrix on F<sub>2</sub>
     /// by decomposing each coefficient on the canonical basis
-    pub fn binary(&self, field: Rc<F2>) -> Mat<F2>
+    pub fn binary(&self, field: Arc<F2>) -> Mat<F2>
     where
         F: F2FiniteExtension,
     {
@@ -270,20 +332,39 @@ where
         ab
     }
 
-    pub fn random_standard_form_parity_check_matrix(field: Rc<F>, n: usize, k: usize) -> Self {
+    pub fn random_standard_form_parity_check_matrix(field: Arc<F>, n: usize, k: usize) -> Self {
+        Self::random_standard_form_parity_check_matrix_with_rng(
+            &mut rand::thread_rng(),
+            field,
+            n,
+            k,
+        )
+    }
+
+    /// Like random_standard_form_parity_check_matrix, except coefficients are drawn from rng
+    pub fn random_standard_form_parity_check_matrix_with_rng<R>(
+        rng: &mut R,
+        field: Arc<F>,
+        n: usize,
+        k: usize,
+    ) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
         assert!(k <= n, "k must be at most n");
-        let mut rng = rand::thread_rng();
         let mut h = Self::zero(field, n - k, n);
         for i in 0..n - k {
             h[(i, k + i)] = h.field.one();
             for j in 0..k {
-                h[(i, j)] = h.field.random_element(&mut rng);
+                h[(i, j)] = h.field.random_element(rng);
             }
         }
         h
     }
 }
 
+pub mod bitmat;
+pub mod bitvec;
 pub mod colvec;
 pub mod gauss;
 pub mod io;
@@ -291,3 +372,7 @@ pub mod perm;
 pub mod rowvec;
 pub mod submat;
 pub mod traits;
+
+mod par;
+#[cfg(feature = "serde")]
+mod serde;
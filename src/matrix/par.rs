@@ -0,0 +1,28 @@
+//! Row-wise iteration over matrix coefficients
+//!
+//! With the `parallel` feature, rows are processed on the rayon thread pool.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Number of coefficients in a block when a single row is split between tasks
+pub(crate) const BLOCK_LEN: usize = 256;
+
+/// Applies f to every row of data, a matrix stored row by row with the given row length
+///
+/// f receives the row index and the row.
+pub(crate) fn for_each_row<T, G>(data: &mut [T], len: usize, f: G)
+where
+    T: Send,
+    G: Fn(usize, &mut [T]) + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    data.par_chunks_mut(len)
+        .enumerate()
+        .for_each(|(i, row)| f(i, row));
+
+    #[cfg(not(feature = "parallel"))]
+    data.chunks_mut(len)
+        .enumerate()
+        .for_each(|(i, row)| f(i, row));
+}
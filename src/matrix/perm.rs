@@ -1,8 +1,8 @@
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 use std::ops::{Index, IndexMut, Mul};
 
 use super::ColVec;
-use crate::finite_field::Field;
+use crate::{finite_field::Field, Error, Result};
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Perm(Vec<usize>);
@@ -33,32 +33,41 @@ impl Perm {
     /// Panics if vec contains an out of range image
     /// or the same image twice.
     pub fn new(vec: Vec<usize>) -> Self {
+        Self::try_new(vec).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like new, except an invalid permutation is reported as an error
+    pub fn try_new(vec: Vec<usize>) -> Result<Self> {
         let n = vec.len();
         let mut list = vec![false; n];
         for i in 0..n {
             if vec[i] >= n {
-                panic!("Invalid image");
+                return Err(Error::InvalidParameters("Invalid image"));
             }
-            if list[vec[i]] == false {
+            if !list[vec[i]] {
                 list[vec[i]] = true;
             } else {
-                panic!("Image has already been assigned");
+                return Err(Error::InvalidParameters("Image has already been assigned"));
             }
         }
-        Perm(vec)
+        Ok(Perm(vec))
     }
 
     pub fn random(n: usize) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::random_with_rng(&mut rand::thread_rng(), n)
+    }
+
+    /// Like random, except the permutation is drawn from rng
+    pub fn random_with_rng<R: CryptoRng + RngCore>(rng: &mut R, n: usize) -> Self {
         let mut cols = Vec::with_capacity(n);
         for i in 0..n {
             cols.push(i);
         }
 
         let mut vec = vec![0; n];
-        for i in 0..n {
+        for image in vec.iter_mut() {
             let index = rng.gen_range(0, cols.len());
-            vec[i] = cols[index];
+            *image = cols[index];
             cols.swap_remove(index);
         }
         Perm(vec)
@@ -82,6 +91,10 @@ impl Perm {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn data(&self) -> &Vec<usize> {
         &self.0
     }
@@ -113,6 +126,48 @@ impl Perm {
     }
 }
 
+impl Mul<Perm> for Perm {
+    type Output = Perm;
+
+    fn mul(self, other: Perm) -> Self::Output {
+        &self * &other
+    }
+}
+
+impl Mul<&Perm> for Perm {
+    type Output = Perm;
+
+    fn mul(self, other: &Perm) -> Self::Output {
+        &self * other
+    }
+}
+
+impl Mul<Perm> for &Perm {
+    type Output = Perm;
+
+    fn mul(self, other: Perm) -> Self::Output {
+        self * &other
+    }
+}
+
+/// Composes two permutations
+///
+/// The product is such that M * (P * Q) = (M * P) * Q for any matrix M.
+///
+/// # Panics
+///
+/// Panics if the permutations have different lengths.
+impl Mul<&Perm> for &Perm {
+    type Output = Perm;
+
+    fn mul(self, other: &Perm) -> Self::Output {
+        if self.len() != other.len() {
+            panic!("Cannot multiply permutations: lengths don't match");
+        }
+        Perm(other.0.iter().map(|&i| self[i]).collect())
+    }
+}
+
 impl<F> Mul<ColVec<F>> for Perm
 where
     F: Field,
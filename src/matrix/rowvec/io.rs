@@ -1,21 +1,23 @@
 //! Converts a row vector on F<sub>2</sub> into a byte vector and vice versa
 
 use std::{
-    convert::TryInto,
-    error::Error,
     fs::File,
-    io::{Read, Write},
-    rc::Rc,
+    io::{self, Read, Write},
+    sync::Arc,
 };
 
 use super::RowVec;
-use crate::finite_field::{Field, F2};
-
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use crate::{
+    finite_field::{Field, F2},
+    Error, Result,
+};
 
 impl RowVec<F2> {
-    pub fn write(&self, file_name: &str) -> Result<()> {
-        let mut f = File::create(file_name)?;
+    /// Encodes the vector in bytes
+    ///
+    /// We start by encoding the length of the vector on four bytes.
+    /// The coefficients follow (eight per byte).
+    pub fn to_bytes(&self) -> Vec<u8> {
         let len = 4 + crate::div_ceil(self.cols(), 8);
         let mut vec = Vec::with_capacity(len);
         vec.extend_from_slice(&(self.cols() as u32).to_be_bytes());
@@ -34,28 +36,90 @@ impl RowVec<F2> {
         if self.cols() % 8 != 0 {
             vec.push(byte);
         }
-        f.write_all(&vec)?;
+        vec
+    }
+
+    /// Saves the vector on disk, encoded with [`to_bytes()`]
+    ///
+    /// Plaintexts and ciphertexts are better saved with [`container::write_vector()`],
+    /// which records what the vector is.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    /// [`container::write_vector()`]: ../../crypto/container/fn.write_vector.html
+    pub fn write(&self, file_name: &str) -> Result<()> {
+        self.write_to(File::create(file_name)?)
+    }
+
+    /// Writes the vector encoded with [`to_bytes()`] to any writer
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(&self.to_bytes())?;
+        writer.flush()?;
         Ok(())
     }
 
-    pub fn read_vector(file_name: &str) -> Result<RowVec<F2>> {
-        let mut f = File::open(file_name)?;
-        let mut vec = Vec::new();
-        f.read_to_end(&mut vec)?;
-        let cols = u32::from_be_bytes(vec[0..4].try_into()?) as usize;
-        let f2 = Rc::new(F2::generate(()));
+    /// Decodes bytes encoded with [`to_bytes()`] to a vector
+    ///
+    /// Also returns the number of bytes read.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
+        let cols = crate::read_u32(vec, 0, "vector")? as usize;
+        if cols == 0 {
+            return Err(Error::Empty);
+        } else if vec.len() < 4 + crate::div_ceil(cols, 8) {
+            return Err(Error::Truncated("vector"));
+        }
+        let f2 = Arc::new(F2::generate(()));
         let mut rowvec = RowVec::zero(f2, cols);
-        let mut k = 4;
+        let mut read = 4;
         let mut shift = 7;
         for i in 0..cols {
-            rowvec[i] = ((vec[k] >> shift) & 1).into();
+            rowvec[i] = ((vec[read] >> shift) & 1).into();
             if shift == 0 {
-                k += 1;
+                read += 1;
                 shift = 7;
             } else {
                 shift -= 1;
             }
         }
+        if shift != 7 {
+            read += 1;
+        }
+        Ok((read, rowvec))
+    }
+
+    /// Reads a vector saved with [`write()`]
+    ///
+    /// [`write()`]: #method.write
+    pub fn read_vector(file_name: &str) -> Result<RowVec<F2>> {
+        Self::read_from(File::open(file_name)?)
+    }
+
+    /// Reads a vector encoded with [`to_bytes()`] from any reader
+    ///
+    /// Exactly the bytes of the vector are read, so that the reader can be read further.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
+    pub fn read_from<R: Read>(mut reader: R) -> Result<RowVec<F2>> {
+        let mut vec = vec![0; 4];
+        reader.read_exact(&mut vec).map_err(truncated)?;
+        let cols = crate::read_u32(&vec, 0, "vector")? as usize;
+        if cols == 0 {
+            return Err(Error::Empty);
+        }
+        let len = crate::div_ceil(cols, 8) as u64;
+        reader.take(len).read_to_end(&mut vec)?;
+        let (_, rowvec) = Self::from_bytes(&vec)?;
         Ok(rowvec)
     }
 }
+
+/// Reports a reader ending in the middle of a vector as a truncated vector
+fn truncated(e: io::Error) -> Error {
+    match e.kind() {
+        io::ErrorKind::UnexpectedEof => Error::Truncated("vector"),
+        _ => e.into(),
+    }
+}
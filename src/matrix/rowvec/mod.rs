@@ -1,8 +1,8 @@
-use rand::Rng;
-use std::rc::Rc;
+use rand::{CryptoRng, Rng, RngCore};
+use std::sync::Arc;
 
 use super::{ColVec, Mat, Perm};
-use crate::finite_field::{Field};
+use crate::{finite_field::Field, Result};
 
 #[derive(Eq, PartialEq)]
 pub struct RowVec<F>(pub Mat<F>)
@@ -13,15 +13,20 @@ impl<F> RowVec<F>
 where
     F: Field,
 {
-    pub fn new(field: Rc<F>, data: Vec<F::FieldElement>) -> Self {
+    pub fn new(field: Arc<F>, data: Vec<F::FieldElement>) -> Self {
         RowVec(Mat::new(field, 1, data.len(), data))
     }
 
-    pub fn zero(field: Rc<F>, cols: usize) -> Self {
+    /// Like new, except an empty vector is reported as an error
+    pub fn try_new(field: Arc<F>, data: Vec<F::FieldElement>) -> Result<Self> {
+        Ok(RowVec(Mat::try_new(field, 1, data.len(), data)?))
+    }
+
+    pub fn zero(field: Arc<F>, cols: usize) -> Self {
         RowVec(Mat::zero(field, 1, cols))
     }
 
-    pub fn field(&self) -> Rc<F> {
+    pub fn field(&self) -> Arc<F> {
         self.0.field()
     }
 
@@ -47,12 +52,24 @@ where
         weight
     }
 
-    pub fn random(field: Rc<F>, n: usize) -> Self {
-        RowVec(Mat::random(field, 1, n))
+    pub fn random(field: Arc<F>, n: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), field, n)
+    }
+
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R: CryptoRng + RngCore>(rng: &mut R, field: Arc<F>, n: usize) -> Self {
+        RowVec(Mat::random_with_rng(rng, field, 1, n))
+    }
+
+    pub fn random_with_weight(field: Arc<F>, n: usize, w: usize) -> Self {
+        Self::random_with_weight_with_rng(&mut rand::thread_rng(), field, n, w)
     }
 
-    pub fn random_with_weight(field: Rc<F>, n: usize, w: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Like random_with_weight, except the vector is drawn from rng
+    pub fn random_with_weight_with_rng<R>(rng: &mut R, field: Arc<F>, n: usize, w: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
         let mut vec = RowVec::zero(field, n);
         let mut cols = Vec::with_capacity(n);
         for i in 0..n {
@@ -62,7 +79,7 @@ where
         for _i in 0..w {
             let mut elt;
             loop {
-                elt = vec.field().random_element(&mut rng);
+                elt = vec.field().random_element(rng);
                 if elt != vec.field().zero() {
                     break;
                 }
@@ -87,7 +104,7 @@ where
         true
     }
 
-    pub fn extract_cols(&self, perm: &Vec<usize>) -> Self {
+    pub fn extract_cols(&self, perm: &[usize]) -> Self {
         RowVec(self.0.extract_cols(perm))
     }
 }
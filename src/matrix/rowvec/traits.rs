@@ -5,6 +5,7 @@ use std::{
 
 use super::{Mat, Perm, RowVec};
 use crate::finite_field::{F2FiniteExtension, Field, FiniteField};
+use crate::matrix::par;
 
 impl<F> From<Mat<F>> for RowVec<F>
 where
@@ -188,7 +189,30 @@ where
     type Output = RowVec<F>;
 
     fn mul(self, other: &Mat<F>) -> Self::Output {
-        RowVec(&self.0 * other)
+        if self.0.field != other.field {
+            panic!("Cannot multiply matrices: fields don't match");
+        } else if self.cols() != other.rows {
+            panic!("Cannot multiply matrices: dimensions don't match");
+        }
+
+        // Each block of the product is a combination of the same blocks of the rows of other
+        let f = &*other.field;
+        let cols = other.cols;
+        let mut prod = RowVec::zero(other.field(), cols);
+        par::for_each_row(&mut prod.0.data, par::BLOCK_LEN, |b, block| {
+            let start = b * par::BLOCK_LEN;
+            for k in 0..other.rows {
+                let lambda = self[k];
+                if lambda == f.zero() {
+                    continue;
+                }
+                let row = &other.data[k * cols + start..k * cols + start + block.len()];
+                for (x, &y) in block.iter_mut().zip(row) {
+                    *x = f.add(*x, f.mul(lambda, y));
+                }
+            }
+        });
+        prod
     }
 }
 
@@ -0,0 +1,243 @@
+//! Serialization of matrices with serde
+//!
+//! A matrix is serialized as its field, its dimensions and its coefficients row by row.
+//! Vectors are serialized as matrices of one row or column.
+//! Packed matrices are serialized as the bytes of their [`to_bytes()`] encoding.
+//!
+//! [`to_bytes()`]: ../struct.BitMat.html#method.to_bytes
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+use super::{BitMat, BitVec, ColVec, Mat, Perm, RowVec};
+use crate::{
+    finite_field::{serde as field_serde, DeserializeWithField, SerdeField},
+    Error,
+};
+
+#[derive(Deserialize, Serialize)]
+struct MatRepr<R> {
+    field: R,
+    rows: usize,
+    cols: usize,
+    data: Vec<u32>,
+}
+
+impl<F> Mat<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_on<'de, D>(field: Option<Arc<F>>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = MatRepr::<F::Repr>::deserialize(deserializer)?;
+        let field = field_serde::field(repr.field, field).map_err(D::Error::custom)?;
+        let data =
+            field_serde::repr_to_elts(field.as_ref(), &repr.data).map_err(D::Error::custom)?;
+        Mat::try_new(field, repr.rows, repr.cols, data).map_err(D::Error::custom)
+    }
+}
+
+impl<F> Serialize for Mat<F>
+where
+    F: SerdeField,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        MatRepr {
+            field: self.field.to_repr(),
+            rows: self.rows,
+            cols: self.cols,
+            data: field_serde::elts_to_repr(self.field.as_ref(), &self.data),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, F> Deserialize<'de> for Mat<F>
+where
+    F: SerdeField,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(None, deserializer)
+    }
+}
+
+impl<'de, F> DeserializeWithField<'de, F> for Mat<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_with_field<D>(field: Arc<F>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(Some(field), deserializer)
+    }
+}
+
+impl<F> RowVec<F>
+where
+    F: SerdeField,
+{
+    fn from_mat<E: serde::de::Error>(mat: Mat<F>) -> Result<Self, E> {
+        if mat.rows() != 1 {
+            return Err(E::custom(Error::DimensionMismatch));
+        }
+        Ok(RowVec(mat))
+    }
+}
+
+impl<F> Serialize for RowVec<F>
+where
+    F: SerdeField,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, F> Deserialize<'de> for RowVec<F>
+where
+    F: SerdeField,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_mat(Mat::deserialize(deserializer)?)
+    }
+}
+
+impl<'de, F> DeserializeWithField<'de, F> for RowVec<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_with_field<D>(field: Arc<F>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_mat(Mat::deserialize_with_field(field, deserializer)?)
+    }
+}
+
+impl<F> ColVec<F>
+where
+    F: SerdeField,
+{
+    fn from_mat<E: serde::de::Error>(mat: Mat<F>) -> Result<Self, E> {
+        if mat.cols() != 1 {
+            return Err(E::custom(Error::DimensionMismatch));
+        }
+        Ok(ColVec(mat))
+    }
+}
+
+impl<F> Serialize for ColVec<F>
+where
+    F: SerdeField,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, F> Deserialize<'de> for ColVec<F>
+where
+    F: SerdeField,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_mat(Mat::deserialize(deserializer)?)
+    }
+}
+
+impl<'de, F> DeserializeWithField<'de, F> for ColVec<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_with_field<D>(field: Arc<F>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_mat(Mat::deserialize_with_field(field, deserializer)?)
+    }
+}
+
+impl Serialize for Perm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Perm {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Perm::try_new(Vec::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for BitMat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for BitMat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = crate::deserialize_bytes(deserializer)?;
+        match BitMat::from_bytes(&bytes) {
+            Ok((read, mat)) if read == bytes.len() => Ok(mat),
+            Ok(_) => Err(D::Error::custom(Error::Malformed(
+                "Trailing bytes after matrix",
+            ))),
+            Err(e) => Err(D::Error::custom(e)),
+        }
+    }
+}
+
+impl Serialize for BitVec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitVec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mat = BitMat::deserialize(deserializer)?;
+        if mat.rows() != 1 {
+            return Err(D::Error::custom(Error::DimensionMismatch));
+        }
+        Ok(BitVec(mat))
+    }
+}
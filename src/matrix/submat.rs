@@ -2,7 +2,7 @@ use super::Mat;
 use std::{
     fmt::{Display, Formatter, Result},
     ops::Index,
-    rc::Rc,
+    sync::Arc,
 };
 
 use crate::finite_field::{Field, FiniteField};
@@ -34,7 +34,7 @@ where
         }
     }
 
-    pub fn field(&self) -> Rc<F> {
+    pub fn field(&self) -> Arc<F> {
         self.mat.field()
     }
 
@@ -80,10 +80,9 @@ where
         let k = self.field();
 
         // Upper bound on the number of digits of order
-        let digits =
-            ((usize::min_value().leading_zeros() - k.order().leading_zeros()) / 3 + 1) as usize;
+        let digits = ((usize::MIN.leading_zeros() - k.order().leading_zeros()) / 3 + 1) as usize;
 
-        write!(f, "\n")?;
+        writeln!(f)?;
         for i in 0..self.rows() {
             for j in 0..self.cols() - 1 {
                 write!(
@@ -93,9 +92,9 @@ where
                     width = if k.order() == 2 { 1 } else { 2 + digits }
                 )?;
             }
-            write!(
+            writeln!(
                 f,
-                "{:>width$}\n",
+                "{:>width$}",
                 k.elt_to_str(self[(i, self.cols() - 1)]),
                 width = if k.order() == 2 { 1 } else { 2 + digits }
             )?;
@@ -1,7 +1,7 @@
 use std::{
     fmt::{Debug, Display, Formatter, Result},
     ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
-    rc::Rc,
+    sync::Arc,
 };
 
 use super::{ColVec, Mat, Perm, RowVec};
@@ -13,7 +13,7 @@ where
 {
     fn clone(&self) -> Self {
         Mat {
-            field: Rc::clone(&self.field),
+            field: Arc::clone(&self.field),
             rows: self.rows,
             cols: self.cols,
             data: self.data.clone(),
@@ -217,15 +217,8 @@ where
             panic!("Cannot multiply matrices: dimensions don't match");
         }
 
-        let f = self.field();
-        let mut prod = Mat::zero(Rc::clone(&f), self.rows, other.cols);
-        for i in 0..prod.rows {
-            for j in 0..prod.cols {
-                for k in 0..self.cols {
-                    prod[(i, j)] = f.add(prod[(i, j)], f.mul(self[(i, k)], other[(k, j)]));
-                }
-            }
-        }
+        let mut prod = Mat::zero(self.field(), self.rows, other.cols);
+        Mat::mul(&mut prod, self, other);
         prod
     }
 }
@@ -251,15 +244,7 @@ where
         }
 
         let tmp = self.clone();
-        let f = tmp.field();
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                self[(i, j)] = f.zero();
-                for k in 0..tmp.cols {
-                    self[(i, j)] = f.add(self[(i, j)], f.mul(tmp[(i, k)], other[(k, j)]));
-                }
-            }
-        }
+        Mat::mul(self, &tmp, other);
     }
 }
 
@@ -368,14 +353,14 @@ where
         let k = self.field();
         let m = k.characteristic_exponent() as usize;
         let width = crate::div_ceil(m, 4);
-        write!(f, "\n")?;
+        writeln!(f)?;
         for i in 0..self.rows {
             for j in 0..self.cols - 1 {
                 write!(f, "{:>w$x} ", k.elt_to_u32(self[(i, j)]), w = width)?;
             }
-            write!(
+            writeln!(
                 f,
-                "{:>w$x}\n",
+                "{:>w$x}",
                 k.elt_to_u32(self[(i, self.cols - 1)]),
                 w = width
             )?;
@@ -392,10 +377,9 @@ where
         let k = self.field();
 
         // Upper bound on the number of digits of order
-        let digits =
-            ((usize::min_value().leading_zeros() - k.order().leading_zeros()) / 3 + 1) as usize;
+        let digits = ((usize::MIN.leading_zeros() - k.order().leading_zeros()) / 3 + 1) as usize;
 
-        write!(f, "\n")?;
+        writeln!(f)?;
         for i in 0..self.rows {
             for j in 0..self.cols - 1 {
                 write!(
@@ -405,9 +389,9 @@ where
                     width = if k.order() == 2 { 1 } else { 2 + digits }
                 )?;
             }
-            write!(
+            writeln!(
                 f,
-                "{:>width$}\n",
+                "{:>width$}",
                 k.elt_to_str(self[(i, self.cols - 1)]),
                 width = if k.order() == 2 { 1 } else { 2 + digits }
             )?;
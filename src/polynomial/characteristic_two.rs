@@ -1,4 +1,5 @@
-use std::rc::Rc;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
 
 use super::Poly;
 use crate::finite_field::{f2m, CharacteristicTwo, F2FiniteExtension, Field};
@@ -7,19 +8,27 @@ impl<F> Poly<F>
 where
     F: CharacteristicTwo + Field,
 {
-    pub fn random_monic_irreducible(field: Rc<F>, degree: usize) -> Self
+    pub fn random_monic_irreducible(field: Arc<F>, degree: usize) -> Self
     where
         F: F2FiniteExtension,
     {
-        let mut rng = rand::thread_rng();
+        Self::random_monic_irreducible_with_rng(&mut rand::thread_rng(), field, degree)
+    }
+
+    /// Like random_monic_irreducible, except coefficients are drawn from rng
+    pub fn random_monic_irreducible_with_rng<R>(rng: &mut R, field: Arc<F>, degree: usize) -> Self
+    where
+        F: F2FiniteExtension,
+        R: CryptoRng + RngCore,
+    {
         let mut p = Self::zero(field, degree + 1);
         p[degree] = p.field.one();
         for i in 0..degree {
-            p[i] = p.field.random_element(&mut rng);
+            p[i] = p.field.random_element(rng);
         }
         while !p.is_irreducible() {
             for i in 0..degree {
-                p[i] = p.field.random_element(&mut rng);
+                p[i] = p.field.random_element(rng);
             }
         }
         p
@@ -43,22 +52,22 @@ where
         n_prime_factors.dedup();
         let n_div_primes: Vec<u32> = n_prime_factors.iter().map(|x| n / x).collect();
 
-        for i in 0..n_prime_factors.len() {
-            let mut h = Self::x_n(Rc::clone(&f), 1);
-            for _j in 0..n_div_primes[i] {
+        for &n_div_prime in n_div_primes.iter() {
+            let mut h = Self::x_n(Arc::clone(&f), 1);
+            for _j in 0..n_div_prime {
                 h.pow_modulo(q, self);
             }
-            h -= &Self::x_n(Rc::clone(&f), 1);
+            h -= &Self::x_n(Arc::clone(&f), 1);
             let g = Self::gcd(self, &h);
             if g.degree() != 0 {
                 return false;
             }
         }
-        let mut g = Self::x_n(Rc::clone(&f), 1);
+        let mut g = Self::x_n(Arc::clone(&f), 1);
         for _i in 0..n {
             g.pow_modulo(q, self);
         }
-        g -= &Self::x_n(Rc::clone(&f), 1);
+        g -= &Self::x_n(Arc::clone(&f), 1);
         g.modulo(self);
         g.is_zero()
     }
@@ -94,9 +103,9 @@ where
         let mut tmp = self.clone();
         for _i in 0..m * modulus.degree() - 2 {
             tmp.square();
-            tmp.modulo(&modulus);
+            tmp.modulo(modulus);
             *self *= &tmp;
-            self.modulo(&modulus);
+            self.modulo(modulus);
         }
     }
 
@@ -147,8 +156,8 @@ where
         let mut b = Vec::new();
         a.push(g.clone());
         a.push(t.clone());
-        let b0 = Self::zero(Rc::clone(&f), 1);
-        let b1 = Self::x_n(Rc::clone(&f), 0);
+        let b0 = Self::zero(Arc::clone(&f), 1);
+        let b1 = Self::x_n(Arc::clone(&f), 0);
         b.push(b0);
         b.push(b1);
 
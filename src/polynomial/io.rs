@@ -1,22 +1,32 @@
 //! Converts a polynomial on F<sub>2<sup>m</sup></sub> into a byte vector and vice versa
 
-use std::{convert::TryInto, error::Error, rc::Rc};
+use std::sync::Arc;
 
 use super::Poly;
-use crate::finite_field::{F2FiniteExtension, F2m, Field, FiniteField};
-
-type Result<T> = std::result::Result<T, Box<dyn Error>>;
+use crate::{
+    finite_field::{f2m, F2FiniteExtension, F2m, FiniteField},
+    Error, Result,
+};
 
 impl Poly<F2m> {
     /// Encodes the polynomial in bytes
     ///
     /// We start by encoding field order and degree of the polynomial followed by coefficients.
     /// Each number is encoded on four bytes.
+    /// If the field is not defined by the default polynomial (see [`primitive_poly()`]),
+    /// its polynomial is encoded in place of the order.
+    /// Both are told apart since the order is a power of 2 and the polynomial is not.
+    ///
+    /// [`primitive_poly()`]: ../finite_field/f2m/fn.primitive_poly.html
     pub fn to_bytes(&self) -> Vec<u8> {
         let f = self.field();
         let len = 4 + 4 + 4 * (self.degree() + 1);
         let mut vec = Vec::with_capacity(len);
-        vec.extend_from_slice(&(f.order() as u32).to_be_bytes());
+        if f.poly() == f2m::primitive_poly(f.order()) {
+            vec.extend_from_slice(&(f.order() as u32).to_be_bytes());
+        } else {
+            vec.extend_from_slice(&f.poly().to_be_bytes());
+        }
         vec.extend_from_slice(&(self.degree() as u32).to_be_bytes());
         for i in 0..self.degree() + 1 {
             vec.extend_from_slice(&(f.elt_to_u32(self[i])).to_be_bytes());
@@ -24,18 +34,31 @@ impl Poly<F2m> {
         vec
     }
 
+    /// Decodes bytes encoded with [`to_bytes()`] to a polynomial
+    ///
+    /// Also returns the number of bytes read.
+    ///
+    /// [`to_bytes()`]: #method.to_bytes
     pub fn from_bytes(vec: &[u8]) -> Result<(usize, Self)> {
-        let order = u32::from_be_bytes(vec[0..4].try_into()?) as usize;
-        let t = u32::from_be_bytes(vec[4..8].try_into()?) as usize;
-        let f2m = Rc::new(F2m::generate(order));
+        let order_or_poly = crate::read_u32(vec, 0, "polynomial")?;
+        let t = crate::read_u32(vec, 4, "polynomial")? as usize;
+        let f2m = if order_or_poly.is_power_of_two() {
+            Arc::new(F2m::try_generate(order_or_poly as usize)?)
+        } else {
+            Arc::new(F2m::try_from_poly(order_or_poly)?)
+        };
+        let read = 4 + 4 + 4 * (t + 1);
+        if vec.len() < read {
+            return Err(Error::Truncated("polynomial"));
+        }
         let mut poly = Self::zero(f2m, t + 1);
         for i in 0..t + 1 {
-            let j = 8 + 4 * i;
-            poly[i] = poly
-                .field()
-                .u32_to_elt(u32::from_be_bytes(vec[j..j + 4].try_into()?));
+            let coefficient = crate::read_u32(vec, 8 + 4 * i, "polynomial")?;
+            if coefficient as usize >= poly.field().order() {
+                return Err(Error::Malformed("Coefficient out of the field"));
+            }
+            poly[i] = poly.field().u32_to_elt(coefficient);
         }
-        let read = 4 + 4 + 4 * (t + 1);
         Ok((read, poly))
     }
 }
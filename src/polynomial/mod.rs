@@ -1,8 +1,11 @@
 //! Polynomials on a field
 
-use std::rc::Rc;
+use rand::{CryptoRng, RngCore};
+use std::sync::Arc;
 
-use crate::finite_field::Field;
+use crate::{finite_field::Field, Error, Result};
+
+pub use roots::RootFinding;
 
 /// Polynomial with coefficients in a field F
 #[derive(Eq)]
@@ -10,7 +13,7 @@ pub struct Poly<F>
 where
     F: Field,
 {
-    field: Rc<F>,
+    field: Arc<F>,
     data: Vec<F::FieldElement>,
 }
 
@@ -23,13 +26,21 @@ where
     /// # Panics
     ///
     /// Panics if the polynomial is empty i.e. if the vector data is empty.
-    pub fn new(field: Rc<F>, data: Vec<F::FieldElement>) -> Self {
+    pub fn new(field: Arc<F>, data: Vec<F::FieldElement>) -> Self {
         if data.is_empty() {
             panic!("Polynomial must have at least one coefficient");
         }
         Self { field, data }
     }
 
+    /// Like new, except an empty polynomial is reported as an error
+    pub fn try_new(field: Arc<F>, data: Vec<F::FieldElement>) -> Result<Self> {
+        if data.is_empty() {
+            return Err(Error::Empty);
+        }
+        Ok(Self { field, data })
+    }
+
     /// Creates a zero polynomial
     ///
     /// len is the starting length of the data vector. It must be at least 1.
@@ -37,7 +48,7 @@ where
     /// # Panics
     ///
     /// Panics if len is zero.
-    pub fn zero(field: Rc<F>, len: usize) -> Self {
+    pub fn zero(field: Arc<F>, len: usize) -> Self {
         if len == 0 {
             panic!("Polynomial must have at least one coefficient");
         }
@@ -46,7 +57,7 @@ where
     }
 
     /// Creates the monic monomial x<sup>n</sup>
-    pub fn x_n(field: Rc<F>, n: usize) -> Self {
+    pub fn x_n(field: Arc<F>, n: usize) -> Self {
         let mut data = vec![field.zero(); n + 1];
         data[n] = field.one();
         Self { field, data }
@@ -58,7 +69,7 @@ where
     /// # Panics
     ///
     /// Panics if support is empty.
-    pub fn support(field: Rc<F>, support: &[usize]) -> Self {
+    pub fn support(field: Arc<F>, support: &[usize]) -> Self {
         if support.is_empty() {
             panic!("Support cannot be empty");
         }
@@ -73,8 +84,8 @@ where
         Self { field, data }
     }
 
-    pub fn field(&self) -> Rc<F> {
-        Rc::clone(&self.field)
+    pub fn field(&self) -> Arc<F> {
+        Arc::clone(&self.field)
     }
 
     pub fn degree(&self) -> usize {
@@ -95,30 +106,58 @@ where
     }
 
     /// Returns a random monic polynomial of the chosen degree
-    pub fn random(field: Rc<F>, degree: usize) -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn random(field: Arc<F>, degree: usize) -> Self {
+        Self::random_with_rng(&mut rand::thread_rng(), field, degree)
+    }
+
+    /// Like random, except coefficients are drawn from rng
+    pub fn random_with_rng<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        field: Arc<F>,
+        degree: usize,
+    ) -> Self {
         let mut p = Self::zero(field, degree + 1);
         for i in 0..degree {
-            p[i] = p.field.random_element(&mut rng);
+            p[i] = p.field.random_element(rng);
         }
         while p[degree] == p.field.zero() {
-            p[degree] = p.field.random_element(&mut rng);
+            p[degree] = p.field.random_element(rng);
         }
         p
     }
 
-    /// Evaluates polynomial at point
+    /// Evaluates polynomial at point by Horner's rule
     pub fn eval(&self, point: F::FieldElement) -> F::FieldElement {
-        let f = self.field();
-        let mut eval = self[0];
-        for i in 1..self.degree() + 1 {
-            let mut x = f.one();
-            for _j in 0..i {
-                x = f.mul(x, point);
-            }
-            eval = f.add(eval, f.mul(self[i], x));
+        let f = &self.field;
+        self.data
+            .iter()
+            .rev()
+            .fold(f.zero(), |eval, &c| f.add(f.mul(eval, point), c))
+    }
+
+    /// Like `self + other`, except polynomials on different fields are reported as an error
+    pub fn try_add(&self, other: &Self) -> Result<Self> {
+        self.check_field(other)?;
+        Ok(self + other)
+    }
+
+    /// Like `self - other`, except polynomials on different fields are reported as an error
+    pub fn try_sub(&self, other: &Self) -> Result<Self> {
+        self.check_field(other)?;
+        Ok(self - other)
+    }
+
+    /// Like `self * other`, except polynomials on different fields are reported as an error
+    pub fn try_mul(&self, other: &Self) -> Result<Self> {
+        self.check_field(other)?;
+        Ok(self * other)
+    }
+
+    fn check_field(&self, other: &Self) -> Result<()> {
+        if self.field != other.field {
+            return Err(Error::FieldMismatch);
         }
-        eval
+        Ok(())
     }
 
     /// <https://en.wikipedia.org/wiki/Polynomial_greatest_common_divisor>
@@ -134,7 +173,7 @@ where
             return (Self::zero(f, 1), a.clone());
         }
 
-        let mut q = Self::zero(Rc::clone(&f), a.degree() - b.degree() + 1);
+        let mut q = Self::zero(Arc::clone(&f), a.degree() - b.degree() + 1);
         let mut r = a.clone();
         let b_deg = b.degree();
         let b_lc_inv = f.inv(b[b_deg]).unwrap();
@@ -192,12 +231,12 @@ where
         let mut t = Vec::new();
         r.push(a.clone());
         r.push(b.clone());
-        let s0 = Self::x_n(Rc::clone(&f), 0);
-        let s1 = Self::zero(Rc::clone(&f), 1);
+        let s0 = Self::x_n(Arc::clone(&f), 0);
+        let s1 = Self::zero(Arc::clone(&f), 1);
         s.push(s0);
         s.push(s1);
-        let t0 = Self::zero(Rc::clone(&f), 1);
-        let t1 = Self::x_n(Rc::clone(&f), 0);
+        let t0 = Self::zero(Arc::clone(&f), 1);
+        let t1 = Self::x_n(Arc::clone(&f), 0);
         t.push(t0);
         t.push(t1);
         let mut i = 1;
@@ -224,6 +263,51 @@ where
         (g, u, v, a1, b1)
     }
 
+    /// Finds the shortest linear recurrence satisfied by the sequence s
+    /// with the Berlekamp-Massey algorithm
+    ///
+    /// Returns the connection polynomial C(x) = 1 + c<sub>1</sub>x + ... + c<sub>L</sub>x<sup>L</sup>
+    /// and the length L of the recurrence
+    /// s<sub>n</sub> + c<sub>1</sub>s<sub>n-1</sub> + ... + c<sub>L</sub>s<sub>n-L</sub> = 0.
+    /// c<sub>L</sub> may be zero, so L is not always the degree of C.  
+    /// See <https://en.wikipedia.org/wiki/Berlekamp%E2%80%93Massey_algorithm>
+    pub fn berlekamp_massey(field: Arc<F>, s: &[F::FieldElement]) -> (Self, usize) {
+        let f = field.as_ref();
+        let mut c = vec![f.one()];
+        let mut b = vec![f.one()];
+        let mut l = 0;
+        let mut m = 1;
+        let mut b_disc = f.one();
+        for n in 0..s.len() {
+            let mut d = s[n];
+            for i in 1..=l.min(c.len() - 1) {
+                d = f.add(d, f.mul(c[i], s[n - i]));
+            }
+            if d == f.zero() {
+                m += 1;
+                continue;
+            }
+            // C(x) = C(x) - d / b x^m B(x)
+            let coef = f.mul(d, f.inv(b_disc).unwrap());
+            let prev = c.clone();
+            if c.len() < b.len() + m {
+                c.resize(b.len() + m, f.zero());
+            }
+            for (i, &bi) in b.iter().enumerate() {
+                c[i + m] = f.sub(c[i + m], f.mul(coef, bi));
+            }
+            if 2 * l <= n {
+                l = n + 1 - l;
+                b = prev;
+                b_disc = d;
+                m = 1;
+            } else {
+                m += 1;
+            }
+        }
+        (Self::new(field, c), l)
+    }
+
     pub fn inverse_modulo(&self, modulus: &Self) -> Self {
         if self.is_zero() {
             panic!("The null polynom has no inverse");
@@ -240,4 +324,8 @@ where
 
 pub mod characteristic_two;
 pub mod io;
+pub mod roots;
 pub mod traits;
+
+#[cfg(feature = "serde")]
+mod serde;
@@ -0,0 +1,323 @@
+//! Root finding for polynomials on F<sub>2<sup>m</sup></sub>
+//!
+//! The decoders of Goppa codes end by looking for the roots of the error locator polynomial
+//! among the elements of the Goppa set. Four algorithms are available:
+//! - [`Horner`]: evaluates the polynomial at every point by Horner's rule,
+//!   in O(nt) operations for n points and a polynomial of degree t
+//! - [`Multipoint`]: evaluates the polynomial at every point through a subproduct tree
+//! - [`BerlekampTrace`]: splits the polynomial with the trace map
+//!   and only depends on the degree of the polynomial, not on the number of points
+//! - [`AdditiveFft`]: evaluates the polynomial at every element of the field
+//!   with the additive FFT of Gao and Mateer, in O(2<sup>m</sup> m) operations
+//!   after a cheap Taylor expansion; best when the points make the whole field
+//!
+//! [`Horner`]: enum.RootFinding.html#variant.Horner
+//! [`Multipoint`]: enum.RootFinding.html#variant.Multipoint
+//! [`BerlekampTrace`]: enum.RootFinding.html#variant.BerlekampTrace
+//! [`AdditiveFft`]: enum.RootFinding.html#variant.AdditiveFft
+
+use std::sync::Arc;
+
+use super::Poly;
+use crate::finite_field::{F2FiniteExtension, Field};
+
+/// Algorithm finding which points are roots of a polynomial
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RootFinding {
+    /// Horner evaluation at every point
+    Horner,
+    /// Multipoint evaluation through a subproduct tree
+    Multipoint,
+    /// Berlekamp trace algorithm
+    BerlekampTrace,
+    /// Gao-Mateer additive FFT on the whole field
+    AdditiveFft,
+}
+
+impl RootFinding {
+    /// Picks the additive FFT if the points make the whole field, and Horner otherwise
+    pub fn for_points(points: usize, order: usize) -> Self {
+        if points == order {
+            RootFinding::AdditiveFft
+        } else {
+            RootFinding::Horner
+        }
+    }
+}
+
+impl<F> Poly<F>
+where
+    F: Field,
+{
+    /// Evaluates the polynomial at every point
+    ///
+    /// The polynomial is reduced down a tree whose leaves are the polynomials x - a
+    /// for every point a and whose nodes are the products of their children.
+    /// Nodes of degree greater than the polynomial do not reduce it and are not computed.
+    pub fn eval_multipoint(&self, points: &[F::FieldElement]) -> Vec<F::FieldElement> {
+        let f = self.field();
+        if points.is_empty() {
+            return Vec::new();
+        }
+
+        // Build the tree from the leaves up while its nodes may reduce the polynomial
+        let degree = self.degree();
+        let mut tree = vec![points
+            .iter()
+            .map(|&a| Self::new(Arc::clone(&f), vec![f.neg(a), f.one()]))
+            .collect::<Vec<Self>>()];
+        let mut width = 1;
+        while width <= degree && tree[tree.len() - 1].len() > 1 {
+            let next = tree[tree.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => a * b,
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            tree.push(next);
+            width *= 2;
+        }
+
+        // Reduce the polynomial from the top nodes down to the leaves
+        let mut rems: Vec<Self> = tree[tree.len() - 1]
+            .iter()
+            .map(|node| {
+                let mut rem = self.clone();
+                rem.modulo(node);
+                rem
+            })
+            .collect();
+        for level in tree.iter().rev().skip(1) {
+            rems = level
+                .iter()
+                .enumerate()
+                .map(|(i, node)| {
+                    let mut rem = rems[i / 2].clone();
+                    rem.modulo(node);
+                    rem
+                })
+                .collect();
+        }
+        rems.iter().map(|rem| rem[0]).collect()
+    }
+}
+
+impl<F> Poly<F>
+where
+    F: F2FiniteExtension,
+{
+    /// Tells for every point whether it is a root of the polynomial
+    ///
+    /// Every point is a root of the null polynomial.
+    pub fn find_roots(&self, points: &[F::FieldElement], method: RootFinding) -> Vec<bool> {
+        let f = self.field();
+        if self.is_zero() {
+            return vec![true; points.len()];
+        }
+        match method {
+            RootFinding::Horner => points.iter().map(|&a| self.eval(a) == f.zero()).collect(),
+            RootFinding::Multipoint => self
+                .eval_multipoint(points)
+                .iter()
+                .map(|&v| v == f.zero())
+                .collect(),
+            RootFinding::BerlekampTrace => {
+                let mut is_root = vec![false; f.order()];
+                for root in self.roots() {
+                    is_root[f.elt_to_u32(root) as usize] = true;
+                }
+                points
+                    .iter()
+                    .map(|&a| is_root[f.elt_to_u32(a) as usize])
+                    .collect()
+            }
+            RootFinding::AdditiveFft => {
+                let evals = self.eval_field();
+                points
+                    .iter()
+                    .map(|&a| evals[f.elt_to_u32(a) as usize] == f.zero())
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the distinct roots of the polynomial in the field
+    /// with the Berlekamp trace algorithm
+    ///
+    /// The polynomial is first replaced by its gcd with x<sup>q</sup> - x,
+    /// the product of its distinct linear factors.
+    /// It is then split by its gcd with Tr(&beta;x) for every element &beta;
+    /// of a basis of F<sub>q</sub> over F<sub>2</sub>, where Tr is the trace map
+    /// x + x<sup>2</sup> + ... + x<sup>2<sup>m-1</sup></sup>.
+    /// Roots are returned in no particular order.
+    /// Every element of the field is a root of the null polynomial.
+    pub fn roots(&self) -> Vec<F::FieldElement> {
+        let f = self.field();
+        if self.is_zero() {
+            return f.to_vec();
+        }
+        if self.degree() == 0 {
+            return Vec::new();
+        }
+
+        let m = f.characteristic_exponent() as usize;
+        let x = Self::x_n(Arc::clone(&f), 1);
+        let mut x_q = x.clone();
+        for _i in 0..m {
+            x_q.square();
+            x_q.modulo(self);
+        }
+        let split = Self::gcd(self, &(x_q - &x));
+
+        let basis: Vec<F::FieldElement> = (0..m).map(|i| f.u32_to_elt(1 << i)).collect();
+        let mut roots = Vec::with_capacity(split.degree());
+        split.trace_split(&basis, &mut roots);
+        roots
+    }
+
+    /// Finds the roots of a product of distinct linear factors
+    /// by splitting it along the elements of the basis
+    fn trace_split(&self, basis: &[F::FieldElement], roots: &mut Vec<F::FieldElement>) {
+        let f = self.field();
+        let degree = self.degree();
+        if degree == 0 {
+            return;
+        }
+        if degree == 1 {
+            roots.push(f.mul(self[0], f.inv(self[1]).unwrap()));
+            return;
+        }
+        let (&beta, basis) = match basis.split_first() {
+            Some(split) => split,
+            None => panic!("Polynomial is not a product of distinct linear factors"),
+        };
+
+        let mut beta_x = Self::new(Arc::clone(&f), vec![f.zero(), beta]);
+        beta_x.modulo(self);
+        let mut trace = beta_x.clone();
+        for _i in 1..f.characteristic_exponent() {
+            beta_x.square();
+            beta_x.modulo(self);
+            trace += &beta_x;
+        }
+        let g = Self::gcd(self, &trace);
+        let g_degree = g.degree();
+        if g_degree == 0 || g_degree == degree {
+            self.trace_split(basis, roots);
+        } else {
+            let (h, _) = Self::euclidean_division(self, &g);
+            g.trace_split(basis, roots);
+            h.trace_split(basis, roots);
+        }
+    }
+
+    /// Evaluates the polynomial at every element of the field
+    /// with the additive FFT of Gao and Mateer
+    ///
+    /// The value at element a is at index `elt_to_u32(a)`.
+    /// See Gao, S., & Mateer, T. (2010), Additive fast Fourier transforms over finite fields.
+    /// IEEE Transactions on Information Theory, 56(12), 6265-6272.
+    pub fn eval_field(&self) -> Vec<F::FieldElement> {
+        let f = self.field();
+        let m = f.characteristic_exponent();
+        // Element a = sum of the 2^i such that bit i of elt_to_u32(a) is set,
+        // which is the order in which the FFT enumerates the subspace spanned by the 2^i
+        let basis: Vec<F::FieldElement> = (0..m).map(|i| f.u32_to_elt(1 << i)).collect();
+        let coeffs = self.data[..self.degree() + 1].to_vec();
+        fft(f.as_ref(), coeffs, &basis)
+    }
+}
+
+/// Evaluates the polynomial of coefficients coeffs at every element
+/// of the subspace spanned by basis
+///
+/// The value at the sum of the basis[i] for which bit i of k is set is at index k.
+fn fft<F>(f: &F, coeffs: Vec<F::FieldElement>, basis: &[F::FieldElement]) -> Vec<F::FieldElement>
+where
+    F: F2FiniteExtension,
+{
+    let size = 1 << basis.len();
+    if coeffs.len() == 1 {
+        return vec![coeffs[0]; size];
+    }
+    let horner = |a| {
+        coeffs
+            .iter()
+            .rev()
+            .fold(f.zero(), |e, &c| f.add(f.mul(e, a), c))
+    };
+    if basis.len() == 1 {
+        return vec![coeffs[0], horner(basis[0])];
+    }
+
+    // g(x) = f(beta x) where beta is the last basis element
+    let (&beta, gammas) = basis.split_last().unwrap();
+    let beta_inv = f.inv(beta).unwrap();
+    let mut g = coeffs;
+    let mut power = f.one();
+    for c in g.iter_mut() {
+        *c = f.mul(*c, power);
+        power = f.mul(power, beta);
+    }
+
+    // Taylor expansion g(x) = g0(x^2 + x) + x g1(x^2 + x)
+    let (g0, g1) = taylor_expansion(f, g);
+
+    // g(y) for y in span(gamma) and y + 1 only needs g0 and g1 at y^2 + y = sum of the deltas
+    let gammas: Vec<F::FieldElement> = gammas.iter().map(|&b| f.mul(b, beta_inv)).collect();
+    let deltas: Vec<F::FieldElement> = gammas.iter().map(|&c| f.add(f.mul(c, c), c)).collect();
+    let u = fft(f, g0, &deltas);
+    let v = fft(f, g1, &deltas);
+
+    let half = size / 2;
+    let mut evals = vec![f.zero(); size];
+    for k in 0..half {
+        let y = span_element(f, &gammas, k);
+        evals[k] = f.add(u[k], f.mul(y, v[k]));
+        evals[k + half] = f.add(evals[k], v[k]);
+    }
+    evals
+}
+
+/// Returns the sum of the basis[i] for which bit i of k is set
+fn span_element<F>(f: &F, basis: &[F::FieldElement], k: usize) -> F::FieldElement
+where
+    F: F2FiniteExtension,
+{
+    basis
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (k >> i) & 1 == 1)
+        .fold(f.zero(), |sum, (_, &b)| f.add(sum, b))
+}
+
+/// Writes g(x) as g0(x<sup>2</sup> + x) + x g1(x<sup>2</sup> + x)
+///
+/// Returns the coefficients of g0 and g1, at least one of each.
+fn taylor_expansion<F>(
+    f: &F,
+    mut g: Vec<F::FieldElement>,
+) -> (Vec<F::FieldElement>, Vec<F::FieldElement>)
+where
+    F: F2FiniteExtension,
+{
+    let len = crate::div_ceil(g.len(), 2);
+    let mut g0 = Vec::with_capacity(len);
+    let mut g1 = Vec::with_capacity(len);
+    // Divide repeatedly by x^2 + x, the remainders giving the coefficients
+    while g.len() > 2 {
+        for i in (2..g.len()).rev() {
+            let c = g[i];
+            // Subtract c x^(i-2) (x^2 + x): c stays in g[i] as coefficient of the quotient
+            g[i - 1] = f.add(g[i - 1], c);
+        }
+        g0.push(g[0]);
+        g1.push(g[1]);
+        g.drain(..2);
+    }
+    g0.push(g[0]);
+    g1.push(*g.get(1).unwrap_or(&f.zero()));
+    (g0, g1)
+}
@@ -0,0 +1,71 @@
+//! Serialization of polynomials with serde
+//!
+//! A polynomial is serialized as its field and its coefficients by increasing degree.
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+use super::Poly;
+use crate::finite_field::{serde as field_serde, DeserializeWithField, SerdeField};
+
+#[derive(Deserialize, Serialize)]
+struct PolyRepr<R> {
+    field: R,
+    data: Vec<u32>,
+}
+
+impl<F> Poly<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_on<'de, D>(field: Option<Arc<F>>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = PolyRepr::<F::Repr>::deserialize(deserializer)?;
+        let field = field_serde::field(repr.field, field).map_err(D::Error::custom)?;
+        let data =
+            field_serde::repr_to_elts(field.as_ref(), &repr.data).map_err(D::Error::custom)?;
+        Poly::try_new(field, data).map_err(D::Error::custom)
+    }
+}
+
+impl<F> Serialize for Poly<F>
+where
+    F: SerdeField,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        PolyRepr {
+            field: self.field.to_repr(),
+            data: field_serde::elts_to_repr(self.field.as_ref(), &self.data),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, F> Deserialize<'de> for Poly<F>
+where
+    F: SerdeField,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(None, deserializer)
+    }
+}
+
+impl<'de, F> DeserializeWithField<'de, F> for Poly<F>
+where
+    F: SerdeField,
+{
+    fn deserialize_with_field<D>(field: Arc<F>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_on(Some(field), deserializer)
+    }
+}
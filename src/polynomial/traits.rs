@@ -2,7 +2,7 @@ use std::{
     cmp,
     fmt::{Debug, Display, Formatter, Result},
     ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
-    rc::Rc,
+    sync::Arc,
 };
 
 use super::Poly;
@@ -31,7 +31,7 @@ where
 {
     fn clone(&self) -> Self {
         Poly {
-            field: Rc::clone(&self.field),
+            field: Arc::clone(&self.field),
             data: self.data.clone(),
         }
     }
@@ -100,6 +100,9 @@ where
     F: Field,
 {
     fn add_assign(&mut self, other: &Self) {
+        if self.field != other.field {
+            panic!("Cannot add polynomials: fields don't match");
+        }
         self.data.resize(
             1 + cmp::max(self.degree(), other.degree()),
             self.field.zero(),
@@ -175,6 +178,9 @@ where
     F: Field,
 {
     fn sub_assign(&mut self, other: &Self) {
+        if self.field != other.field {
+            panic!("Cannot substract polynomials: fields don't match");
+        }
         self.data.resize(
             1 + cmp::max(self.degree(), other.degree()),
             self.field.zero(),
@@ -228,8 +234,11 @@ where
     type Output = Poly<F>;
 
     fn mul(self, other: Self) -> Self::Output {
+        if self.field != other.field {
+            panic!("Cannot multiply polynomials: fields don't match");
+        }
         let f = self.field();
-        let mut prod = Poly::zero(Rc::clone(&f), self.degree() + other.degree() + 1);
+        let mut prod = Poly::zero(Arc::clone(&f), self.degree() + other.degree() + 1);
 
         for i in 0..self.degree() + 1 {
             for j in 0..other.degree() + 1 {
@@ -256,6 +265,9 @@ where
     F: Field,
 {
     fn mul_assign(&mut self, other: &Self) {
+        if self.field != other.field {
+            panic!("Cannot multiply polynomials: fields don't match");
+        }
         let tmp = self.clone();
         let f = tmp.field();
         self.data.iter_mut().map(|x| *x = f.zero()).count();
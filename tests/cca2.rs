@@ -0,0 +1,66 @@
+use log::info;
+
+use mceliece::{crypto::*, matrix::RowVec};
+
+pub mod common;
+
+#[test]
+fn cca2_encrypt_decrypt() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let msg = b"The quick brown fox jumps over the lazy dog".to_vec();
+    let cpt = pk.encrypt_cca2(&msg);
+    let dmsg = sk.decrypt_cca2(&cpt).unwrap();
+    assert_eq!(dmsg, msg);
+}
+
+#[test]
+fn cca2_implicit_rejection() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let msg = b"The quick brown fox jumps over the lazy dog".to_vec();
+    let mut cpt = pk.encrypt_cca2(&msg);
+    let last = cpt.len() - 1;
+    cpt[last] ^= 1;
+    let dmsg = sk.decrypt_cca2(&cpt).unwrap();
+    assert_eq!(dmsg.len(), msg.len());
+    assert_ne!(dmsg, msg);
+    assert_eq!(sk.decrypt_cca2(&cpt).unwrap(), dmsg);
+}
+
+#[test]
+fn cca2_tampered_codeword() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let msg = b"The quick brown fox jumps over the lazy dog".to_vec();
+    let cpt = pk.encrypt_cca2(&msg);
+    let (read, mut c1) = RowVec::from_bytes(&cpt).unwrap();
+    let sigma = sk.decrypt(&c1);
+    let e = &c1 + &sigma * &pk.sgp();
+    // Flip a bit of the codeword outside the error positions
+    let i = (0..n).find(|&i| e[i] == 0).unwrap();
+    c1[i] ^= 1;
+    let mut tampered = c1.to_bytes();
+    tampered.extend_from_slice(&cpt[read..]);
+    let dmsg = sk.decrypt_cca2(&tampered).unwrap();
+    assert_eq!(dmsg.len(), msg.len());
+    assert_ne!(dmsg, msg);
+}
+
+#[test]
+fn cca2_short_ciphertext() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (_, sk) = keygen(n, t);
+    assert!(matches!(
+        sk.decrypt_cca2(&[0, 0]),
+        Err(mceliece::Error::Truncated(_))
+    ));
+}
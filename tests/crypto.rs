@@ -1,7 +1,20 @@
 use log::{info, warn};
-use std::rc::Rc;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use std::sync::Arc;
 
-use mceliece::{crypto::*, finite_field::*, matrix::*};
+use mceliece::{
+    crypto::{
+        container::{self, ObjectType},
+        passphrase::KdfParams,
+        seed::KeySeed,
+        *,
+    },
+    finite_field::*,
+    goppa::decoder::BerlekampMassey,
+    matrix::*,
+    polynomial::RootFinding,
+};
 
 pub mod common;
 
@@ -13,12 +26,52 @@ fn crypto_keygen() {
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
     let (pk, sk) = keygen(n, t);
-    let f2 = Rc::new(F2::generate(()));
-    let g = sk.goppa().generator_matrix(f2);
+    let g = sk.goppa().generator_matrix();
     assert!(sk.s().is_invertible());
     assert_eq!(g.rank(), g.rows());
     assert!(sk.p().is_permutation());
-    assert_eq!(*pk.sgp(), sk.s() * g * sk.p());
+    assert!(pk.sgp().is_standard_form());
+    assert_eq!(pk.sgp(), sk.s() * g * sk.p());
+}
+
+#[test]
+fn crypto_keygen_from_seed() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let seed = [7; SEED_LEN];
+    let (pk1, sk1) = keygen_from_seed(n, t, &seed);
+    let (pk2, sk2) = keygen_from_seed(n, t, &seed);
+    assert_eq!(pk1, pk2);
+    assert!(sk1 == sk2);
+
+    // Tiny codes may have a single key pair: compare seeds on a larger code
+    let (pk1, _) = keygen_from_seed(64, 4, &seed);
+    let (pk3, _) = keygen_from_seed(64, 4, &[8; SEED_LEN]);
+    assert_ne!(pk1, pk3);
+}
+
+#[test]
+fn crypto_key_seed_write_read() {
+    let params = ParameterSet::from_name("toy64").unwrap();
+    let seed = KeySeed::new(params, [16; SEED_LEN]);
+    let (_, sk) = params.keygen_from_seed(&[16; SEED_LEN]);
+    let file_name = "seed_write_read_test.mce";
+    seed.write(file_name).unwrap();
+    assert!(KeySeed::read_seed(file_name).unwrap() == seed);
+    assert!(SecretKey::read_secret_key(file_name).unwrap() == sk);
+
+    let mut buf = Vec::new();
+    seed.write_armored_to(&mut buf).unwrap();
+    assert!(SecretKey::read_from(buf.as_slice()).unwrap() == sk);
+
+    // t = 0 does not define a code
+    let mut bytes = seed.to_bytes();
+    bytes[4..8].copy_from_slice(&[0; 4]);
+    assert!(matches!(
+        KeySeed::from_bytes(&bytes),
+        Err(mceliece::Error::InvalidParameters(_))
+    ));
 }
 
 #[test]
@@ -45,16 +98,313 @@ fn crypto_sk_write_read() {
     assert!(sk == sk_read);
 }
 
+#[test]
+fn crypto_write_to_read_from() {
+    let (pk, sk) = keygen_from_seed(64, 4, &[15; SEED_LEN]);
+    let mut buf = Vec::new();
+    pk.write_to(&mut buf).unwrap();
+    assert_eq!(PublicKey::read_from(buf.as_slice()).unwrap(), pk);
+    assert_eq!(
+        PublicKey::read_params_from(buf.as_slice()).unwrap(),
+        pk.params()
+    );
+
+    let mut buf = Vec::new();
+    sk.write_armored_to(&mut buf).unwrap();
+    assert!(SecretKey::read_from(buf.as_slice()).unwrap() == sk);
+
+    let f2 = Arc::new(F2::generate(()));
+    let c = pk.encrypt(&RowVec::random(Arc::clone(&f2), pk.k()));
+    let mut buf = Vec::new();
+    container::write_vector_to(&mut buf, ObjectType::Ciphertext, &pk.params(), &c).unwrap();
+    let c_read =
+        container::read_vector_from(buf.as_slice(), ObjectType::Ciphertext, &sk.params()).unwrap();
+    assert!(c_read == c);
+
+    // Raw vectors are read one after the other
+    let u = RowVec::random(Arc::clone(&f2), 13);
+    let v = RowVec::random(f2, 70);
+    let mut buf = Vec::new();
+    u.write_to(&mut buf).unwrap();
+    v.write_to(&mut buf).unwrap();
+    let mut reader = buf.as_slice();
+    assert!(RowVec::read_from(&mut reader).unwrap() == u);
+    assert!(RowVec::read_from(&mut reader).unwrap() == v);
+    assert!(matches!(
+        RowVec::read_from(&mut reader),
+        Err(mceliece::Error::Truncated(_))
+    ));
+}
+
+#[test]
+fn crypto_container() {
+    let (pk, sk) = keygen_from_seed(64, 4, &[12; SEED_LEN]);
+    let pk_file = "container_pk_write_read_test.mce";
+    let sk_file = "container_sk_write_read_test.mce";
+    let c_file = "container_c_write_read_test.mce";
+    pk.write(pk_file).unwrap();
+    sk.write(sk_file).unwrap();
+    let params = PublicKey::read_params(pk_file).unwrap();
+    assert_eq!(params, pk.params());
+    assert!(params.matches(&sk.params()));
+
+    let f2 = Arc::new(F2::generate(()));
+    let c = pk.encrypt(&RowVec::random(f2, pk.k()));
+    container::write_vector(c_file, ObjectType::Ciphertext, &pk.params(), &c).unwrap();
+    let c_read = container::read_vector(c_file, ObjectType::Ciphertext, &sk.params()).unwrap();
+    assert!(c_read == c);
+
+    // A key is not a ciphertext
+    assert!(matches!(
+        container::read_vector(pk_file, ObjectType::Ciphertext, &sk.params()),
+        Err(mceliece::Error::UnexpectedObject { .. })
+    ));
+
+    // Corruption is detected
+    let mut bytes = container::seal(ObjectType::PublicKey, &pk.params(), &pk.to_bytes());
+    assert_eq!(&bytes[..4], &container::MAGIC);
+    bytes[30] ^= 1;
+    assert!(matches!(
+        container::open(&bytes, ObjectType::PublicKey),
+        Err(mceliece::Error::ChecksumMismatch)
+    ));
+
+    // Nothing may follow the checksum
+    let mut bytes = container::seal(ObjectType::PublicKey, &pk.params(), &pk.to_bytes());
+    bytes.push(0);
+    assert!(matches!(
+        container::open(&bytes, ObjectType::PublicKey),
+        Err(mceliece::Error::Malformed(_))
+    ));
+
+    // The field polynomial of the header must be the one of the key
+    let mut params = sk.params();
+    assert_ne!(params.poly, 0);
+    params.poly ^= 0b110;
+    let bytes = container::seal(ObjectType::SecretKey, &params, &sk.to_bytes());
+    assert!(matches!(
+        SecretKey::read_from(bytes.as_slice()),
+        Err(mceliece::Error::Malformed(_))
+    ));
+    let mut buf = Vec::new();
+    container::write_vector_to(&mut buf, ObjectType::Ciphertext, &params, &c).unwrap();
+    assert!(matches!(
+        container::read_vector_from(buf.as_slice(), ObjectType::Ciphertext, &sk.params()),
+        Err(mceliece::Error::Malformed(_))
+    ));
+
+    // Legacy files hold the bare encoding
+    std::fs::write(sk_file, sk.to_bytes()).unwrap();
+    assert!(SecretKey::read_secret_key(sk_file).unwrap() == sk);
+    std::fs::write(pk_file, pk.to_bytes()).unwrap();
+    assert_eq!(PublicKey::read_code_dimension(pk_file).unwrap(), pk.k());
+}
+
+#[test]
+fn crypto_armor() {
+    let (pk, sk) = keygen_from_seed(64, 4, &[13; SEED_LEN]);
+    let text = pk.to_armor();
+    assert!(text.starts_with("-----BEGIN MCELIECE PUBLIC KEY-----\n"));
+    assert!(text.lines().all(|line| line.len() <= 64));
+    assert_eq!(PublicKey::from_armor(&text).unwrap(), pk);
+    assert!(matches!(
+        SecretKey::from_armor(&text),
+        Err(mceliece::Error::UnexpectedObject { .. })
+    ));
+
+    // Armored files are recognized when read
+    let sk_file = "armor_sk_write_read_test.mce";
+    let c_file = "armor_c_write_read_test.mce";
+    sk.write_armored(sk_file).unwrap();
+    assert!(SecretKey::read_secret_key(sk_file).unwrap() == sk);
+    let f2 = Arc::new(F2::generate(()));
+    let c = pk.encrypt(&RowVec::random(f2, pk.k()));
+    container::write_vector_armored(c_file, ObjectType::Ciphertext, &pk.params(), &c).unwrap();
+    assert!(container::read_vector(c_file, ObjectType::Ciphertext, &sk.params()).unwrap() == c);
+
+    // Headers must match the content
+    let forged = text.replace("n=64", "n=128");
+    assert!(PublicKey::from_armor(&forged).is_err());
+}
+
+#[test]
+fn crypto_passphrase() {
+    let (_pk, sk) = keygen_from_seed(64, 4, &[14; SEED_LEN]);
+    let kdf = KdfParams {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+    let mut buf = Vec::new();
+    sk.write_encrypted_to(&mut buf, b"correct horse", &kdf)
+        .unwrap();
+    assert!(SecretKey::read_from_with_passphrase(buf.as_slice(), b"correct horse").unwrap() == sk);
+    assert!(matches!(
+        SecretKey::read_from(buf.as_slice()),
+        Err(mceliece::Error::PassphraseRequired)
+    ));
+    assert!(matches!(
+        SecretKey::read_from_with_passphrase(buf.as_slice(), b"battery staple"),
+        Err(mceliece::Error::WrongPassphrase)
+    ));
+
+    // Armored encrypted keys are recognized when read
+    let mut buf = Vec::new();
+    sk.write_encrypted_armored_to(&mut buf, b"correct horse", &kdf)
+        .unwrap();
+    assert!(buf.starts_with(b"-----BEGIN MCELIECE ENCRYPTED SECRET KEY-----\n"));
+    assert!(SecretKey::read_from_with_passphrase(buf.as_slice(), b"correct horse").unwrap() == sk);
+
+    // Unencrypted keys are read whatever the passphrase
+    let mut buf = Vec::new();
+    sk.write_to(&mut buf).unwrap();
+    assert!(SecretKey::read_from_with_passphrase(buf.as_slice(), b"unused").unwrap() == sk);
+
+    // The prompt is only called for encrypted keys
+    let sk_read =
+        SecretKey::read_from_with_prompt(buf.as_slice(), || -> Result<&[u8], mceliece::Error> {
+            panic!("Prompt called for an unencrypted key")
+        });
+    assert!(sk_read.unwrap() == sk);
+    let mut buf = Vec::new();
+    sk.write_encrypted_to(&mut buf, b"correct horse", &kdf)
+        .unwrap();
+    let sk_read = SecretKey::read_from_with_prompt(buf.as_slice(), || {
+        Ok::<_, mceliece::Error>("correct horse")
+    });
+    assert!(sk_read.unwrap() == sk);
+
+    // Salt and nonce drawn from the same rng give the same file
+    let mut buf1 = Vec::new();
+    let mut buf2 = Vec::new();
+    sk.write_encrypted_to_with_rng(
+        &mut ChaCha20Rng::from_seed([15; 32]),
+        &mut buf1,
+        b"correct horse",
+        &kdf,
+    )
+    .unwrap();
+    sk.write_encrypted_to_with_rng(
+        &mut ChaCha20Rng::from_seed([15; 32]),
+        &mut buf2,
+        b"correct horse",
+        &kdf,
+    )
+    .unwrap();
+    assert_eq!(buf1, buf2);
+    assert!(SecretKey::read_from_with_passphrase(buf1.as_slice(), b"correct horse").unwrap() == sk);
+    let mut buf = Vec::new();
+    sk.write_encrypted_armored_to_with_rng(
+        &mut ChaCha20Rng::from_seed([15; 32]),
+        &mut buf,
+        b"correct horse",
+        &kdf,
+    )
+    .unwrap();
+    assert!(SecretKey::read_from_with_passphrase(buf.as_slice(), b"correct horse").unwrap() == sk);
+}
+
+#[test]
+fn crypto_passphrase_costs() {
+    let (_pk, sk) = keygen_from_seed(64, 4, &[18; SEED_LEN]);
+    let kdf = KdfParams {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+    let mut buf = Vec::new();
+    sk.write_encrypted_to(&mut buf, b"correct horse", &kdf)
+        .unwrap();
+
+    // Costs tampered with are rejected before the key is derived
+    let (params, payload) = container::open(&buf, ObjectType::EncryptedSecretKey).unwrap();
+    let mut payload = payload.to_vec();
+    payload[..4].copy_from_slice(&u32::MAX.to_be_bytes());
+    let tampered = container::seal(ObjectType::EncryptedSecretKey, &params, &payload);
+    assert!(matches!(
+        SecretKey::read_from_with_passphrase(tampered.as_slice(), b"correct horse"),
+        Err(mceliece::Error::Malformed(_))
+    ));
+
+    // and so are costs above the maximum when writing
+    let kdf = KdfParams {
+        t_cost: KdfParams::MAX.t_cost + 1,
+        ..kdf
+    };
+    assert!(matches!(
+        sk.write_encrypted_to(&mut Vec::new(), b"correct horse", &kdf),
+        Err(mceliece::Error::InvalidParameters(_))
+    ));
+}
+
+#[test]
+fn crypto_check_keypair() {
+    let (pk, sk) = keygen_from_seed(64, 4, &[16; SEED_LEN]);
+    assert_eq!(sk.public_key(), pk);
+    check_keypair(&pk, &sk).unwrap();
+
+    let (pk2, _) = keygen_from_seed(64, 4, &[17; SEED_LEN]);
+    assert!(matches!(
+        check_keypair(&pk2, &sk),
+        Err(mceliece::Error::KeyMismatch)
+    ));
+}
+
+#[test]
+fn crypto_from_bytes_malformed() {
+    let (pk, sk) = keygen_from_seed(64, 4, &[11; SEED_LEN]);
+
+    // Every truncation of a key is rejected without panicking
+    let pk_bytes = pk.to_bytes();
+    for len in 0..pk_bytes.len() {
+        assert!(PublicKey::from_bytes(&pk_bytes[..len]).is_err());
+    }
+    let sk_bytes = sk.to_bytes();
+    for len in 0..sk_bytes.len() {
+        assert!(SecretKey::from_bytes(&sk_bytes[..len]).is_err());
+    }
+
+    // So are corrupted keys: a permutation with a repeated image here
+    let mut bytes = sk_bytes.clone();
+    let p_start = bytes.len() - 32 - 4 * 64;
+    let image = bytes[p_start..p_start + 4].to_vec();
+    bytes[p_start + 4..p_start + 8].copy_from_slice(&image);
+    assert!(matches!(
+        SecretKey::from_bytes(&bytes),
+        Err(mceliece::Error::InvalidParameters(_))
+    ));
+
+    // or a matrix S = 0, which could not decrypt
+    let mut bytes = sk_bytes.clone();
+    let s_len = sk.s().to_bytes().len();
+    for byte in &mut bytes[4 + 8..4 + s_len] {
+        *byte = 0;
+    }
+    assert!(matches!(
+        SecretKey::from_bytes(&bytes),
+        Err(mceliece::Error::Malformed(_))
+    ));
+
+    // or a leading field order which is not the one of the Goppa code
+    let mut bytes = sk_bytes.clone();
+    bytes[..4].copy_from_slice(&128u32.to_be_bytes());
+    assert!(matches!(
+        SecretKey::from_bytes(&bytes),
+        Err(mceliece::Error::Malformed(_))
+    ));
+    assert!(SecretKey::from_bytes(&sk_bytes).is_ok());
+}
+
 #[test]
 fn crypto_decrypt_null_ciphertext() {
     let (q, n, t) = common::goppa_setup();
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
-    let f2 = Rc::new(F2::generate(()));
+    let f2 = Arc::new(F2::generate(()));
     let (pk, sk) = keygen(n, t);
-    let k = pk.sgp().rows();
-    let msg = RowVec::zero(Rc::clone(&f2), k);
-    let cpt = RowVec::zero(Rc::clone(&f2), n);
+    let k = pk.k();
+    let msg = RowVec::zero(Arc::clone(&f2), k);
+    let cpt = RowVec::zero(Arc::clone(&f2), n);
     let dmsg = sk.decrypt(&cpt);
     assert_eq!(dmsg, msg);
 }
@@ -64,11 +414,11 @@ fn crypto_decrypt_codeword() {
     let (q, n, t) = common::goppa_setup();
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
-    let f2 = Rc::new(F2::generate(()));
+    let f2 = Arc::new(F2::generate(()));
     let (pk, sk) = keygen(n, t);
-    let k = pk.sgp().rows();
+    let k = pk.k();
     let msg = RowVec::random(f2, k);
-    let cpt = &msg * pk.sgp();
+    let cpt = &msg * &pk.sgp();
     let dmsg = sk.decrypt(&cpt);
     assert_eq!(dmsg, msg);
 }
@@ -78,9 +428,9 @@ fn crypto_encrypt_decrypt_null_message() {
     let (q, n, t) = common::goppa_setup();
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
-    let f2 = Rc::new(F2::generate(()));
+    let f2 = Arc::new(F2::generate(()));
     let (pk, sk) = keygen(n, t);
-    let k = pk.sgp().rows();
+    let k = pk.k();
     let msg = RowVec::zero(f2, k);
     let cpt = pk.encrypt(&msg);
     assert_eq!(cpt.weight(), t);
@@ -94,15 +444,73 @@ fn crypto_encrypt_decrypt_random_message() {
     let (q, n, t) = common::goppa_setup();
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
-    let f2 = Rc::new(F2::generate(()));
+    let f2 = Arc::new(F2::generate(()));
     let (pk, sk) = keygen(n, t);
-    let k = pk.sgp().rows();
+    let k = pk.k();
     let msg = RowVec::random(f2, k);
     let cpt = pk.encrypt(&msg);
     let dmsg = sk.decrypt(&cpt);
     assert_eq!(dmsg, msg);
 }
 
+#[test]
+fn crypto_decryption_context() {
+    let (pk, sk) = keygen_from_seed(256, 8, &[18; SEED_LEN]);
+    let ctx = sk.context();
+    assert!(ctx.s_inv() * sk.s() == BitMat::identity(pk.k()));
+    assert!(ctx.p_inv() * sk.p() == Perm::identity(pk.n()));
+    assert!(*ctx.xyz() == sk.goppa().parity_check_xyz());
+    assert_eq!(ctx.g_inv().len(), pk.n());
+    assert_eq!(ctx.root_finding(), sk.goppa().default_root_finding());
+    let f2 = Arc::new(F2::generate(()));
+    for _ in 0..REPEAT {
+        let m = RowVec::random(Arc::clone(&f2), pk.k());
+        let c = pk.encrypt(&m);
+        let m_dec = ctx.decrypt(&c);
+        assert!(m_dec == m);
+        assert!(m_dec == sk.decrypt(&c));
+        assert!(ctx.try_decrypt(&c).unwrap() == m);
+    }
+}
+
+#[test]
+fn crypto_decryption_context_with_decoder() {
+    let (pk, sk) = keygen_from_seed(256, 8, &[19; SEED_LEN]);
+    let ctx = sk
+        .context_with_decoder(Box::new(BerlekampMassey::new()))
+        .with_root_finding(RootFinding::BerlekampTrace);
+    assert_eq!(ctx.root_finding(), RootFinding::BerlekampTrace);
+    let f2 = Arc::new(F2::generate(()));
+    for _ in 0..REPEAT {
+        let m = RowVec::random(Arc::clone(&f2), pk.k());
+        let c = pk.encrypt(&m);
+        assert!(ctx.try_decrypt(&c).unwrap() == m);
+        assert_eq!(ctx.decrypt(&c), m);
+
+        // Beyond t errors, decryption fails or gives another message
+        let e = RowVec::random_with_weight(Arc::clone(&f2), pk.n(), pk.t() + 1);
+        let c = &m * &pk.sgp() + &e;
+        match ctx.try_decrypt(&c) {
+            Ok(m_dec) => assert!(m_dec != m),
+            Err(e) => assert!(matches!(e, mceliece::Error::DecodingFailure)),
+        }
+    }
+}
+
+#[test]
+fn crypto_encrypt_with_rng() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let mut rng = ChaCha20Rng::from_seed([1; 32]);
+    let msg = RowVec::random_with_rng(&mut rng, Arc::new(F2::generate(())), pk.k());
+    let cpt1 = pk.encrypt_with_rng(&mut ChaCha20Rng::from_seed([2; 32]), &msg);
+    let cpt2 = pk.encrypt_with_rng(&mut ChaCha20Rng::from_seed([2; 32]), &msg);
+    assert_eq!(cpt1, cpt2);
+    assert_eq!(sk.decrypt(&cpt1), msg);
+}
+
 #[test]
 fn crypto_repeat() {
     common::log_setup();
@@ -119,9 +527,9 @@ fn crypto_repeat() {
 }
 
 fn crypto_repeated(n: usize, t: usize) {
-    let f2 = Rc::new(F2::generate(()));
+    let f2 = Arc::new(F2::generate(()));
     let (pk, sk) = keygen(n, t);
-    let k = pk.sgp().rows();
+    let k = pk.k();
     let msg = RowVec::random(f2, k);
     let cpt = pk.encrypt(&msg);
     let dmsg = sk.decrypt(&cpt);
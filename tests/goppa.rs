@@ -1,7 +1,12 @@
 use log::{info, warn};
-use std::rc::Rc;
+use std::sync::Arc;
 
-use mceliece::{finite_field::*, goppa::*, matrix::*, polynomial::*};
+use mceliece::{
+    finite_field::*,
+    goppa::{decoder::*, *},
+    matrix::*,
+    polynomial::*,
+};
 
 pub mod common;
 
@@ -10,27 +15,27 @@ const REPEAT: u32 = 10;
 #[test]
 fn goppa_f8() {
     common::log_setup();
-    let f2 = Rc::new(F2::generate(()));
-    let f8 = Rc::new(F2m::generate(8));
-    let p = Poly::support(Rc::clone(&f8), &[2, 1, 0]);
+    let f2 = Arc::new(F2::generate(()));
+    let f8 = Arc::new(F2m::generate(8));
+    let p = Poly::support(Arc::clone(&f8), &[2, 1, 0]);
     let c = Goppa::new(p, [0, 1, 2, 3, 4, 5, 6, 7].to_vec());
     info!("{}", c);
 
     let x = c.parity_check_x();
-    assert_eq!(x, Mat::new(Rc::clone(&f8), 2, 2, [1, 0, 1, 1].to_vec()));
+    assert_eq!(x, Mat::new(Arc::clone(&f8), 2, 2, [1, 0, 1, 1].to_vec()));
 
     let y = c.parity_check_y();
     assert_eq!(
         y,
         Mat::new(
-            Rc::clone(&f8),
+            Arc::clone(&f8),
             2,
             8,
             [1, 1, 1, 1, 1, 1, 1, 1, 0, 1, 2, 3, 4, 5, 6, 7].to_vec()
         )
     );
 
-    let h = c.parity_check_matrix(Rc::clone(&f2));
+    let h = c.parity_check_matrix();
     info!("Parity-check matrix H:{}", h);
 
     let (g, _) = Goppa::<F2m>::generator_from_parity_check(&h);
@@ -39,13 +44,13 @@ fn goppa_f8() {
     let z = &h * &g.transpose();
     assert!(z.is_zero());
 
-    let msg = RowVec::random(Rc::clone(&f2), 2);
+    let msg = RowVec::random(Arc::clone(&f2), 2);
     info!("Message:{}", msg);
 
     let cdw = c.encode(&msg);
     info!("Codeword:{}", cdw);
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), 8, 2);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), 8, 2);
     info!("Error:{}", err);
 
     let rcv = &cdw + err;
@@ -60,24 +65,24 @@ fn goppa_f8() {
 #[test]
 fn goppa_f1024() {
     common::log_setup();
-    let f2 = Rc::new(F2::generate(()));
-    let f1024 = Rc::new(F2m::generate(1024));
+    let f2 = Arc::new(F2::generate(()));
+    let f1024 = Arc::new(F2m::generate(1024));
     let c = Goppa::<F2m>::random(f1024, 30, 2);
     info!("{}", c);
 
-    let h = c.parity_check_matrix(Rc::clone(&f2));
+    let h = c.parity_check_matrix();
     info!("Parity-check matrix H:{}", h);
 
     let (g, _) = Goppa::<F2m>::generator_from_parity_check(&h);
     assert!((&h * &g.transpose()).is_zero());
 
-    let cdw = RowVec::new(Rc::clone(&f2), g.data()[0..30].to_vec());
+    let cdw = RowVec::from(&BitVec(g.extract_rows(&[0])));
     info!("Codeword: {}", cdw);
-    let syndrome = Goppa::syndrome_from_xyz(&h, &cdw);
+    let syndrome = h.mul_transpose(&BitVec::from(&cdw));
 
     assert!(syndrome.is_zero());
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), 30, 2);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), 30, 2);
     info!("Error:{}", err);
 
     let rcv = &cdw + &err;
@@ -93,21 +98,21 @@ fn goppa_f1024() {
 fn goppa_f256() {
     common::log_setup();
     let (n, k, t) = (256, 80, 22);
-    let f2 = Rc::new(F2::generate(()));
-    let f256 = Rc::new(F2m::generate(n));
-    let mut g = Poly::support(Rc::clone(&f256), &[22, 17, 15, 12, 5]);
+    let f2 = Arc::new(F2::generate(()));
+    let f256 = Arc::new(F2m::generate(n));
+    let mut g = Poly::support(Arc::clone(&f256), &[22, 17, 15, 12, 5]);
     g[0] = f256.exp(78);
     let l = f256.to_vec();
     let c = Goppa::new(g, l);
     info!("{}", c);
 
-    let msg = RowVec::random(Rc::clone(&f2), k);
+    let msg = RowVec::random(Arc::clone(&f2), k);
     info!("Message:{}", msg);
 
     let cdw = c.encode(&msg);
     info!("Codeword:{}", cdw);
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), n, t);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), n, t);
     info!("Error:{}", err);
 
     let rcv = &cdw + err;
@@ -123,18 +128,18 @@ fn goppa_f256() {
 fn goppa_f128() {
     common::log_setup();
     let (n, k, t) = (128, 58, 10);
-    let f2 = Rc::new(F2::generate(()));
-    let fq = Rc::new(F2m::generate(n));
+    let f2 = Arc::new(F2::generate(()));
+    let fq = Arc::new(F2m::generate(n));
     let goppa = Goppa::<F2m>::random(fq, n, t);
     info!("{}", goppa);
 
-    let msg = RowVec::random(Rc::clone(&f2), k);
+    let msg = RowVec::random(Arc::clone(&f2), k);
     info!("Message:{}", msg);
 
     let cdw = goppa.encode(&msg);
     info!("Codeword:{}", cdw);
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), n, t);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), n, t);
     info!("Error:{}", err);
 
     let rcv = &cdw + err;
@@ -151,15 +156,15 @@ fn goppa_random() {
     let (q, n, t) = common::goppa_setup();
     info!("F2m=F{}, n={}, t={}", q, n, t);
 
-    let f2 = Rc::new(F2::generate(()));
-    let f2m = Rc::new(F2m::generate(q));
+    let f2 = Arc::new(F2::generate(()));
+    let f2m = Arc::new(F2m::generate(q));
     let goppa = Goppa::random(f2m, n, t);
     info!("{}", goppa);
 
     let xyz = goppa.parity_check_xyz();
     info!("XYZ:{}", xyz);
 
-    let h = Goppa::parity_check_from_xyz(&xyz, Rc::clone(&f2));
+    let h = Goppa::parity_check_from_xyz(&xyz);
     info!("Parity-check matrix H:{}", h);
 
     let (g, _) = Goppa::<F2m>::generator_from_parity_check(&h);
@@ -168,13 +173,13 @@ fn goppa_random() {
     let k = g.rows();
     info!("Code dimension k = {}", k);
 
-    let msg = RowVec::random(Rc::clone(&f2), k);
+    let msg = RowVec::random(Arc::clone(&f2), k);
     info!("Message:{}", msg);
 
     let cdw = &msg * &g;
     info!("Codeword:{}", cdw);
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), n, t);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), n, t);
     info!("Error:{}", err);
 
     let rcv = &cdw + &err;
@@ -186,6 +191,121 @@ fn goppa_random() {
     assert_eq!(cdw, dcdw);
 }
 
+#[test]
+fn goppa_parity_check_structure() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let f2 = Arc::new(F2::generate(()));
+    let f2m = Arc::new(F2m::generate(q));
+    let goppa = Goppa::random(Arc::clone(&f2m), n, t);
+    let x = goppa.parity_check_x();
+    let mut yz = goppa.parity_check_y();
+    let g_inv = goppa.g_inv();
+    for i in 0..t {
+        for j in 0..n {
+            yz[(i, j)] = f2m.mul(yz[(i, j)], g_inv[j]);
+        }
+    }
+    assert_eq!(goppa.parity_check_xyz(), &x * &yz);
+    assert_eq!(goppa.parity_check_alternant(), yz);
+    assert_eq!(
+        goppa.parity_check_alternant_binary(),
+        BitMat::binary(&goppa.parity_check_alternant())
+    );
+
+    // The alternant matrix defines the same code
+    let g = goppa.generator_matrix();
+    let h = goppa.parity_check_alternant_binary();
+    assert!((&h * &g.transpose()).is_zero());
+
+    let xyz = goppa.parity_check_xyz();
+    let rcv = RowVec::random(f2, n);
+    assert_eq!(goppa.syndrome(&rcv), Goppa::syndrome_from_xyz(&xyz, &rcv));
+    assert_eq!(
+        goppa.alternant_syndrome(&rcv),
+        goppa.alternant_syndrome_from_g_inv(&g_inv, &rcv)
+    );
+}
+
+#[test]
+fn goppa_root_finding() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let f2 = Arc::new(F2::generate(()));
+    let f2m = Arc::new(F2m::generate(q));
+    let goppa = Goppa::random(f2m, n, t);
+    let g = goppa.generator_matrix();
+    let cdw = &RowVec::random(Arc::clone(&f2), g.rows()) * &g;
+    let rcv = &cdw + &RowVec::random_with_weight(f2, n, t);
+    for &method in &[
+        RootFinding::Horner,
+        RootFinding::Multipoint,
+        RootFinding::BerlekampTrace,
+        RootFinding::AdditiveFft,
+    ] {
+        assert_eq!(goppa.decode_with(&rcv, method), cdw, "{:?}", method);
+    }
+}
+
+#[test]
+fn goppa_decode_alternant() {
+    let f2 = Arc::new(F2::generate(()));
+    for _r in 0..REPEAT {
+        let (q, n, t) = common::goppa_setup();
+        info!("F2m=F{}, n={}, t={}", q, n, t);
+
+        let f2m = Arc::new(F2m::generate(q));
+        let goppa = Goppa::random(f2m, n, t);
+        let g = goppa.generator_matrix();
+        let cdw = &RowVec::random(Arc::clone(&f2), g.rows()) * &g;
+        for w in 0..=t {
+            let rcv = &cdw + &RowVec::random_with_weight(Arc::clone(&f2), n, w);
+            assert_eq!(goppa.decode_alternant(&rcv), cdw);
+            assert_eq!(goppa.decode_alternant(&rcv), goppa.decode(&rcv));
+        }
+    }
+}
+
+#[test]
+fn goppa_decoder() {
+    let f2 = Arc::new(F2::generate(()));
+    let decoders: Vec<Box<dyn Decoder<F2m>>> = vec![
+        Box::new(Patterson::new()),
+        Box::new(BerlekampMassey::new()),
+        Box::new(Patterson::with_root_finding(RootFinding::BerlekampTrace)),
+        Box::new(BerlekampMassey::with_root_finding(RootFinding::Multipoint)),
+    ];
+    for _r in 0..REPEAT {
+        let (q, n, t) = common::goppa_setup();
+        info!("F2m=F{}, n={}, t={}", q, n, t);
+
+        let f2m = Arc::new(F2m::generate(q));
+        let goppa = Goppa::random(f2m, n, t);
+        let g = goppa.generator_matrix();
+        let cdw = &RowVec::random(Arc::clone(&f2), g.rows()) * &g;
+        let pre = Precomputed::new(&goppa);
+        for decoder in decoders.iter() {
+            for w in 0..=t {
+                let err = RowVec::random_with_weight(Arc::clone(&f2), n, w);
+                let rcv = &cdw + &err;
+                let syndrome = decoder.syndrome(&goppa, &pre, &rcv);
+                assert_eq!(decoder.decode_syndrome(&goppa, &syndrome).unwrap(), err);
+                assert_eq!(decoder.error(&goppa, &rcv).unwrap(), err);
+                assert_eq!(decoder.decode(&goppa, &rcv).unwrap(), cdw);
+            }
+
+            // Beyond t errors, the decoder fails or finds another codeword
+            let err = RowVec::random_with_weight(Arc::clone(&f2), n, t + 1);
+            match decoder.error(&goppa, &(&cdw + &err)) {
+                Ok(e) => assert!(e.weight() <= t && e != err),
+                Err(e) => assert!(matches!(e, mceliece::Error::DecodingFailure)),
+            }
+        }
+    }
+}
+
 #[test]
 fn goppa_repeat() {
     common::log_setup();
@@ -202,15 +322,15 @@ fn goppa_repeat() {
 }
 
 fn goppa_repeated(q: usize, n: usize, t: usize) {
-    let f2 = Rc::new(F2::generate(()));
-    let fq = Rc::new(F2m::generate(q));
+    let f2 = Arc::new(F2::generate(()));
+    let fq = Arc::new(F2m::generate(q));
     let goppa = Goppa::<F2m>::random(fq, n, t);
     info!("{}", goppa);
 
     let xyz = goppa.parity_check_xyz();
     info!("XYZ:{}", xyz);
 
-    let h = Goppa::parity_check_from_xyz(&xyz, Rc::clone(&f2));
+    let h = Goppa::parity_check_from_xyz(&xyz);
     info!("Parity-check matrix H:{}", h);
 
     let (g, _) = Goppa::<F2m>::generator_from_parity_check(&h);
@@ -219,13 +339,13 @@ fn goppa_repeated(q: usize, n: usize, t: usize) {
     let k = g.rows();
     info!("Code dimension k = {}", k);
 
-    let msg = RowVec::random(Rc::clone(&f2), k);
+    let msg = RowVec::random(Arc::clone(&f2), k);
     info!("Message:{}", msg);
 
     let cdw = &msg * &g;
     info!("Codeword:{}", cdw);
 
-    let err = RowVec::random_with_weight(Rc::clone(&f2), n, t);
+    let err = RowVec::random_with_weight(Arc::clone(&f2), n, t);
     info!("Error:{}", err);
 
     let rcv = &cdw + &err;
@@ -0,0 +1,58 @@
+use log::info;
+use rand::Rng;
+
+use mceliece::crypto::{hybrid::*, *};
+
+pub mod common;
+
+#[test]
+fn hybrid_encrypt_decrypt() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let mut rng = rand::thread_rng();
+    for &len in &[0, 1, 1000, CHUNK_LEN, 2 * CHUNK_LEN + 5] {
+        let m: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let mut c = Vec::new();
+        encrypt(&pk, &m[..], &mut c).unwrap();
+        let mut d = Vec::new();
+        decrypt(&sk, &c[..], &mut d).unwrap();
+        assert_eq!(d, m);
+    }
+}
+
+#[test]
+fn hybrid_tampered_ciphertext() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let m = [0x42; 100];
+    let mut c = Vec::new();
+    encrypt(&pk, &m[..], &mut c).unwrap();
+    let last = c.len() - 1;
+    c[last] ^= 1;
+    assert!(matches!(
+        decrypt(&sk, &c[..], &mut Vec::new()),
+        Err(mceliece::Error::Malformed(_))
+    ));
+}
+
+#[test]
+fn hybrid_truncated_ciphertext() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let m = vec![0x42; CHUNK_LEN + 1];
+    let mut c = Vec::new();
+    encrypt(&pk, &m[..], &mut c).unwrap();
+
+    // Drop the last chunk (1 byte of plaintext and its 16 bytes tag)
+    c.truncate(c.len() - 17);
+    assert!(matches!(
+        decrypt(&sk, &c[..], &mut Vec::new()),
+        Err(mceliece::Error::Malformed(_))
+    ));
+}
@@ -0,0 +1,49 @@
+use log::info;
+
+use mceliece::crypto::{isd::*, params::*};
+
+pub mod common;
+
+#[test]
+fn isd_ordering() {
+    common::log_setup();
+    for params in PARAMETER_SETS.iter() {
+        let wf = WorkFactors::new(params.n, params.k(), params.t);
+        info!("{}: {}", params, wf);
+        assert!(wf.lee_brickell <= wf.prange);
+        assert!(wf.stern <= wf.prange);
+        assert!(wf.dumer <= wf.stern);
+        assert!(wf.bjmm <= wf.dumer);
+        assert_eq!(wf.security(), wf.bjmm);
+    }
+}
+
+#[test]
+fn isd_monotonic() {
+    common::log_setup();
+    let mut previous = 0.0;
+    for &t in &[10, 20, 30, 40] {
+        let n = 1024;
+        let security = WorkFactors::new(n, n - 10 * t, t).security();
+        info!("n = {}, t = {}: {:.1} bits", n, t, security);
+        assert!(security > previous);
+        previous = security;
+    }
+}
+
+#[test]
+fn isd_mceliece348864() {
+    common::log_setup();
+    let params = ParameterSet::from_name("mceliece348864").unwrap();
+    let (n, k, t) = (params.n, params.k(), params.t);
+    assert_eq!(prange(n, k, t), WorkFactors::new(n, k, t).prange);
+    assert!((165.0..185.0).contains(&prange(n, k, t)));
+    assert!((135.0..155.0).contains(&stern(n, k, t)));
+    assert!((120.0..145.0).contains(&bjmm(n, k, t)));
+}
+
+#[test]
+#[should_panic]
+fn isd_invalid() {
+    prange(100, 50, 51);
+}
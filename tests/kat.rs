@@ -0,0 +1,61 @@
+use log::info;
+
+use mceliece::crypto::{kat::*, ParameterSet};
+
+pub mod common;
+
+#[test]
+fn kat_generate_verify() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let params = ParameterSet::new(n, t);
+    let seed = [5; 32];
+    let kat = generate(&params, &seed, 3);
+    assert_eq!(kat, generate(&params, &seed, 3));
+    assert_eq!(verify(&kat).unwrap(), 3);
+}
+
+#[test]
+fn kat_tampered_record() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let kat = generate(&ParameterSet::new(n, t), &[6; 32], 2);
+    // Flip a bit of the last ciphertext
+    let mut tampered = kat.trim_end().to_string();
+    let last = tampered.pop().unwrap().to_digit(16).unwrap() ^ 1;
+    tampered.push(std::char::from_digit(last, 16).unwrap());
+    assert!(verify(&tampered).is_err());
+}
+
+#[test]
+fn kat_record_new() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let params = ParameterSet::new(n, t);
+    let record = Record::new(&params, 0, &[7; 32]);
+    assert_eq!(record, Record::new(&params, 0, &[7; 32]));
+    assert_ne!(record.c, Record::new(&params, 0, &[8; 32]).c);
+}
+
+#[test]
+fn kat_invalid_header() {
+    // More errors than the code can hold
+    let kat = "n = 10\nt = 100\n";
+    assert!(matches!(
+        verify(kat),
+        Err(mceliece::Error::InvalidParameters(_))
+    ));
+
+    // Reducible field polynomial x^6 + 1
+    let kat = "n = 64\nt = 2\nm = 6\npoly = 41\n";
+    assert!(matches!(verify(kat), Err(mceliece::Error::InvalidField(_))));
+
+    let kat = "t = 2\n";
+    assert!(matches!(
+        verify(kat),
+        Err(mceliece::Error::MissingField("n"))
+    ));
+}
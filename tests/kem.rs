@@ -0,0 +1,95 @@
+use log::info;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use std::sync::Arc;
+
+use mceliece::{
+    crypto::{kem::*, *},
+    finite_field::{Field, F2},
+    goppa::decoder::BerlekampMassey,
+    matrix::RowVec,
+};
+
+pub mod common;
+
+#[test]
+fn kem_encapsulate_decapsulate() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let k = pk.k();
+    let (c, ss) = encapsulate(&pk);
+    assert_eq!(c.cols(), n - k);
+
+    let ds = decapsulate(&sk, &c);
+    assert_eq!(ds, ss);
+}
+
+#[test]
+fn kem_encapsulate_with_rng() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let (c1, ss1) = encapsulate_with_rng(&mut ChaCha20Rng::from_seed([3; 32]), &pk);
+    let (c2, ss2) = encapsulate_with_rng(&mut ChaCha20Rng::from_seed([3; 32]), &pk);
+    assert_eq!(c1, c2);
+    assert_eq!(ss1, ss2);
+    assert_eq!(decapsulate(&sk, &c1), ss1);
+}
+
+#[test]
+fn kem_tampered_ciphertext() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let (mut c, ss) = encapsulate(&pk);
+    c[0] ^= 1;
+    let ds = decapsulate(&sk, &c);
+    assert_ne!(ds, ss);
+}
+
+#[test]
+fn kem_ciphertext_length() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let k = pk.k();
+    let (c, ss) = encapsulate(&pk);
+    let f2 = Arc::new(F2::generate(()));
+
+    let mut truncated = RowVec::zero(Arc::clone(&f2), n - k - 1);
+    for i in 0..n - k - 1 {
+        truncated[i] = c[i];
+    }
+    let rejected = decapsulate(&sk, &truncated);
+    assert_ne!(rejected, ss);
+    assert_eq!(decapsulate(&sk, &truncated), rejected);
+
+    let mut extended = RowVec::zero(f2, n - k + 1);
+    for i in 0..n - k {
+        extended[i] = c[i];
+    }
+    assert_ne!(decapsulate(&sk, &extended), ss);
+}
+
+#[test]
+fn kem_decapsulate_with_context() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let contexts = [
+        sk.context(),
+        sk.context_with_decoder(Box::new(BerlekampMassey::new())),
+    ];
+    for ctx in contexts.iter() {
+        for _ in 0..4 {
+            let (c, ss) = encapsulate(&pk);
+            assert_eq!(decapsulate_with_context(ctx, &c), ss);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use log::info;
+
+use mceliece::crypto::*;
+
+pub mod common;
+
+#[test]
+fn kobara_imai_encrypt_decrypt() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    for len in [0, 1, 43, 500].iter() {
+        let msg: Vec<u8> = (0..*len).map(|i| i as u8).collect();
+        let cpt = pk.encrypt_kobara_imai(&msg);
+        let dmsg = sk.decrypt_kobara_imai(&cpt).unwrap();
+        assert_eq!(dmsg, msg);
+    }
+}
+
+#[test]
+fn kobara_imai_tampered_ciphertext() {
+    let (q, n, t) = common::goppa_setup();
+    info!("F2m=F{}, n={}, t={}", q, n, t);
+
+    let (pk, sk) = keygen(n, t);
+    let msg = b"The quick brown fox jumps over the lazy dog".to_vec();
+    let mut cpt = pk.encrypt_kobara_imai(&msg);
+    let last = cpt.len() - 1;
+    cpt[last] ^= 0x80;
+    assert!(matches!(
+        sk.decrypt_kobara_imai(&cpt),
+        Err(mceliece::Error::InvalidCiphertext)
+    ));
+
+    // Flip a bit of the McEliece ciphertext
+    let mut cpt = pk.encrypt_kobara_imai(&msg);
+    cpt[4] ^= 0x01;
+    assert!(matches!(
+        sk.decrypt_kobara_imai(&cpt),
+        Err(mceliece::Error::InvalidCiphertext)
+    ));
+}
@@ -0,0 +1,148 @@
+use log::info;
+use rand::Rng;
+use std::sync::Arc;
+
+use mceliece::{finite_field::*, matrix::*};
+
+pub mod common;
+
+#[test]
+fn matrix_bit_conversion() {
+    common::log_setup();
+    let f2 = Arc::new(F2::generate(()));
+    let mat = Mat::random(Arc::clone(&f2), 5, 130);
+    let bits = BitMat::from(&mat);
+    info!("Packed matrix:{}", bits);
+    assert_eq!(bits.rows(), 5);
+    assert_eq!(bits.cols(), 130);
+    for i in 0..5 {
+        for j in 0..130 {
+            assert_eq!(bits.get(i, j), mat[(i, j)] == 1);
+        }
+    }
+    assert!(Mat::from(&bits) == mat);
+
+    let vec = RowVec::random(f2, 77);
+    assert!(RowVec::from(&BitVec::from(&vec)) == vec);
+}
+
+#[test]
+fn matrix_bit_mul() {
+    common::log_setup();
+    let mut rng = rand::thread_rng();
+    let f2 = Arc::new(F2::generate(()));
+    let (l, m, n) = (
+        rng.gen_range(1, 100),
+        rng.gen_range(1, 100),
+        rng.gen_range(1, 200),
+    );
+    let a = Mat::random(Arc::clone(&f2), l, m);
+    let b = Mat::random(Arc::clone(&f2), m, n);
+    let prod = BitMat::from(&a) * BitMat::from(&b);
+    assert!(Mat::from(&prod) == &a * &b);
+    assert_eq!(BitMat::from(&b).transpose(), BitMat::from(&b.transpose()));
+
+    let p = Perm::random(n);
+    assert_eq!(BitMat::from(&b) * &p, BitMat::from(&(&b * &p)));
+
+    let v = RowVec::random(Arc::clone(&f2), m);
+    assert!(&v * &BitMat::from(&b) == &v * &b);
+
+    let h = BitMat::random(l, n);
+    let e = BitVec::random(n);
+    let syndrome = h.mul_transpose(&e);
+    assert_eq!(syndrome.0, (&h * &e.0.transpose()).transpose());
+}
+
+#[test]
+fn matrix_bit_inverse() {
+    common::log_setup();
+    let id = BitMat::identity(70);
+    assert!(id.is_invertible());
+    assert_eq!(id.inverse(), Some(id.clone()));
+
+    let s = BitMat::invertible_random(70);
+    let inv = s.inverse().expect("Failed to invert an invertible matrix");
+    assert_eq!(&s * &inv, id);
+
+    let mut singular = BitMat::random(70, 70);
+    for j in 0..70 {
+        let bit = singular.get(5, j);
+        singular.set(3, j, bit);
+    }
+    assert!(!singular.is_invertible());
+    assert_eq!(singular.inverse(), None);
+}
+
+#[test]
+fn matrix_bit_standard_form() {
+    common::log_setup();
+    let mat = BitMat::identity(19);
+    assert!(mat.is_standard_form());
+    let (u, h, p) = mat
+        .standard_form()
+        .expect("Cannot recognize the identity matrix as a standard form");
+    assert_eq!(u, mat);
+    assert_eq!(h, mat);
+    assert!(p.is_permutation());
+
+    let h = BitMat::hconcat(&BitMat::random(73, 131), &BitMat::invertible_random(73));
+    let (u, s, p) = h
+        .standard_form()
+        .expect("Failed to put a full rank matrix in standard form");
+    assert!(u.is_invertible());
+    assert!(s.is_standard_form());
+    assert!(p.is_permutation());
+    assert_eq!(s, u * h * p);
+}
+
+#[test]
+fn matrix_bit_write_read() {
+    common::log_setup();
+    let f2 = Arc::new(F2::generate(()));
+    let mat = Mat::random(Arc::clone(&f2), 13, 29);
+    let bits = BitMat::from(&mat);
+    assert_eq!(bits.to_bytes(), mat.to_bytes());
+    let (read, bits_read) = BitMat::from_bytes(&mat.to_bytes()).unwrap();
+    assert_eq!(read, mat.to_bytes().len());
+    assert_eq!(bits_read, bits);
+
+    let vec = RowVec::random(f2, 45);
+    let bitvec = BitVec::from(&vec);
+    assert_eq!(bitvec.to_bytes(), vec.to_bytes());
+    assert_eq!(BitVec::from_bytes(&vec.to_bytes()).unwrap().1, bitvec);
+}
+
+#[test]
+fn matrix_bit_m4rm() {
+    common::log_setup();
+    let f2 = Arc::new(F2::generate(()));
+    let a = Mat::random(Arc::clone(&f2), 150, 203);
+    let b = Mat::random(Arc::clone(&f2), 203, 97);
+    let prod = BitMat::from(&a) * BitMat::from(&b);
+    assert!(Mat::from(&prod) == &a * &b);
+}
+
+#[test]
+fn matrix_bit_m4ri() {
+    common::log_setup();
+    let f2 = Arc::new(F2::generate(()));
+    let mat = Mat::random(Arc::clone(&f2), 120, 90);
+    let mut bits = BitMat::from(&mat);
+    let pivots = bits.reduced_row_echelon_form();
+    info!("Pivots: {:?}", pivots);
+    assert_eq!(pivots.len(), mat.rank());
+    assert!(pivots.windows(2).all(|w| w[0] < w[1]));
+    for (i, &j) in pivots.iter().enumerate() {
+        assert!((0..j).all(|l| !bits.get(i, l)));
+        assert!((0..bits.rows()).all(|l| bits.get(l, j) == (l == i)));
+    }
+    assert!((pivots.len()..bits.rows()).all(|i| bits.ones(i).next().is_none()));
+
+    let h = BitMat::random(60, 200);
+    let (s, p) = h.standard_parity_check_equivalent();
+    assert!(s.is_standard_form());
+    assert_eq!(s.rank(), h.rank());
+    let g = mceliece::goppa::Goppa::<F2m>::generator_from_parity_check_standard(&s);
+    assert!((&h * &(g * p.inverse()).transpose()).is_zero());
+}
@@ -1,6 +1,6 @@
 use log::info;
 use rand::Rng;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use mceliece::{finite_field::*, matrix::*};
 
@@ -16,8 +16,8 @@ fn matrix_f1024_permutation_random() {
 #[test]
 fn matrix_f1024_is_invertible() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let id = Mat::identity(Rc::clone(&f1024), 11);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let id = Mat::identity(Arc::clone(&f1024), 11);
     info!("Matrix identity:{:?}", id);
     assert!(id.is_invertible());
 }
@@ -25,16 +25,16 @@ fn matrix_f1024_is_invertible() {
 #[test]
 fn matrix_f1024_inverse() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let id = Mat::identity(Rc::clone(&f1024), 11);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let id = Mat::identity(Arc::clone(&f1024), 11);
     assert_eq!(id.inverse().as_ref(), Some(&id));
 }
 
 #[test]
 fn matrix_f1024_invertible_random() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let mat = Mat::invertible_random(Rc::clone(&f1024), 15);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let mat = Mat::invertible_random(Arc::clone(&f1024), 15);
     assert!(mat.is_invertible());
 
     assert_eq!(
@@ -46,18 +46,18 @@ fn matrix_f1024_invertible_random() {
     );
 
     let prod = &mat * &mat.inverse().expect("Cannot inverse invertible matrix");
-    let id = Mat::identity(Rc::clone(&f1024), 15);
+    let id = Mat::identity(Arc::clone(&f1024), 15);
     assert_eq!(prod, id);
 }
 
 #[test]
 fn matrix_f1024_add() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let a = &Mat::random(Rc::clone(&f1024), 11, 11);
-    let b = &Mat::random(Rc::clone(&f1024), 11, 11);
-    let c = &Mat::random(Rc::clone(&f1024), 11, 11);
-    let z = &Mat::zero(Rc::clone(&f1024), 11, 11);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let a = &Mat::random(Arc::clone(&f1024), 11, 11);
+    let b = &Mat::random(Arc::clone(&f1024), 11, 11);
+    let c = &Mat::random(Arc::clone(&f1024), 11, 11);
+    let z = &Mat::zero(Arc::clone(&f1024), 11, 11);
 
     // Associativity
     assert_eq!((a + b) + c, a + (b + c));
@@ -76,9 +76,9 @@ fn matrix_f1024_add() {
 #[should_panic]
 fn matrix_f1024_mul_wrong_dimensions() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let a = Mat::zero(Rc::clone(&f1024), 5, 4);
-    let b = Mat::zero(Rc::clone(&f1024), 3, 5);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let a = Mat::zero(Arc::clone(&f1024), 5, 4);
+    let b = Mat::zero(Arc::clone(&f1024), 3, 5);
     let ab = a * b;
     assert!(ab.is_zero());
 }
@@ -86,32 +86,32 @@ fn matrix_f1024_mul_wrong_dimensions() {
 #[test]
 fn matrix_f1024_mul() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let a = &Mat::random(Rc::clone(&f1024), 10, 8);
-    let b = &Mat::random(Rc::clone(&f1024), 8, 13);
-    let c = &Mat::random(Rc::clone(&f1024), 13, 4);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let a = &Mat::random(Arc::clone(&f1024), 10, 8);
+    let b = &Mat::random(Arc::clone(&f1024), 8, 13);
+    let c = &Mat::random(Arc::clone(&f1024), 13, 4);
 
     // Associativity
     assert_eq!((a * b) * c, a * (b * c));
 
     // Neutral element
-    let i8 = &Mat::identity(Rc::clone(&f1024), 8);
-    let i10 = &Mat::identity(Rc::clone(&f1024), 10);
+    let i8 = &Mat::identity(Arc::clone(&f1024), 8);
+    let i10 = &Mat::identity(Arc::clone(&f1024), 10);
     assert_eq!(a * i8, *a);
     assert_eq!(i10 * a, *a);
 
     // Zero case
-    let z8 = &Mat::zero(Rc::clone(&f1024), 8, 8);
-    let z10 = &Mat::zero(Rc::clone(&f1024), 10, 10);
-    let z10_8 = &Mat::zero(Rc::clone(&f1024), 10, 8);
+    let z8 = &Mat::zero(Arc::clone(&f1024), 8, 8);
+    let z10 = &Mat::zero(Arc::clone(&f1024), 10, 10);
+    let z10_8 = &Mat::zero(Arc::clone(&f1024), 10, 8);
     assert_eq!(a * z8, *z10_8);
     assert_eq!(z10 * a, *z10_8);
 
     // Distributivity
-    let a = &Mat::random(Rc::clone(&f1024), 10, 12);
-    let b = &Mat::random(Rc::clone(&f1024), 10, 12);
-    let c = &Mat::random(Rc::clone(&f1024), 12, 9);
-    let d = &Mat::random(Rc::clone(&f1024), 12, 9);
+    let a = &Mat::random(Arc::clone(&f1024), 10, 12);
+    let b = &Mat::random(Arc::clone(&f1024), 10, 12);
+    let c = &Mat::random(Arc::clone(&f1024), 12, 9);
+    let d = &Mat::random(Arc::clone(&f1024), 12, 9);
 
     // Left: (a + b)c = ac + bc
     assert_eq!((a + b) * c, a * c + b * c);
@@ -123,19 +123,19 @@ fn matrix_f1024_mul() {
 #[test]
 fn matrix_f1024_rank() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let mat = Mat::zero(Rc::clone(&f1024), 23, 4);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let mat = Mat::zero(Arc::clone(&f1024), 23, 4);
     assert_eq!(mat.rank(), 0);
 
-    let mat = Mat::identity(Rc::clone(&f1024), 19);
+    let mat = Mat::identity(Arc::clone(&f1024), 19);
     assert_eq!(mat.rank(), 19);
 }
 
 #[test]
 fn matrix_f1024_standard_form() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let id = Mat::identity(Rc::clone(&f1024), 19);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let id = Mat::identity(Arc::clone(&f1024), 19);
     assert!(id.is_standard_form());
 
     let (u, h, p) = id
@@ -145,8 +145,8 @@ fn matrix_f1024_standard_form() {
     assert_eq!(h, id);
     assert!(p.is_permutation());
 
-    let mut h = Mat::random(Rc::clone(&f1024), 13, 31);
-    let inv = Mat::invertible_random(Rc::clone(&f1024), 13);
+    let mut h = Mat::random(Arc::clone(&f1024), 13, 31);
+    let inv = Mat::invertible_random(Arc::clone(&f1024), 13);
     for i in 0..13 {
         for j in 0..13 {
             h[(i, j)] = inv[(i, j)];
@@ -168,10 +168,10 @@ fn matrix_f1024_standard_form() {
 fn matrix_f1024_random_standard_form() {
     common::log_setup();
     let mut rng = rand::thread_rng();
-    let f1024 = Rc::new(F2m::generate(1024));
+    let f1024 = Arc::new(F2m::generate(1024));
     let n = rng.gen_range(2, 50);
     let k = rng.gen_range(1, n);
-    let h = Mat::random(Rc::clone(&f1024), n - k, n);
+    let h = Mat::random(Arc::clone(&f1024), n - k, n);
     if let Some((u, s, p)) = h.random_standard_form() {
         info!("Invertible matrix U:{:?}", u);
         info!("Standard form matrix S:{:?}", s);
@@ -189,11 +189,11 @@ fn matrix_f1024_random_standard_form() {
 #[test]
 fn matrix_f1024_transpose() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
+    let f1024 = Arc::new(F2m::generate(1024));
     let mut rng = rand::thread_rng();
     let rows = rng.gen_range(1, 100);
     let cols = rng.gen_range(1, 100);
-    let mat = Mat::random(Rc::clone(&f1024), rows, cols);
+    let mat = Mat::random(Arc::clone(&f1024), rows, cols);
     let tmat = mat.transpose();
     for i in 0..rows {
         for j in 0..cols {
@@ -205,10 +205,10 @@ fn matrix_f1024_transpose() {
 #[test]
 fn matrix_f1024_rowvec_weight() {
     common::log_setup();
-    let f1024 = Rc::new(F2m::generate(1024));
-    let vec = RowVec::zero(Rc::clone(&f1024), 4);
+    let f1024 = Arc::new(F2m::generate(1024));
+    let vec = RowVec::zero(Arc::clone(&f1024), 4);
     assert!(vec.weight() == 0);
 
-    let vec = RowVec::random_with_weight(Rc::clone(&f1024), 35, 13);
+    let vec = RowVec::random_with_weight(Arc::clone(&f1024), 35, 13);
     assert_eq!(vec.weight(), 13);
 }
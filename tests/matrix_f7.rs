@@ -1,6 +1,6 @@
 use log::info;
 use rand::Rng;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use mceliece::{finite_field::*, matrix::*};
 
@@ -16,8 +16,8 @@ fn matrix_f7_permutation_random() {
 #[test]
 fn matrix_f7_is_invertible() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let id = Mat::identity(Rc::clone(&f7), 11);
+    let f7 = Arc::new(F7::generate(()));
+    let id = Mat::identity(Arc::clone(&f7), 11);
     info!("Identity matrix:{}", id);
     assert!(id.is_invertible());
 }
@@ -25,8 +25,8 @@ fn matrix_f7_is_invertible() {
 #[test]
 fn matrix_f7_inverse() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let id = Mat::identity(Rc::clone(&f7), 11);
+    let f7 = Arc::new(F7::generate(()));
+    let id = Mat::identity(Arc::clone(&f7), 11);
     assert!(id.inverse().as_ref() == Some(&id));
 
     let p = Perm::random(11);
@@ -36,8 +36,8 @@ fn matrix_f7_inverse() {
 #[test]
 fn matrix_f7_invertible_random() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let mat = Mat::invertible_random(Rc::clone(&f7), 15);
+    let f7 = Arc::new(F7::generate(()));
+    let mat = Mat::invertible_random(Arc::clone(&f7), 15);
     assert!(mat.is_invertible());
     assert!(
         mat.inverse()
@@ -48,18 +48,18 @@ fn matrix_f7_invertible_random() {
     );
 
     let prod = &mat * &mat.inverse().expect("Cannot inverse invertible matrix");
-    let id = Mat::identity(Rc::clone(&f7), 15);
+    let id = Mat::identity(Arc::clone(&f7), 15);
     assert!(prod == id);
 }
 
 #[test]
 fn matrix_f7_add() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let a = &Mat::random(Rc::clone(&f7), 11, 11);
-    let b = &Mat::random(Rc::clone(&f7), 11, 11);
-    let c = &Mat::random(Rc::clone(&f7), 11, 11);
-    let z = &Mat::zero(Rc::clone(&f7), 11, 11);
+    let f7 = Arc::new(F7::generate(()));
+    let a = &Mat::random(Arc::clone(&f7), 11, 11);
+    let b = &Mat::random(Arc::clone(&f7), 11, 11);
+    let c = &Mat::random(Arc::clone(&f7), 11, 11);
+    let z = &Mat::zero(Arc::clone(&f7), 11, 11);
 
     // Associativity
     assert!((a + b) + c == a + (b + c));
@@ -71,7 +71,7 @@ fn matrix_f7_add() {
     assert!(a + z == *a);
 
     // Characteristic
-    let mut s = Mat::zero(Rc::clone(&f7), 11, 11);
+    let mut s = Mat::zero(Arc::clone(&f7), 11, 11);
     for _i in 0..7 {
         s += a;
     }
@@ -82,9 +82,9 @@ fn matrix_f7_add() {
 #[should_panic]
 fn matrix_f7_mul_wrong_dimensions() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let a = Mat::zero(Rc::clone(&f7), 5, 4);
-    let b = Mat::zero(Rc::clone(&f7), 3, 5);
+    let f7 = Arc::new(F7::generate(()));
+    let a = Mat::zero(Arc::clone(&f7), 5, 4);
+    let b = Mat::zero(Arc::clone(&f7), 3, 5);
     let ab = a * b;
     assert!(ab.is_zero());
 }
@@ -92,32 +92,32 @@ fn matrix_f7_mul_wrong_dimensions() {
 #[test]
 fn matrix_f7_mul() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let a = &Mat::random(Rc::clone(&f7), 10, 8);
-    let b = &Mat::random(Rc::clone(&f7), 8, 13);
-    let c = &Mat::random(Rc::clone(&f7), 13, 4);
+    let f7 = Arc::new(F7::generate(()));
+    let a = &Mat::random(Arc::clone(&f7), 10, 8);
+    let b = &Mat::random(Arc::clone(&f7), 8, 13);
+    let c = &Mat::random(Arc::clone(&f7), 13, 4);
 
     // Associativity
     assert!((a * b) * c == a * (b * c));
 
     // Neutral element
-    let i8 = &Mat::identity(Rc::clone(&f7), 8);
-    let i10 = &Mat::identity(Rc::clone(&f7), 10);
+    let i8 = &Mat::identity(Arc::clone(&f7), 8);
+    let i10 = &Mat::identity(Arc::clone(&f7), 10);
     assert!(a * i8 == *a);
     assert!(i10 * a == *a);
 
     // Zero case
-    let z8 = &Mat::zero(Rc::clone(&f7), 8, 8);
-    let z10 = &Mat::zero(Rc::clone(&f7), 10, 10);
-    let z10_8 = &Mat::zero(Rc::clone(&f7), 10, 8);
+    let z8 = &Mat::zero(Arc::clone(&f7), 8, 8);
+    let z10 = &Mat::zero(Arc::clone(&f7), 10, 10);
+    let z10_8 = &Mat::zero(Arc::clone(&f7), 10, 8);
     assert!(a * z8 == *z10_8);
     assert!(z10 * a == *z10_8);
 
     // Distributivity
-    let a = &Mat::random(Rc::clone(&f7), 10, 12);
-    let b = &Mat::random(Rc::clone(&f7), 10, 12);
-    let c = &Mat::random(Rc::clone(&f7), 12, 9);
-    let d = &Mat::random(Rc::clone(&f7), 12, 9);
+    let a = &Mat::random(Arc::clone(&f7), 10, 12);
+    let b = &Mat::random(Arc::clone(&f7), 10, 12);
+    let c = &Mat::random(Arc::clone(&f7), 12, 9);
+    let d = &Mat::random(Arc::clone(&f7), 12, 9);
 
     // Left: (a + b)c = ac + bc
     assert!((a + b) * c == a * c + b * c);
@@ -126,22 +126,88 @@ fn matrix_f7_mul() {
     assert!(a * (c + d) == a * c + a * d);
 }
 
+#[test]
+fn matrix_f7_rowvec_mul() {
+    common::log_setup();
+    let f7 = Arc::new(F7::generate(()));
+    let a = Mat::random(Arc::clone(&f7), 30, 600);
+    let v = RowVec::random(Arc::clone(&f7), 30);
+
+    // Matrices can be moved to other threads
+    let (a, v) = std::thread::spawn(move || (a, v)).join().unwrap();
+    assert!(&v * &a == RowVec(&v.0 * &a));
+}
+
+#[test]
+fn matrix_f7_reduced_row_echelon_form() {
+    common::log_setup();
+    let f7 = Arc::new(F7::generate(()));
+
+    // Rows above a pivot are reduced by a multiple of the whole pivot row
+    let mut mat = Mat::new(
+        Arc::clone(&f7),
+        3,
+        5,
+        vec![1, 2, 3, 4, 5, 0, 1, 4, 0, 6, 0, 0, 0, 1, 2],
+    );
+    mat.reduced_row_echelon_form();
+    let rref = Mat::new(
+        Arc::clone(&f7),
+        3,
+        5,
+        vec![1, 0, 2, 0, 6, 0, 1, 4, 0, 6, 0, 0, 0, 1, 2],
+    );
+    assert!(mat == rref);
+
+    let mut mat = Mat::random(Arc::clone(&f7), 9, 14);
+    let rank = mat.rank();
+    mat.reduced_row_echelon_form();
+    info!("Reduced row echelon form:{}", mat);
+    let mut pivots = 0;
+    for i in 0..mat.rows() {
+        if let Some(j) = (0..mat.cols()).find(|&j| mat[(i, j)] != f7.zero()) {
+            assert!(mat[(i, j)] == f7.one());
+            assert!((0..mat.rows()).all(|k| k == i || mat[(k, j)] == f7.zero()));
+            pivots += 1;
+        }
+    }
+    assert_eq!(pivots, rank);
+}
+
+#[test]
+fn matrix_f7_try_ops() {
+    common::log_setup();
+    let f7 = Arc::new(F7::generate(()));
+    let a = Mat::random(Arc::clone(&f7), 4, 6);
+    let b = Mat::random(Arc::clone(&f7), 6, 3);
+    assert!(a.try_mul(&b).unwrap() == &a * &b);
+    assert!(matches!(
+        a.try_add(&b),
+        Err(mceliece::Error::DimensionMismatch)
+    ));
+    assert!(matches!(
+        Mat::try_new(f7, 2, 3, vec![1; 5]),
+        Err(mceliece::Error::DimensionMismatch)
+    ));
+    assert!(Perm::try_new(vec![0, 2, 2]).is_err());
+}
+
 #[test]
 fn matrix_f7_rank() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let mat = Mat::zero(Rc::clone(&f7), 23, 4);
+    let f7 = Arc::new(F7::generate(()));
+    let mat = Mat::zero(Arc::clone(&f7), 23, 4);
     assert!(mat.rank() == 0);
 
-    let mat = Mat::identity(Rc::clone(&f7), 19);
+    let mat = Mat::identity(Arc::clone(&f7), 19);
     assert!(mat.rank() == 19);
 }
 
 #[test]
 fn matrix_f7_standard_form() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let id = Mat::identity(Rc::clone(&f7), 19);
+    let f7 = Arc::new(F7::generate(()));
+    let id = Mat::identity(Arc::clone(&f7), 19);
     assert!(id.is_standard_form());
 
     let (u, h, p) = id
@@ -151,8 +217,8 @@ fn matrix_f7_standard_form() {
     assert!(h == id);
     assert!(p.is_permutation());
 
-    let mut h = Mat::random(Rc::clone(&f7), 13, 31);
-    let inv = Mat::invertible_random(Rc::clone(&f7), 13);
+    let mut h = Mat::random(Arc::clone(&f7), 13, 31);
+    let inv = Mat::invertible_random(Arc::clone(&f7), 13);
     for i in 0..13 {
         for j in 0..13 {
             h[(i, j)] = inv[(i, j)];
@@ -174,10 +240,10 @@ fn matrix_f7_standard_form() {
 fn matrix_f7_random_standard_form() {
     common::log_setup();
     let mut rng = rand::thread_rng();
-    let f7 = Rc::new(F7::generate(()));
+    let f7 = Arc::new(F7::generate(()));
     let n = rng.gen_range(2, 50);
     let k = rng.gen_range(1, n);
-    let h = Mat::random(Rc::clone(&f7), n - k, n);
+    let h = Mat::random(Arc::clone(&f7), n - k, n);
     if let Some((u, s, p)) = h.random_standard_form() {
         info!("Invertible matrix U:{}", u);
         info!("Standard form matrix S:{}", s);
@@ -196,7 +262,7 @@ fn matrix_f7_random_standard_form() {
 #[test]
 fn matrix_f7_random_standard_form_0() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
+    let f7 = Arc::new(F7::generate(()));
     let h = Mat::new(f7, 2, 3, vec![3, 1, 6, 3, 1, 5]);
     if let Some((u, s, p)) = h.random_standard_form() {
         info!("Invertible matrix U:{}", u);
@@ -215,11 +281,11 @@ fn matrix_f7_random_standard_form_0() {
 #[test]
 fn matrix_f7_transpose() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
+    let f7 = Arc::new(F7::generate(()));
     let mut rng = rand::thread_rng();
     let rows = rng.gen_range(1, 100);
     let cols = rng.gen_range(1, 100);
-    let mat = Mat::random(Rc::clone(&f7), rows, cols);
+    let mat = Mat::random(Arc::clone(&f7), rows, cols);
     let tmat = mat.transpose();
     for i in 0..rows {
         for j in 0..cols {
@@ -231,10 +297,10 @@ fn matrix_f7_transpose() {
 #[test]
 fn matrix_f7_rowvec_weight() {
     common::log_setup();
-    let f7 = Rc::new(F7::generate(()));
-    let vec = RowVec::zero(Rc::clone(&f7), 4);
+    let f7 = Arc::new(F7::generate(()));
+    let vec = RowVec::zero(Arc::clone(&f7), 4);
     assert!(vec.weight() == 0);
 
-    let vec = RowVec::random_with_weight(Rc::clone(&f7), 35, 13);
+    let vec = RowVec::random_with_weight(Arc::clone(&f7), 35, 13);
     assert!(vec.weight() == 13);
 }
@@ -0,0 +1,95 @@
+use log::info;
+use std::sync::Arc;
+
+use mceliece::{
+    crypto::{params::*, *},
+    finite_field::*,
+    matrix::*,
+};
+
+pub mod common;
+
+#[test]
+fn params_presets() {
+    common::log_setup();
+    for params in PARAMETER_SETS.iter() {
+        info!("{}", params);
+        assert_eq!(ParameterSet::from_name(params.name), Some(*params));
+        let f = params.field();
+        assert_eq!(f.order(), 1 << params.m);
+        assert!(params.n <= f.order());
+        assert!(params.n > params.m as usize * params.t);
+    }
+    assert_eq!(ParameterSet::from_name("mceliece1"), None);
+}
+
+#[test]
+fn params_check() {
+    common::log_setup();
+    for params in PARAMETER_SETS.iter() {
+        assert_eq!(params.check(), Ok(()));
+    }
+    assert_eq!(ParameterSet::new(1024, 50).check(), Ok(()));
+    assert!(ParameterSet::new(64, 0).check().is_err());
+    assert!(ParameterSet::new(64, 11).check().is_err());
+    assert!(ParameterSet::new(8, 1).check().is_ok());
+    let mut params = ParameterSet::new(64, 4);
+    params.n = 65;
+    assert!(params.check().is_err());
+    params.n = 64;
+    params.poly = 0x11D;
+    assert!(params.check().is_err());
+}
+
+#[test]
+fn params_key_len() {
+    common::log_setup();
+    for name in &["toy64", "toy256"] {
+        let params = ParameterSet::from_name(name).unwrap();
+        let (pk, sk) = params.keygen();
+        assert_eq!(pk.to_bytes().len(), params.public_key_len());
+        assert_eq!(sk.to_bytes().len(), params.secret_key_len());
+    }
+}
+
+#[test]
+fn params_new() {
+    common::log_setup();
+    let params = ParameterSet::new(1000, 20);
+    assert_eq!(params.m, 10);
+    assert!(params.field() == F2m::generate(1024));
+    assert_eq!(params.k(), 800);
+}
+
+#[test]
+fn params_toy_encrypt_decrypt() {
+    common::log_setup();
+    let params = ParameterSet::from_name("toy64").unwrap();
+    let (pk, sk) = params.keygen();
+    assert_eq!(pk.n(), params.n);
+    assert_eq!(pk.t(), params.t);
+
+    let msg = RowVec::random(Arc::new(F2::generate(())), pk.k());
+    let cpt = pk.encrypt(&msg);
+    assert_eq!(sk.decrypt(&cpt), msg);
+}
+
+#[test]
+fn params_non_default_field_sk_write_read() {
+    common::log_setup();
+    let params = ParameterSet {
+        name: "test",
+        n: 60,
+        t: 3,
+        m: 6,
+        poly: 0x67,
+        security_level: 0,
+    };
+    let (pk, sk) = params.keygen_from_seed(&[9; SEED_LEN]);
+    assert!(*sk.goppa().field() == F2m::from_poly(0x67));
+    let sk_read = SecretKey::from_bytes(&sk.to_bytes()).unwrap();
+    assert!(sk == sk_read);
+
+    let msg = RowVec::random(Arc::new(F2::generate(())), pk.k());
+    assert_eq!(sk_read.decrypt(&pk.encrypt(&msg)), msg);
+}
@@ -1,6 +1,6 @@
 use log::info;
 use rand::Rng;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use mceliece::{finite_field::*, polynomial::*};
 
@@ -9,11 +9,11 @@ pub mod common;
 #[test]
 fn polynomial_f2_division() {
     common::log_setup();
-    let f = Rc::new(F2::generate(()));
-    let a = Poly::support(Rc::clone(&f), &[0, 2, 4]);
+    let f = Arc::new(F2::generate(()));
+    let a = Poly::support(Arc::clone(&f), &[0, 2, 4]);
     info!("a(x) = {:?}\n", a);
 
-    let b = Poly::support(Rc::clone(&f), &[0, 1, 2]);
+    let b = Poly::support(Arc::clone(&f), &[0, 1, 2]);
     info!("b(x) = {:?}\n", b);
 
     let (q, r) = Poly::euclidean_division(&a, &b);
@@ -27,14 +27,14 @@ fn polynomial_f2_division() {
 #[test]
 fn polynomial_f2_gcd() {
     common::log_setup();
-    let f = Rc::new(F2::generate(()));
-    let a = Poly::support(Rc::clone(&f), &[0, 1, 4]);
+    let f = Arc::new(F2::generate(()));
+    let a = Poly::support(Arc::clone(&f), &[0, 1, 4]);
     info!("a(x) = {:?}\n", a);
 
-    let b = Poly::support(Rc::clone(&f), &[0, 1, 2]);
+    let b = Poly::support(Arc::clone(&f), &[0, 1, 2]);
     info!("b(x) = {:?}\n", b);
 
-    let c = Poly::support(Rc::clone(&f), &[0, 1]);
+    let c = Poly::support(Arc::clone(&f), &[0, 1]);
     info!("c(x) = {:?}\n", c);
 
     let ac = &a * &c;
@@ -52,14 +52,14 @@ fn polynomial_f2_gcd() {
 #[test]
 fn polynomial_f2_extended_gcd() {
     common::log_setup();
-    let f = Rc::new(F2::generate(()));
+    let f = Arc::new(F2::generate(()));
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(1, 100);
-    let a = Poly::random(Rc::clone(&f), deg_a);
+    let a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {:?}\n", a);
 
     let deg_b = rng.gen_range(0, deg_a);
-    let b = Poly::random(Rc::clone(&f), deg_b);
+    let b = Poly::random(Arc::clone(&f), deg_b);
     info!("b(x) = {:?}\n", b);
 
     let (d, u, v, a1, b1) = Poly::extended_gcd(&a, &b);
@@ -77,14 +77,14 @@ fn polynomial_f2_extended_gcd() {
 #[test]
 fn polynomial_f7_extended_gcd() {
     common::log_setup();
-    let f = Rc::new(F7::generate(()));
+    let f = Arc::new(F7::generate(()));
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(1, 100);
-    let a = Poly::random(Rc::clone(&f), deg_a);
+    let a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {}\n", a);
 
     let deg_b = rng.gen_range(0, deg_a);
-    let b = Poly::random(Rc::clone(&f), deg_b);
+    let b = Poly::random(Arc::clone(&f), deg_b);
     info!("b(x) = {}\n", b);
 
     let (d, u, v, a1, b1) = Poly::extended_gcd(&a, &b);
@@ -102,14 +102,14 @@ fn polynomial_f7_extended_gcd() {
 #[test]
 fn polynomial_f1024_extended_gcd() {
     common::log_setup();
-    let f = Rc::new(F2m::generate(1024));
+    let f = Arc::new(F2m::generate(1024));
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(1, 100);
-    let a = Poly::random(Rc::clone(&f), deg_a);
+    let a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {:?}\n", a);
 
     let deg_b = rng.gen_range(0, deg_a);
-    let b = Poly::random(Rc::clone(&f), deg_b);
+    let b = Poly::random(Arc::clone(&f), deg_b);
     info!("b(x) = {:?}\n", b);
 
     let (d, u, v, a1, b1) = Poly::extended_gcd(&a, &b);
@@ -129,7 +129,7 @@ fn polynomial_f1024_square() {
     common::log_setup();
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(0, 100);
-    let f1024 = Rc::new(F2m::generate(1024));
+    let f1024 = Arc::new(F2m::generate(1024));
     let mut a = Poly::<F2m>::random(f1024, deg_a);
     info!("a(x) = {:?}\n", a);
 
@@ -141,14 +141,14 @@ fn polynomial_f1024_square() {
 #[test]
 fn polynomial_f1024_modulo() {
     common::log_setup();
-    let f = Rc::new(F2m::generate(1024));
+    let f = Arc::new(F2m::generate(1024));
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(0, 100);
-    let mut a = Poly::random(Rc::clone(&f), deg_a);
+    let mut a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {:?}\n", a);
 
     let deg_g = rng.gen_range(0, 100);
-    let g = Poly::random(Rc::clone(&f), deg_g);
+    let g = Poly::random(Arc::clone(&f), deg_g);
     info!("g(x) = {:?}\n", g);
 
     let (_q, r) = Poly::euclidean_division(&a, &g);
@@ -159,16 +159,16 @@ fn polynomial_f1024_modulo() {
 #[test]
 fn polynomial_f1024_sq_root_mod() {
     common::log_setup();
-    let f = Rc::new(F2m::generate(1024));
+    let f = Arc::new(F2m::generate(1024));
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(0, 11);
-    let a = Poly::random(Rc::clone(&f), deg_a);
+    let a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {:?}\n", a);
 
     let mut b = &a * &a;
     info!("a^2(x) = {:?}\n", b);
 
-    let g = Poly::support(Rc::clone(&f), &[0, 2, 11]);
+    let g = Poly::support(Arc::clone(&f), &[0, 2, 11]);
     b.square_root_modulo(&g);
     info!("b(x) = {:?}\n", b);
 
@@ -178,11 +178,11 @@ fn polynomial_f1024_sq_root_mod() {
 #[test]
 fn polynomial_f1024_inverse_mod() {
     common::log_setup();
-    let f = Rc::new(F2m::generate(1024));
-    let g = Poly::support(Rc::clone(&f), &[0, 2, 11]);
+    let f = Arc::new(F2m::generate(1024));
+    let g = Poly::support(Arc::clone(&f), &[0, 2, 11]);
     let mut rng = rand::thread_rng();
     let deg_a = rng.gen_range(0, 11);
-    let a = Poly::random(Rc::clone(&f), deg_a);
+    let a = Poly::random(Arc::clone(&f), deg_a);
     info!("a(x) = {:?}\n", a);
 
     let inv = a.inverse_modulo(&g);
@@ -190,35 +190,134 @@ fn polynomial_f1024_inverse_mod() {
 
     let mut p = &a * &inv;
     p.modulo(&g);
-    let id = Poly::x_n(Rc::clone(&f), 0);
+    let id = Poly::x_n(Arc::clone(&f), 0);
     assert_eq!(p, id);
 }
 
 #[test]
 fn polynomial_is_irreducible() {
     common::log_setup();
-    let f2 = Rc::new(F2::generate(()));
-    let f1024 = Rc::new(F2m::generate(1024));
+    let f2 = Arc::new(F2::generate(()));
+    let f1024 = Arc::new(F2m::generate(1024));
 
-    let zero = Poly::zero(Rc::clone(&f2), 1);
+    let zero = Poly::zero(Arc::clone(&f2), 1);
     assert!(!zero.is_irreducible());
 
-    let mut constant_poly = Poly::zero(Rc::clone(&f1024), 1);
+    let mut constant_poly = Poly::zero(Arc::clone(&f1024), 1);
     constant_poly[0] = f1024.exp(533);
     assert!(!constant_poly.is_irreducible());
 
-    let p = Poly::support(Rc::clone(&f2), &[0, 1, 2]);
+    let p = Poly::support(Arc::clone(&f2), &[0, 1, 2]);
     assert!(p.is_irreducible());
 
-    let p = Poly::support(Rc::clone(&f2), &[0, 2]);
+    let p = Poly::support(Arc::clone(&f2), &[0, 2]);
     assert!(!p.is_irreducible());
 
-    let p = Poly::support(Rc::clone(&f1024), &[0, 2, 11]);
+    let p = Poly::support(Arc::clone(&f1024), &[0, 2, 11]);
     assert!(p.is_irreducible());
 
-    let p = Poly::support(Rc::clone(&f2), &[0, 3, 10]);
+    let p = Poly::support(Arc::clone(&f2), &[0, 3, 10]);
     assert!(p.is_irreducible());
 
-    let p = Poly::support(Rc::clone(&f1024), &[0, 3, 10]);
+    let p = Poly::support(Arc::clone(&f1024), &[0, 3, 10]);
     assert!(!p.is_irreducible());
 }
+
+#[test]
+fn polynomial_try_ops() {
+    common::log_setup();
+    let f16 = Arc::new(F2m::generate(16));
+    let f32 = Arc::new(F2m::generate(32));
+    let a = Poly::random(Arc::clone(&f16), 5);
+    let b = Poly::random(Arc::clone(&f16), 3);
+    assert!(a.try_add(&b).unwrap() == &a + &b);
+    assert!(a.try_mul(&b).unwrap() == &a * &b);
+
+    let c = Poly::random(f32, 3);
+    assert!(matches!(a.try_sub(&c), Err(mceliece::Error::FieldMismatch)));
+    assert!(matches!(
+        Poly::try_new(f16, Vec::new()),
+        Err(mceliece::Error::Empty)
+    ));
+    assert!(F2m::try_generate(48).is_err());
+    assert!(F2m::try_from_poly(0x15).is_err());
+    assert!(F2m::try_from_poly(0x10001).is_err());
+    // Irreducible polynomial of which x is not a primitive element
+    assert_eq!(F2m::try_from_poly(0x1F).unwrap().order(), 16);
+}
+
+#[test]
+fn polynomial_root_finding() {
+    common::log_setup();
+    let mut rng = rand::thread_rng();
+    for &q in &[4, 8, 256, 1024] {
+        let f = Arc::new(F2m::generate(q));
+        let field = f.to_vec();
+
+        // Random polynomial: all evaluations agree with Horner's
+        let p = Poly::random(Arc::clone(&f), rng.gen_range(0, 20));
+        info!("p(x) = {:?}\n", p);
+        let evals: Vec<_> = field.iter().map(|&a| p.eval(a)).collect();
+        assert!(p.eval_field() == evals);
+        assert!(p.eval_multipoint(&field) == evals);
+
+        // Product of distinct linear factors and of an irreducible polynomial
+        let t = rng.gen_range(1, q.min(40) + 1);
+        let mut elts = field.clone();
+        let mut roots = Vec::new();
+        for _i in 0..t {
+            roots.push(elts.swap_remove(rng.gen_range(0, elts.len())));
+        }
+        let mut sigma = Poly::x_n(Arc::clone(&f), 0);
+        for &r in &roots {
+            sigma *= Poly::new(Arc::clone(&f), vec![r, f.one()]);
+        }
+        sigma *= Poly::random_monic_irreducible(Arc::clone(&f), 2);
+        let mut found = sigma.roots();
+        found.sort_by_key(|&a| f.elt_to_u32(a));
+        roots.sort_by_key(|&a| f.elt_to_u32(a));
+        assert!(found == roots);
+
+        let expected: Vec<bool> = field.iter().map(|a| roots.contains(a)).collect();
+        for &method in &[
+            RootFinding::Horner,
+            RootFinding::Multipoint,
+            RootFinding::BerlekampTrace,
+            RootFinding::AdditiveFft,
+        ] {
+            assert_eq!(sigma.find_roots(&field, method), expected, "{:?}", method);
+        }
+    }
+}
+
+#[test]
+fn polynomial_berlekamp_massey() {
+    common::log_setup();
+    let f = Arc::new(F2m::generate(256));
+    let mut rng = rand::thread_rng();
+
+    // Sequence of the recurrence of connection polynomial c(x) = 1 + c1 x + ... + cl x^l
+    let l = rng.gen_range(1, 10);
+    let mut c = Poly::random(Arc::clone(&f), l);
+    c[0] = f.one();
+    let mut s: Vec<u32> = (0..l).map(|_| f.random_element(&mut rng)).collect();
+    for n in l..2 * l {
+        let mut x = f.zero();
+        for i in 1..=l {
+            x = f.add(x, f.mul(c[i], s[n - i]));
+        }
+        s.push(x);
+    }
+    info!("s = {:?}\n", s);
+
+    let (c1, l1) = Poly::berlekamp_massey(Arc::clone(&f), &s);
+    info!("c(x) = {:?}\n", c1);
+    assert!(l1 <= l);
+    for n in l1..s.len() {
+        let mut x = s[n];
+        for i in 1..=l1.min(c1.degree()) {
+            x = f.add(x, f.mul(c1[i], s[n - i]));
+        }
+        assert_eq!(x, f.zero());
+    }
+}
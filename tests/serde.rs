@@ -0,0 +1,73 @@
+#![cfg(feature = "serde")]
+
+use std::sync::Arc;
+
+use mceliece::{crypto::*, finite_field::*, goppa::Goppa, matrix::*, polynomial::*};
+
+pub mod common;
+
+#[test]
+fn serde_matrix() {
+    common::log_setup();
+    let f7 = Arc::new(F7::generate(()));
+    let mat = Mat::random(Arc::clone(&f7), 5, 8);
+    let json = serde_json::to_string(&mat).unwrap();
+    assert!(serde_json::from_str::<Mat<F7>>(&json).unwrap() == mat);
+
+    let v = RowVec::random(Arc::clone(&f7), 8);
+    let json = serde_json::to_string(&v).unwrap();
+    assert!(serde_json::from_str::<RowVec<F7>>(&json).unwrap() == v);
+    assert!(serde_json::from_str::<ColVec<F7>>(&json).is_err());
+
+    let p = Perm::random(10);
+    assert_eq!(
+        serde_json::from_str::<Perm>(&serde_json::to_string(&p).unwrap()).unwrap(),
+        p
+    );
+    assert!(serde_json::from_str::<Perm>("[0, 2, 2]").is_err());
+
+    let b = BitMat::random(7, 70);
+    assert_eq!(
+        serde_json::from_str::<BitMat>(&serde_json::to_string(&b).unwrap()).unwrap(),
+        b
+    );
+
+    // Coefficients must belong to the field
+    let json = r#"{"field":null,"rows":1,"cols":2,"data":[3,7]}"#;
+    assert!(serde_json::from_str::<Mat<F7>>(json).is_err());
+}
+
+#[test]
+fn serde_shared_field() {
+    common::log_setup();
+    let f64 = Arc::new(F2m::generate(64));
+    let poly = Poly::random(Arc::clone(&f64), 6);
+    let json = serde_json::to_string(&poly).unwrap();
+
+    // Deserialization rebuilds the field or shares the supplied one
+    let rebuilt: Poly<F2m> = serde_json::from_str(&json).unwrap();
+    assert_eq!(rebuilt, poly);
+    assert!(!Arc::ptr_eq(&rebuilt.field(), &f64));
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let shared = Poly::deserialize_with_field(Arc::clone(&f64), &mut de).unwrap();
+    assert_eq!(shared, poly);
+    assert!(Arc::ptr_eq(&shared.field(), &f64));
+
+    // The supplied field must be the serialized one
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert!(Poly::deserialize_with_field(Arc::new(F2m::generate(128)), &mut de).is_err());
+}
+
+#[test]
+fn serde_keys() {
+    common::log_setup();
+    let (pk, sk) = keygen_from_seed(64, 4, &[14; SEED_LEN]);
+    let json = serde_json::to_string(sk.goppa()).unwrap();
+    assert!(serde_json::from_str::<Goppa<F2m>>(&json).unwrap() == *sk.goppa());
+
+    let json = serde_json::to_string(&pk).unwrap();
+    assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), pk);
+    let json = serde_json::to_string(&sk).unwrap();
+    assert!(serde_json::from_str::<SecretKey>(&json).unwrap() == sk);
+    assert!(serde_json::from_str::<SecretKey>("[0, 0, 0, 1]").is_err());
+}
@@ -1,6 +1,6 @@
 // use log::info;
 // use rand::Rng;
-// use std::rc::Rc;
+// use std::sync::Arc;
 
 // use mceliece::{finite_field::*, matrix::*};
 
@@ -9,7 +9,7 @@
 // #[test]
 // fn submat() {
 //     common::log_setup();
-//     let f2 = Rc::new(F2::generate(()));
+//     let f2 = Arc::new(F2::generate(()));
 //     let mut a = Mat::new(
 //         f2,
 //         4,